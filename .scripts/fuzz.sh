@@ -1,3 +1,4 @@
 #!/usr/bin/env bash
 
-cargo fuzz run --features libfuzzer-sys/link_libfuzzer --fuzz-dir oapth-fuzz parsers -- -max_len=10 -runs=100000
\ No newline at end of file
+cargo fuzz run --features libfuzzer-sys/link_libfuzzer --fuzz-dir oapth-fuzz parsers -- -max_len=10 -runs=100000
+cargo fuzz run --features libfuzzer-sys/link_libfuzzer --fuzz-dir oapth-fuzz sql_escaping -- -max_len=10 -runs=100000
\ No newline at end of file
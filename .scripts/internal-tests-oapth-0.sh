@@ -7,16 +7,27 @@ $rt test-with-features oapth chrono
 $rt test-with-features oapth clap
 $rt test-with-features oapth dotenv
 $rt test-with-features oapth orm
+$rt test-with-features oapth orm,chrono
+$rt test-with-features oapth orm,sm
+$rt test-with-features oapth orm,sm,std
+$rt test-with-features oapth pool
+$rt test-with-features oapth postgres
 $rt test-with-features oapth quote
 $rt test-with-features oapth rust_decimal
+$rt test-with-features oapth serde_json
 $rt test-with-features oapth sm
 $rt test-with-features oapth sm-cli
+$rt test-with-features oapth sm-cli,postgres
 $rt test-with-features oapth sm-dev
 $rt test-with-features oapth sm-dev
 $rt test-with-features oapth sqlx-core
 $rt test-with-features oapth sqlx-mysql
+$rt test-with-features oapth sqlx-mysql,sm
 $rt test-with-features oapth sqlx-postgres
+$rt test-with-features oapth sqlx-postgres,sm
 $rt test-with-features oapth sqlx-sqlite
+$rt test-with-features oapth sqlx-sqlite,sm
 $rt test-with-features oapth std
 $rt test-with-features oapth tiberius
-$rt test-with-features oapth tokio
\ No newline at end of file
+$rt test-with-features oapth tokio
+$rt test-with-features oapth uuid
\ No newline at end of file
@@ -34,7 +34,8 @@ macro_rules! add_benchmark_group {
           let rt = Runtime::new().unwrap();
           rt.block_on(async {
             let c = Commands::with_database(SqlxMysql::new(&mysql_config).await.unwrap());
-            $sqlx_mysql(c).await;
+            let fun = $sqlx_mysql;
+            fun(c).await;
           });
         })
       });
@@ -44,7 +45,8 @@ macro_rules! add_benchmark_group {
           let rt = Runtime::new().unwrap();
           rt.block_on(async {
             let c = Commands::with_database(SqlxPostgres::new(&pg_config).await.unwrap());
-            $sqlx_pg(c).await;
+            let fun = $sqlx_pg;
+            fun(c).await;
           });
         })
       });
@@ -54,7 +56,8 @@ macro_rules! add_benchmark_group {
           let rt = Runtime::new().unwrap();
           rt.block_on(async {
             let c = Commands::with_database(SqlxSqlite::new(&sqlite_config).await.unwrap());
-            $sqlx_sqlite(c).await;
+            let fun = $sqlx_sqlite;
+            fun(c).await;
           });
         })
       });
@@ -69,7 +72,8 @@ macro_rules! add_benchmark_group {
             let c = Commands::with_database(
               Tiberius::new(&mssql_config, tcp.compat_write()).await.unwrap(),
             );
-            $tiberius(c).await;
+            let fun = $tiberius;
+            fun(c).await;
           });
         })
       });
@@ -0,0 +1,48 @@
+//! Escape identifiers and literals
+
+#![allow(missing_docs)]
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use oapth::{
+  orm::{write_escaped_identifier, write_escaped_literal},
+  DatabaseTy,
+};
+
+const TYS: [DatabaseTy; 4] =
+  [DatabaseTy::Mssql, DatabaseTy::MySql, DatabaseTy::Postgres, DatabaseTy::Sqlite];
+
+fuzz_target!(|data: &str| {
+  let mut buffer = String::new();
+  for ty in TYS {
+    buffer.clear();
+    write_escaped_identifier(&mut buffer, format_args!("{data}"), ty).unwrap();
+    let (open, close) = match ty {
+      DatabaseTy::Mssql => ('[', ']'),
+      DatabaseTy::MySql => ('`', '`'),
+      _ => ('"', '"'),
+    };
+    let inner = buffer.strip_prefix(open).and_then(|el| el.strip_suffix(close)).unwrap();
+    assert_eq!(unescape(inner, |c| c == close), data);
+
+    buffer.clear();
+    write_escaped_literal(&mut buffer, format_args!("{data}"), ty).unwrap();
+    let prefix = if ty == DatabaseTy::Mssql { "N'" } else { "'" };
+    let inner = buffer.strip_prefix(prefix).and_then(|el| el.strip_suffix('\'')).unwrap();
+    assert_eq!(unescape(inner, |c| c == '\'' || (ty == DatabaseTy::MySql && c == '\\')), data);
+  }
+});
+
+// Panics if an escapable character isn't followed by itself, i.e., if the escaped text could
+// terminate its delimiters prematurely.
+fn unescape(s: &str, is_escapable: impl Fn(char) -> bool) -> String {
+  let mut chars = s.chars();
+  let mut rslt = String::new();
+  while let Some(c) = chars.next() {
+    if is_escapable(c) {
+      assert_eq!(chars.next(), Some(c));
+    }
+    rslt.push(c);
+  }
+  rslt
+}
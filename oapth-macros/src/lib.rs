@@ -1,6 +1,7 @@
 //! Oapth macros
 
 mod embed_migrations;
+mod table;
 
 use proc_macro::{TokenStream, TokenTree};
 
@@ -32,3 +33,42 @@ pub fn embed_migrations(item: TokenStream) -> TokenStream {
   let literal_str = manage_err!(literal_str_opt().ok_or(invalid_path_msg));
   manage_err!(embed_migrations::embed_migrations(literal_str))
 }
+
+/// Implements `oapth::orm::Table` and `oapth::FromRows` for a structure with named fields.
+///
+/// * `#[oapth(alias = "...", error = "...", table = "...")]` on the structure: Optional table
+/// alias, error type that defaults to `oapth::Error` and table name that defaults to the
+/// structure name in snake case.
+/// * `#[oapth(primary_key)]`: Required on at least one field. Up to four fields form a composite
+/// key in declaration order.
+/// * `#[oapth(association(from = "...", to = "..."))]`: Field that is another table, or a `Vec`
+/// of tables, linked by the `from` column of this table and the `to` column of the other table.
+/// Not supported by structures with composite keys.
+///
+/// All other fields are regular columns named after their fields. Every column type must
+/// implement `oapth::FromSqlColumn`.
+///
+/// ```compile_fail
+/// #[derive(oapth_macros::Table)]
+/// struct OrderLine {
+///   #[oapth(primary_key)]
+///   id: i32,
+/// }
+///
+/// #[derive(oapth_macros::Table)]
+/// struct Order {
+///   #[oapth(primary_key)]
+///   code: String,
+///   #[oapth(association(from = "code", to = "order_code"))]
+///   lines: Vec<OrderLine>,
+///   #[oapth(primary_key)]
+///   year: i32,
+/// }
+/// ```
+#[proc_macro_derive(Table, attributes(oapth))]
+pub fn table(item: TokenStream) -> TokenStream {
+  match syn::parse::<syn::DeriveInput>(item).and_then(table::table) {
+    Err(err) => err.to_compile_error().into(),
+    Ok(elem) => elem.into(),
+  }
+}
@@ -0,0 +1,408 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+  ext::IdentExt, spanned::Spanned, Data, DeriveInput, Fields, GenericArgument, Ident, LitStr,
+  PathArguments, Type, TypePath,
+};
+
+// Limited by the tuple implementations of `SqlPrimaryKey`.
+const MAX_PRIMARY_KEYS_LEN: usize = 4;
+// Limited by the tuple implementations of `TableAssociations` and `TableFields`.
+const MAX_TUPLE_LEN: usize = 16;
+
+pub(crate) fn table(input: DeriveInput) -> syn::Result<TokenStream> {
+  let struct_attrs = StructAttrs::new(&input)?;
+  if !input.generics.params.is_empty() {
+    return Err(syn::Error::new(input.generics.span(), "generic structures are not supported"));
+  }
+  let Data::Struct(data) = &input.data else {
+    return Err(syn::Error::new(Span::call_site(), "only structures can derive `Table`"));
+  };
+  let Fields::Named(named) = &data.fields else {
+    return Err(syn::Error::new(data.fields.span(), "only named fields are supported"));
+  };
+
+  let mut associations = Vec::new();
+  let mut fields = Vec::new();
+  let mut primary_keys = Vec::new();
+  for field in &named.named {
+    let Some(ident) = &field.ident else { continue };
+    match FieldKind::new(field)? {
+      FieldKind::Association { from, to } => {
+        associations.push(Association::new(ident, &field.ty, from, to));
+      }
+      FieldKind::PrimaryKey => primary_keys.push((ident, &field.ty)),
+      FieldKind::Regular => fields.push((ident, &field.ty)),
+    }
+  }
+  let Some(&(first_pk_ident, _)) = primary_keys.first() else {
+    return Err(syn::Error::new(
+      Span::call_site(),
+      "at least one field must be marked with `#[oapth(primary_key)]`",
+    ));
+  };
+  if primary_keys.len() > MAX_PRIMARY_KEYS_LEN {
+    return Err(syn::Error::new(Span::call_site(), "at most 4 primary key fields are supported"));
+  }
+  // Associated tables are linked through a single column of the parent
+  if primary_keys.len() > 1 && !associations.is_empty() {
+    return Err(syn::Error::new(
+      Span::call_site(),
+      "associations are not supported by structures with composite primary keys",
+    ));
+  }
+  if associations.len() > MAX_TUPLE_LEN || fields.len() > MAX_TUPLE_LEN {
+    return Err(syn::Error::new(
+      Span::call_site(),
+      "at most 16 associations and 16 regular fields are supported",
+    ));
+  }
+
+  let StructAttrs { alias, error, table } = struct_attrs;
+  let ident = &input.ident;
+  let pk_name = first_pk_ident.unraw().to_string();
+  let pk_names = primary_keys.iter().map(|(pk_ident, _)| pk_ident.unraw().to_string());
+  let table_alias = match &alias {
+    Some(elem) => quote!(::core::option::Option::Some(#elem)),
+    None => quote!(::core::option::Option::None),
+  };
+  let table_or_alias = alias.unwrap_or_else(|| table.clone());
+  let mut pk_decodes = primary_keys.iter().map(|(pk_ident, pk_ty)| {
+    let name = pk_ident.unraw().to_string();
+    quote!(oapth::orm::decode_column_alias::<#pk_ty, R>(
+      buffer_cmd,
+      row,
+      #table_or_alias,
+      table_suffix,
+      #name,
+    ))
+  });
+  // Composite keys are represented by tuples
+  let (pk_decode, pk_ty, pk_value, pk_value_ty) =
+    if let [(pk_ident, pk_ty)] = primary_keys.as_slice() {
+      let decode = pk_decodes.next();
+      (
+        quote!(#decode.map_err(::core::convert::Into::into)),
+        quote!(#pk_ty),
+        quote!(&entity.#pk_ident),
+        quote!(&'entity #pk_ty),
+      )
+    } else {
+      let idents = primary_keys.iter().map(|(pk_ident, _)| pk_ident);
+      let tys = primary_keys.iter().map(|(_, pk_ty)| pk_ty);
+      let value_tys = tys.clone();
+      (
+        quote!(::core::result::Result::Ok((#(#pk_decodes?,)*))),
+        quote!((#(#tys,)*)),
+        quote!((#(&entity.#idents,)*)),
+        quote!((#(&'entity #value_tys,)*)),
+      )
+    };
+
+  let (associations_ty, associations_instances) = if associations.is_empty() {
+    (quote!(oapth::orm::NoTableAssociation<#error>), quote!(oapth::orm::NoTableAssociation::new()))
+  } else {
+    let tys = associations.iter().map(|elem| {
+      let ty = &elem.ty;
+      if elem.is_collection {
+        let storage = replace_generic_argument(elem.field_ty, quote!(oapth::orm::TableParams<'entity, #ty>));
+        quote!(oapth::orm::TableAssociationWrapper<'entity, #ty, #storage>)
+      } else {
+        quote!(oapth::orm::TableAssociationWrapper<'entity, #ty, [oapth::orm::TableParams<'entity, #ty>; 1]>)
+      }
+    });
+    let instances = associations.iter().enumerate().map(|(idx, elem)| {
+      let Association { from, to, .. } = elem;
+      let suffix = suffix_offset(idx);
+      let tables = if elem.is_collection {
+        quote!(::core::default::Default::default())
+      } else {
+        quote!([oapth::orm::TableParams::new(ts + #suffix)])
+      };
+      quote!(
+        oapth::orm::TableAssociationWrapper {
+          association: oapth::orm::TableAssociation::new(#from, #to),
+          guide: oapth::orm::TableParams::new(ts + #suffix),
+          tables: #tables,
+        }
+      )
+    });
+    (quote!((#(#tys,)*)), quote!((#(#instances,)*)))
+  };
+  let ts_param = if associations.is_empty() { quote!(_) } else { quote!(ts) };
+
+  let fields_tys = fields.iter().map(|(_, ty)| quote!(oapth::orm::TableField<&'entity #ty>));
+  let fields_instances = fields.iter().map(|(field_ident, _)| {
+    let name = field_ident.unraw().to_string();
+    quote!(oapth::orm::TableField::new(#name))
+  });
+  let fields_updates = fields.iter().enumerate().map(|(idx, (field_ident, _))| {
+    let idx = syn::Index::from(idx);
+    quote!(*table.fields_mut().#idx.value_mut() = ::core::option::Option::Some(&entity.#field_ident);)
+  });
+  let associations_updates = associations.iter().enumerate().map(|(idx, elem)| {
+    let field_ident = elem.ident;
+    let suffix = suffix_offset(idx);
+    let idx = syn::Index::from(idx);
+    if elem.is_collection {
+      quote!(
+        table.associations_mut().#idx.tables.clear();
+        for elem in entity.#field_ident.iter() {
+          let mut elem_table = oapth::orm::TableParams::new(ts + #suffix);
+          elem_table.update_all_table_fields(elem);
+          table.associations_mut().#idx.tables.push(elem_table);
+        }
+      )
+    } else {
+      quote!(
+        for elem_table in table.associations_mut().#idx.tables.iter_mut() {
+          elem_table.update_all_table_fields(&entity.#field_ident);
+        }
+      )
+    }
+  });
+  let ts_update = (associations.iter().any(|elem| elem.is_collection))
+    .then(|| quote!(let ts = table.table_suffix();));
+
+  let fields_decodes = fields.iter().chain(&primary_keys).map(|(field_ident, ty)| {
+    let name = field_ident.unraw().to_string();
+    quote!(
+      let #field_ident = oapth::orm::decode_column_alias::<#ty, R>(
+        buffer_cmd,
+        curr_row,
+        #table_or_alias,
+        table_suffix,
+        #name,
+      )?;
+    )
+  });
+  let associations_decodes = associations.iter().enumerate().map(|(idx, elem)| {
+    let Association { field_ty, ident: field_ident, ty, .. } = elem;
+    let suffix = suffix_offset(idx);
+    if elem.is_collection {
+      quote!(
+        let mut #field_ident = <#field_ty as ::core::default::Default>::default();
+        oapth::orm::collect_related_entities(buffer_cmd, entity_rows, table_suffix + #suffix, |elem| {
+          #field_ident.push(elem);
+          ::core::result::Result::Ok(())
+        })?;
+      )
+    } else {
+      quote!(
+        let #field_ident = <#ty as oapth::FromRows<R>>::from_rows(
+          buffer_cmd,
+          curr_row,
+          entity_rows,
+          table_suffix + #suffix,
+        )?.1;
+      )
+    }
+  });
+  let entity_rows = (!associations.is_empty())
+    .then(|| quote!(let entity_rows = all_rows.get(..len).unwrap_or_default();));
+  let all_idents = named.named.iter().filter_map(|field| field.ident.as_ref());
+  let columns_bounds =
+    fields.iter().chain(&primary_keys).map(|(_, ty)| quote!(#ty: oapth::FromSqlColumn<R>,));
+  let associations_bounds = associations.iter().map(|elem| {
+    let ty = elem.ty;
+    quote!(#ty: oapth::FromRows<R, Error = #error>,)
+  });
+
+  Ok(quote!(
+    impl<'entity> oapth::orm::Table<'entity> for #ident {
+      const PRIMARY_KEY_NAME: &'static str = #pk_name;
+      const PRIMARY_KEY_NAMES: &'static [&'static str] = &[#(#pk_names,)*];
+      const TABLE_NAME: &'static str = #table;
+      const TABLE_NAME_ALIAS: ::core::option::Option<&'static str> = #table_alias;
+
+      type Associations = #associations_ty;
+      type Error = #error;
+      type Fields = (#(#fields_tys,)*);
+      type PrimaryKeyValue = #pk_value_ty;
+
+      #[inline]
+      fn type_instances(#ts_param: oapth::TableSuffix) -> oapth::orm::FromSuffixRslt<'entity, Self> {
+        (#associations_instances, (#(#fields_instances,)*))
+      }
+
+      #[inline]
+      fn update_all_table_fields(
+        entity: &'entity Self,
+        table: &mut oapth::orm::TableParams<'entity, Self>,
+      ) {
+        #ts_update
+        *table.id_field_mut().value_mut() = ::core::option::Option::Some(#pk_value);
+        #(#fields_updates)*
+        #(#associations_updates)*
+      }
+    }
+
+    impl<R> oapth::FromRows<R> for #ident
+    where
+      R: oapth::Row,
+      #(#columns_bounds)*
+      #(#associations_bounds)*
+    {
+      type Error = #error;
+      type PrimaryKey = #pk_ty;
+
+      #[inline]
+      fn from_rows(
+        buffer_cmd: &mut String,
+        curr_row: &R,
+        rows: &[R],
+        table_suffix: oapth::TableSuffix,
+      ) -> ::core::result::Result<(usize, Self), Self::Error> {
+        let all_rows = if rows.is_empty() { ::core::slice::from_ref(curr_row) } else { rows };
+        let len = oapth::orm::entity_rows_len::<R, Self>(buffer_cmd, all_rows, table_suffix)?;
+        #entity_rows
+        #(#fields_decodes)*
+        #(#associations_decodes)*
+        ::core::result::Result::Ok((len, Self { #(#all_idents,)* }))
+      }
+
+      #[inline]
+      fn primary_key(
+        buffer_cmd: &mut String,
+        row: &R,
+        table_suffix: oapth::TableSuffix,
+      ) -> ::core::result::Result<Self::PrimaryKey, Self::Error> {
+        #pk_decode
+      }
+    }
+  ))
+}
+
+struct Association<'any> {
+  field_ty: &'any Type,
+  from: LitStr,
+  ident: &'any Ident,
+  is_collection: bool,
+  to: LitStr,
+  ty: &'any Type,
+}
+
+impl<'any> Association<'any> {
+  fn new(ident: &'any Ident, field_ty: &'any Type, from: LitStr, to: LitStr) -> Self {
+    let (is_collection, ty) = match vec_argument(field_ty) {
+      Some(elem) => (true, elem),
+      None => (false, field_ty),
+    };
+    Self { field_ty, from, ident, is_collection, to, ty }
+  }
+}
+
+enum FieldKind {
+  Association { from: LitStr, to: LitStr },
+  PrimaryKey,
+  Regular,
+}
+
+impl FieldKind {
+  fn new(field: &syn::Field) -> syn::Result<Self> {
+    let mut kind = Self::Regular;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("oapth")) {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("association") {
+          let (mut from, mut to) = (None, None);
+          meta.parse_nested_meta(|inner| {
+            if inner.path.is_ident("from") {
+              from = Some(inner.value()?.parse::<LitStr>()?);
+            } else if inner.path.is_ident("to") {
+              to = Some(inner.value()?.parse::<LitStr>()?);
+            } else {
+              return Err(inner.error("expected `from` or `to`"));
+            }
+            Ok(())
+          })?;
+          match (from, to) {
+            (Some(from), Some(to)) => kind = Self::Association { from, to },
+            _ => return Err(meta.error("associations must have both `from` and `to`")),
+          }
+        } else if meta.path.is_ident("primary_key") {
+          kind = Self::PrimaryKey;
+        } else {
+          return Err(meta.error("expected `association` or `primary_key`"));
+        }
+        Ok(())
+      })?;
+    }
+    Ok(kind)
+  }
+}
+
+struct StructAttrs {
+  alias: Option<LitStr>,
+  error: Type,
+  table: LitStr,
+}
+
+impl StructAttrs {
+  fn new(input: &DeriveInput) -> syn::Result<Self> {
+    let (mut alias, mut error, mut table) = (None, None, None);
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("oapth")) {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("alias") {
+          alias = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("error") {
+          error = Some(meta.value()?.parse::<LitStr>()?.parse::<Type>()?);
+        } else if meta.path.is_ident("table") {
+          table = Some(meta.value()?.parse::<LitStr>()?);
+        } else {
+          return Err(meta.error("expected `alias`, `error` or `table`"));
+        }
+        Ok(())
+      })?;
+    }
+    Ok(Self {
+      alias,
+      error: error.unwrap_or_else(|| syn::parse_quote!(oapth::Error)),
+      table: table
+        .unwrap_or_else(|| LitStr::new(&snake_case(&input.ident.to_string()), input.ident.span())),
+    })
+  }
+}
+
+// `StructName` -> `struct_name`
+fn snake_case(s: &str) -> String {
+  let mut rslt = String::with_capacity(s.len());
+  for (idx, c) in s.char_indices() {
+    if c.is_uppercase() {
+      if idx > 0 {
+        rslt.push('_');
+      }
+      rslt.extend(c.to_lowercase());
+    } else {
+      rslt.push(c);
+    }
+  }
+  rslt
+}
+
+fn replace_generic_argument(ty: &Type, arg: TokenStream) -> TokenStream {
+  let mut new_ty = ty.clone();
+  if let Type::Path(TypePath { path, .. }) = &mut new_ty {
+    if let Some(segment) = path.segments.last_mut() {
+      segment.arguments = PathArguments::AngleBracketed(syn::parse_quote!(<#arg>));
+    }
+  }
+  quote!(#new_ty)
+}
+
+// Associations of the first element have suffix `ts + 1`, the second `ts + 2` and so on.
+fn suffix_offset(idx: usize) -> u32 {
+  u32::try_from(idx).unwrap_or(u32::MAX).saturating_add(1)
+}
+
+fn vec_argument(ty: &Type) -> Option<&Type> {
+  let Type::Path(TypePath { path, qself: None }) = ty else { return None };
+  let segment = path.segments.last()?;
+  if segment.ident != "Vec" {
+    return None;
+  }
+  let PathArguments::AngleBracketed(args) = &segment.arguments else { return None };
+  match args.args.first()? {
+    GenericArgument::Type(elem) => Some(elem),
+    _ => None,
+  }
+}
@@ -0,0 +1,311 @@
+use oapth::{
+  database::SqlParam,
+  orm::{
+    InitialInsertValue, SelectLimit, SelectOrderBy, SqlBindings, SqlWriter, Table, TableParams,
+  },
+  DatabaseTy, FromRows, FromSqlColumn, Row,
+};
+
+#[derive(Debug, PartialEq, oapth_macros::Table)]
+#[oapth(table = "a")]
+struct A {
+  #[oapth(primary_key)]
+  id: i32,
+  name: String,
+}
+
+#[derive(Debug, PartialEq, oapth_macros::Table)]
+#[oapth(table = "b")]
+struct B {
+  #[oapth(association(from = "id", to = "id_b"))]
+  a: A,
+  #[oapth(primary_key)]
+  id: i32,
+  name: String,
+}
+
+#[derive(Debug, PartialEq, oapth_macros::Table)]
+#[oapth(table = "c")]
+struct C {
+  #[oapth(association(from = "id", to = "id_a"))]
+  r#as: Vec<A>,
+  #[oapth(association(from = "id", to = "id_b"))]
+  bs: Vec<B>,
+  #[oapth(primary_key)]
+  id: i32,
+  name: String,
+}
+
+#[derive(Debug, PartialEq, oapth_macros::Table)]
+struct OrderLine {
+  #[oapth(primary_key)]
+  code: String,
+  #[oapth(primary_key)]
+  line: i32,
+  quantity: i32,
+}
+
+#[derive(Debug, oapth_macros::Table)]
+#[oapth(alias = "items", error = "()")]
+struct OrderItem {
+  #[oapth(primary_key)]
+  r#ref: i64,
+}
+
+// Columns are only found by name and absent columns behave like NULL values.
+struct TestRow(Vec<(&'static str, i64, &'static str)>);
+
+impl Row for TestRow {
+  fn str_from_idx(&self, idx: usize) -> oapth::Result<&str> {
+    self.0.get(idx).map(|elem| elem.2).ok_or(oapth::Error::InvalidSqlQuery)
+  }
+
+  fn str_from_name(&self, name: &str) -> oapth::Result<&str> {
+    self
+      .0
+      .iter()
+      .find(|elem| elem.0 == name)
+      .map(|elem| elem.2)
+      .ok_or(oapth::Error::InvalidSqlQuery)
+  }
+
+  fn i64_from_idx(&self, idx: usize) -> oapth::Result<i64> {
+    self.0.get(idx).map(|elem| elem.1).ok_or(oapth::Error::InvalidSqlQuery)
+  }
+
+  fn i64_from_name(&self, name: &str) -> oapth::Result<i64> {
+    self
+      .0
+      .iter()
+      .find(|elem| elem.0 == name)
+      .map(|elem| elem.1)
+      .ok_or(oapth::Error::InvalidSqlQuery)
+  }
+
+  fn column_name(&self, idx: usize) -> Option<&str> {
+    self.0.get(idx).map(|elem| elem.0)
+  }
+
+  fn column_ty(&self, _: usize) -> Option<&str> {
+    None
+  }
+
+  fn columns_len(&self) -> usize {
+    self.0.len()
+  }
+}
+
+impl FromSqlColumn<TestRow> for i32 {
+  fn from_column_idx(row: &TestRow, idx: usize) -> oapth::Result<Self> {
+    Ok(row.i64_from_idx(idx)?.try_into()?)
+  }
+
+  fn from_column_name(row: &TestRow, name: &str) -> oapth::Result<Self> {
+    Ok(row.i64_from_name(name)?.try_into()?)
+  }
+}
+
+impl FromSqlColumn<TestRow> for i64 {
+  fn from_column_idx(row: &TestRow, idx: usize) -> oapth::Result<Self> {
+    row.i64_from_idx(idx)
+  }
+
+  fn from_column_name(row: &TestRow, name: &str) -> oapth::Result<Self> {
+    row.i64_from_name(name)
+  }
+}
+
+impl FromSqlColumn<TestRow> for String {
+  fn from_column_idx(row: &TestRow, idx: usize) -> oapth::Result<Self> {
+    Ok(row.str_from_idx(idx)?.into())
+  }
+
+  fn from_column_name(row: &TestRow, name: &str) -> oapth::Result<Self> {
+    Ok(row.str_from_name(name)?.into())
+  }
+}
+
+fn a(id: i32) -> A {
+  A { id, name: format!("foo{id}") }
+}
+
+fn b(id: i32) -> B {
+  B { a: a(id + 10), id, name: format!("bar{id}") }
+}
+
+fn c_row(c: i64, a: Option<i64>, b: Option<i64>) -> TestRow {
+  let mut columns = vec![("c0__id", c, "c"), ("c0__name", 0, "baz")];
+  if let Some(id) = a {
+    columns.extend([("a1__id", id, ""), ("a1__name", 0, "")]);
+  }
+  if let Some(id) = b {
+    columns.extend([
+      ("b2__id", id, ""),
+      ("b2__name", 0, ""),
+      ("a3__id", id + 10, ""),
+      ("a3__name", 0, ""),
+    ]);
+  }
+  TestRow(columns)
+}
+
+#[test]
+fn attributes_define_table_names() {
+  assert_eq!(<A as Table<'_>>::TABLE_NAME, "a");
+  assert_eq!(<A as Table<'_>>::TABLE_NAME_ALIAS, None);
+  assert_eq!(<OrderItem as Table<'_>>::PRIMARY_KEY_NAME, "ref");
+  assert_eq!(<OrderItem as Table<'_>>::TABLE_NAME, "order_item");
+  assert_eq!(<OrderItem as Table<'_>>::TABLE_NAME_ALIAS, Some("items"));
+}
+
+#[test]
+fn derived_table_has_correct_statements() {
+  let entity = b(2);
+  let mut buffer = String::new();
+  let mut table_params = TableParams::<B>::default();
+
+  table_params
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Ascending, &[]),
+      SelectLimit::All,
+      DatabaseTy::Postgres,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"SELECT "b0"."id" AS "b0__id","b0"."name" AS "b0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name" FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0"."id" = "a1"."id_b"  ORDER BY "b0"."id" ASC,"a1"."id" ASC LIMIT ALL"#
+  );
+
+  table_params.update_all_table_fields(&entity);
+
+  buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  table_params
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"INSERT INTO "b" ("id","name") VALUES ($1,$2);INSERT INTO "a" ("id","name","id_b") VALUES ($1,$2,$3);"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(2),
+      SqlParam::Str("bar2"),
+      SqlParam::I32(12),
+      SqlParam::Str("foo12"),
+      SqlParam::I32(2)
+    ]
+  );
+}
+
+#[test]
+fn derived_table_updates_collections() {
+  let entity = C { r#as: vec![a(1), a(2)], bs: vec![b(3)], id: 4, name: "baz".into() };
+  let mut buffer = String::new();
+  let mut table_params = TableParams::<C>::default();
+  table_params.update_all_table_fields(&entity);
+
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  table_params.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"DELETE FROM "a" WHERE "id"=$1;DELETE FROM "a" WHERE "id"=$1;DELETE FROM "a" WHERE "id"=$1;DELETE FROM "b" WHERE "id"=$1;DELETE FROM "c" WHERE "id"=$1;"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[SqlParam::I32(1), SqlParam::I32(2), SqlParam::I32(13), SqlParam::I32(3), SqlParam::I32(4)]
+  );
+}
+
+#[test]
+fn derived_composite_keys_filter_every_column() {
+  let entity = OrderLine { code: "foo".into(), line: 2, quantity: 3 };
+  let mut buffer = String::new();
+  let mut table_params = TableParams::<OrderLine>::default();
+  table_params.update_all_table_fields(&entity);
+  assert_eq!(<OrderLine as Table<'_>>::PRIMARY_KEY_NAMES, ["code", "line"]);
+
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  table_params.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  table_params
+    .write_upsert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "order_line" SET "code"=$1,"line"=$2,"quantity"=$3 WHERE "code"=$4 AND "line"=$5;INSERT INTO "order_line" ("code","line","quantity") VALUES ($1,$2,$3) ON CONFLICT ("code","line") DO UPDATE SET "quantity"=EXCLUDED."quantity";"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::Str("foo"),
+      SqlParam::I32(2),
+      SqlParam::I32(3),
+      SqlParam::Str("foo"),
+      SqlParam::I32(2),
+      SqlParam::Str("foo"),
+      SqlParam::I32(2),
+      SqlParam::I32(3)
+    ]
+  );
+}
+
+#[test]
+fn derived_composite_keys_group_rows() {
+  let row = |code, line| {
+    TestRow(vec![
+      ("order_line0__code", 0, code),
+      ("order_line0__line", line, ""),
+      ("order_line0__quantity", 1, ""),
+    ])
+  };
+  let rows = [row("foo", 1), row("foo", 1), row("foo", 2), row("bar", 1)];
+  let mut buffer = String::new();
+  let (len, entity) = OrderLine::from_rows(&mut buffer, &rows[0], &rows, 0).unwrap();
+  assert_eq!(len, 2);
+  assert_eq!(entity, OrderLine { code: "foo".into(), line: 1, quantity: 1 });
+  assert_eq!(OrderLine::primary_key(&mut buffer, &rows[3], 0).unwrap(), ("bar".into(), 1));
+  assert!(buffer.is_empty());
+}
+
+#[test]
+fn derived_from_rows_groups_joined_rows() {
+  let rows = [
+    c_row(4, Some(1), Some(3)),
+    c_row(4, Some(1), Some(5)),
+    c_row(4, Some(2), Some(3)),
+    c_row(4, Some(2), Some(5)),
+    c_row(6, None, None),
+  ];
+  let mut buffer = String::new();
+
+  let (len, entity) = C::from_rows(&mut buffer, &rows[0], &rows, 0).unwrap();
+  assert_eq!(len, 4);
+  assert_eq!(
+    entity,
+    C {
+      r#as: vec![A { id: 1, name: String::new() }, A { id: 2, name: String::new() }],
+      bs: vec![
+        B { a: A { id: 13, name: String::new() }, id: 3, name: String::new() },
+        B { a: A { id: 15, name: String::new() }, id: 5, name: String::new() }
+      ],
+      id: 4,
+      name: "baz".into()
+    }
+  );
+
+  let (len, entity) = C::from_rows(&mut buffer, &rows[4], &rows[4..], 0).unwrap();
+  assert_eq!(len, 1);
+  assert_eq!(entity, C { r#as: vec![], bs: vec![], id: 6, name: "baz".into() });
+  assert!(buffer.is_empty());
+}
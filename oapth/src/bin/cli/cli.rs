@@ -32,15 +32,33 @@ pub(crate) struct Cli {
 #[allow(unused_tuple_struct_fields)]
 #[derive(Debug, clap::Subcommand)]
 pub(crate) enum Commands {
+  Baseline {
+    /// name of the migration group.
+    _group: String,
+    /// migrations up to this version are recorded as applied without being executed.
+    _version: i32,
+  },
   #[cfg(feature = "sm-dev")]
   Clean {},
   Migrate {},
   #[cfg(feature = "sm-dev")]
   MigrateAndSeed {},
+  Plan {
+    /// prints the plan as JSON instead of human readable text.
+    #[arg(long = "json")]
+    _json: bool,
+  },
+  Repair {
+    /// confirms that stored names and checksums can be overwritten, otherwise only the changes
+    /// that would be applied are printed.
+    #[arg(long = "yes")]
+    yes: bool,
+  },
   Rollback {
     _versions: Vec<i32>,
   },
   #[cfg(feature = "sm-dev")]
   Seed {},
+  Status {},
   Validate {},
 }
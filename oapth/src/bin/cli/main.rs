@@ -2,8 +2,11 @@
 
 mod cli;
 
-use oapth::{sm::DbMigration, Config, Identifier};
-use std::{borrow::Cow, env::current_dir, path::Path};
+use oapth::{
+  sm::{DbMigration, MigrationPlan, MigrationRepair, MigrationStatusReport},
+  Config, Identifier,
+};
+use std::{borrow::Cow, env::current_dir, fmt::Write, path::Path};
 
 const _DEFAULT_CFG_FILE_NAME: &str = "oapth.toml";
 
@@ -16,30 +19,37 @@ async fn main() -> oapth::Result<()> {
 
   let cli = <cli::Cli as clap::Parser>::parse();
   let config = Config::with_url_from_var(&cli._var)?;
+  let mut _buffer = (String::new(), Vec::<DbMigration>::new(), Vec::<Identifier>::new());
+  let _buffer = (&mut _buffer.0, &mut _buffer.1, &mut _buffer.2);
 
   match config.database()? {
     "mysql" => {
-      #[cfg(feature = "mysql")]
-      _handle_commands(&cli, oapth::database::SqlxMysql::new(&config).await?).await?;
-      #[cfg(not(feature = "mysql"))]
+      #[cfg(feature = "sqlx-mysql")]
+      _handle_commands(_buffer, &cli, oapth::database::SqlxMysql::new(&config).await?).await?;
+      #[cfg(not(feature = "sqlx-mysql"))]
       eprintln!("No feature enabled for MySQL-like databases");
     }
     "mssql" | "sqlserver" => {
-      #[cfg(feature = "mssql")]
-      _handle_commands(&cli, oapth::database::Tiberius::new(&config).await?).await?;
-      #[cfg(not(feature = "mssql"))]
+      #[cfg(feature = "tiberius")]
+      {
+        use tokio_util::compat::TokioAsyncWriteCompatExt;
+        let tcp = tokio::net::TcpStream::connect(config.full_host()?).await?;
+        let database = oapth::database::Tiberius::new(&config, tcp.compat_write()).await?;
+        _handle_commands(_buffer, &cli, database).await?;
+      }
+      #[cfg(not(feature = "tiberius"))]
       eprintln!("No feature enabled for MS-SQL");
     }
     "postgres" | "postgresql" => {
-      #[cfg(feature = "postgres")]
-      _handle_commands(&cli, oapth::database::SqlxPostgres::new(&config).await?).await?;
-      #[cfg(not(feature = "postgres"))]
+      #[cfg(feature = "sqlx-postgres")]
+      _handle_commands(_buffer, &cli, oapth::database::SqlxPostgres::new(&config).await?).await?;
+      #[cfg(not(feature = "sqlx-postgres"))]
       eprintln!("No feature enabled for PostgreSQL");
     }
     "sqlite" => {
-      #[cfg(feature = "sqlite")]
-      _handle_commands(&cli, oapth::database::SqlxSqlite::new(&config).await?).await?;
-      #[cfg(not(feature = "sqlite"))]
+      #[cfg(feature = "sqlx-sqlite")]
+      _handle_commands(_buffer, &cli, oapth::database::SqlxSqlite::new(&config).await?).await?;
+      #[cfg(not(feature = "sqlx-sqlite"))]
       eprintln!("No feature enabled for SQLite");
     }
     _ => return Err(oapth::Error::InvalidUrl),
@@ -72,6 +82,16 @@ where
 {
   let mut commands = oapth::sm::Commands::new(cli._files_num, database);
   match &cli._commands {
+    cli::Commands::Baseline { _group, _version } => {
+      commands
+        .baseline_from_toml_path(
+          (buffer_cmd, buffer_db_migrations),
+          &_toml_file_path(cli)?,
+          _group,
+          *_version,
+        )
+        .await?;
+    }
     #[cfg(feature = "sm-dev")]
     cli::Commands::Clean {} => {
       commands.clear((buffer_cmd, _buffer_idents)).await?;
@@ -89,9 +109,37 @@ where
         .await?;
       commands.seed_from_dir(buffer_cmd, _seeds_file_path(cli, seeds.as_deref())?).await?;
     }
+    cli::Commands::Plan { _json } => {
+      let mut plans = Vec::new();
+      commands
+        .plan_from_toml_path((buffer_cmd, buffer_db_migrations), &_toml_file_path(cli)?, &mut plans)
+        .await?;
+      if *_json {
+        _write_plans_json(buffer_cmd, &plans)?;
+      } else {
+        _write_plans_human(buffer_cmd, &plans)?;
+      }
+      println!("{buffer_cmd}");
+      buffer_cmd.clear();
+    }
+    cli::Commands::Repair { yes } => {
+      let mut repairs = Vec::new();
+      let buffer = (&mut *buffer_cmd, &mut *buffer_db_migrations);
+      if *yes {
+        commands.repair_from_toml_path(buffer, &_toml_file_path(cli)?, &mut repairs).await?;
+        _write_repairs(buffer_cmd, &repairs)?;
+      } else {
+        commands
+          .repair_preview_from_toml_path(buffer, &_toml_file_path(cli)?, &mut repairs)
+          .await?;
+        _write_repairs_preview(buffer_cmd, &repairs)?;
+      }
+      println!("{buffer_cmd}");
+      buffer_cmd.clear();
+    }
     cli::Commands::Rollback { _versions } => {
       commands
-        .rollback_from_toml((buffer_cmd, buffer_db_migrations), &_toml_file_path(cli)?, &_versions)
+        .rollback_from_toml((buffer_cmd, buffer_db_migrations), &_toml_file_path(cli)?, _versions)
         .await?;
     }
     #[cfg(feature = "sm-dev")]
@@ -99,6 +147,19 @@ where
       let (_, seeds) = oapth::sm::utils::parse_root_toml(&_toml_file_path(cli)?)?;
       commands.seed_from_dir(buffer_cmd, _seeds_file_path(cli, seeds.as_deref())?).await?;
     }
+    cli::Commands::Status {} => {
+      let mut reports = Vec::new();
+      commands
+        .status_from_toml_path(
+          (buffer_cmd, buffer_db_migrations),
+          &_toml_file_path(cli)?,
+          &mut reports,
+        )
+        .await?;
+      _write_status_table(buffer_cmd, &reports)?;
+      println!("{buffer_cmd}");
+      buffer_cmd.clear();
+    }
     cli::Commands::Validate {} => {
       commands
         .validate_from_toml((buffer_cmd, buffer_db_migrations), &_toml_file_path(cli)?)
@@ -108,6 +169,165 @@ where
   Ok(())
 }
 
+fn _write_json_str(buffer: &mut String, s: &str) -> oapth::Result<()> {
+  buffer.push('"');
+  for c in s.chars() {
+    match c {
+      '"' => buffer.push_str("\\\""),
+      '\\' => buffer.push_str("\\\\"),
+      '\n' => buffer.push_str("\\n"),
+      '\r' => buffer.push_str("\\r"),
+      '\t' => buffer.push_str("\\t"),
+      _ if c.is_control() => buffer.write_fmt(format_args!("\\u{:04x}", u32::from(c)))?,
+      _ => buffer.push(c),
+    }
+  }
+  buffer.push('"');
+  Ok(())
+}
+
+fn _write_plans_human(buffer: &mut String, plans: &[MigrationPlan]) -> oapth::Result<()> {
+  for plan in plans {
+    let mg = plan.group();
+    buffer.write_fmt(format_args!(
+      "{}__{}: {} pending migration(s)\n",
+      mg.version(),
+      mg.name(),
+      plan.migrations().len()
+    ))?;
+    for migration in plan.migrations() {
+      buffer.write_fmt(format_args!("  {}__{}\n", migration.version(), migration.name()))?;
+      for line in migration.sql_up().lines() {
+        buffer.write_fmt(format_args!("    {line}\n"))?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn _write_plans_json(buffer: &mut String, plans: &[MigrationPlan]) -> oapth::Result<()> {
+  buffer.push('[');
+  for (plan_idx, plan) in plans.iter().enumerate() {
+    if plan_idx > 0 {
+      buffer.push(',');
+    }
+    buffer.push_str("{\"group\":{\"name\":");
+    _write_json_str(buffer, plan.group().name())?;
+    buffer.write_fmt(format_args!(",\"version\":{}}},\"migrations\":[", plan.group().version()))?;
+    for (migration_idx, migration) in plan.migrations().iter().enumerate() {
+      if migration_idx > 0 {
+        buffer.push(',');
+      }
+      buffer.write_fmt(format_args!("{{\"checksum\":\"{}\",\"name\":", migration.checksum()))?;
+      _write_json_str(buffer, migration.name())?;
+      buffer.push_str(",\"repeatability\":");
+      if let Some(elem) = migration.repeatability() {
+        _write_json_str(buffer, elem.as_str())?;
+      } else {
+        buffer.push_str("null");
+      }
+      buffer.push_str(",\"sql\":");
+      _write_json_str(buffer, migration.sql_up())?;
+      buffer.write_fmt(format_args!(",\"version\":{}}}", migration.version()))?;
+    }
+    buffer.push_str("],\"sql\":");
+    let mut sql = String::new();
+    plan.write_sql(&mut sql);
+    _write_json_str(buffer, &sql)?;
+    buffer.push('}');
+  }
+  buffer.push(']');
+  Ok(())
+}
+
+fn _write_repair_entries(buffer: &mut String, repair: &MigrationRepair) -> oapth::Result<()> {
+  for entry in repair.entries() {
+    buffer.write_fmt(format_args!("  {}\n", entry.version()))?;
+    if entry.old_name() != entry.new_name() {
+      buffer.write_fmt(format_args!("    - name: {}\n", entry.old_name()))?;
+      buffer.write_fmt(format_args!("    + name: {}\n", entry.new_name()))?;
+    }
+    if entry.old_checksum() != entry.new_checksum() {
+      buffer.write_fmt(format_args!("    - checksum: {}\n", entry.old_checksum()))?;
+      buffer.write_fmt(format_args!("    + checksum: {}\n", entry.new_checksum()))?;
+    }
+  }
+  Ok(())
+}
+
+fn _write_repairs(buffer: &mut String, repairs: &[MigrationRepair]) -> oapth::Result<()> {
+  for repair in repairs {
+    let mg = repair.group();
+    if repair.is_empty() {
+      buffer.write_fmt(format_args!("{}__{}: nothing to repair\n", mg.version(), mg.name()))?;
+      continue;
+    }
+    buffer.write_fmt(format_args!(
+      "{}__{}: {} failed record(s) removed\n",
+      mg.version(),
+      mg.name(),
+      repair.deleted_failed()
+    ))?;
+    _write_repair_entries(buffer, repair)?;
+  }
+  Ok(())
+}
+
+fn _write_repairs_preview(buffer: &mut String, repairs: &[MigrationRepair]) -> oapth::Result<()> {
+  for repair in repairs {
+    let mg = repair.group();
+    buffer.write_fmt(format_args!(
+      "{}__{}: {} failed record(s) to remove, {} stored migration(s) to realign\n",
+      mg.version(),
+      mg.name(),
+      repair.deleted_failed(),
+      repair.entries().len()
+    ))?;
+    _write_repair_entries(buffer, repair)?;
+  }
+  buffer.push_str("Nothing was modified, run again with `--yes` to apply these changes");
+  Ok(())
+}
+
+fn _write_status_table(
+  buffer: &mut String,
+  reports: &[MigrationStatusReport],
+) -> oapth::Result<()> {
+  const HEADERS: [&str; 5] = ["GROUP", "VERSION", "NAME", "STATUS", "CREATED ON"];
+  let mut rows = Vec::new();
+  for report in reports {
+    let mg = report.group();
+    for entry in report.entries() {
+      rows.push([
+        format!("{}__{}", mg.version(), mg.name()),
+        entry.version().to_string(),
+        entry.name().into(),
+        entry.status().to_string(),
+        entry.created_on().map(ToString::to_string).unwrap_or_default(),
+      ]);
+    }
+  }
+  let mut widths = HEADERS.map(str::len);
+  for row in &rows {
+    for (width, column) in widths.iter_mut().zip(row) {
+      *width = (*width).max(column.len());
+    }
+  }
+  let mut write_row = |columns: &[String; 5]| -> oapth::Result<()> {
+    for (width, column) in widths.iter().zip(columns) {
+      buffer.write_fmt(format_args!("{column:<width$}  "))?;
+    }
+    buffer.truncate(buffer.trim_end().len());
+    buffer.push('\n');
+    Ok(())
+  };
+  write_row(&HEADERS.map(String::from))?;
+  for row in &rows {
+    write_row(row)?;
+  }
+  Ok(())
+}
+
 #[cfg(feature = "sm-dev")]
 fn _seeds_file_path<'a, 'b, 'c>(
   cli: &'a cli::Cli,
@@ -1,18 +1,30 @@
 //! Database
 
+#[cfg(feature = "pool")]
+mod pool;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod sql_param;
 #[cfg(any(feature = "sqlx-mysql", feature = "sqlx-postgres", feature = "sqlx-sqlite",))]
 mod sqlx;
 #[cfg(feature = "tiberius")]
 mod tiberius;
+mod transaction;
 mod unit;
 
 use crate::{row::Row, DatabaseTy, FromRow};
 use alloc::vec::Vec;
 use core::future::Future;
+#[cfg(feature = "pool")]
+pub use pool::*;
+#[cfg(feature = "postgres")]
+pub use postgres::*;
+pub use sql_param::*;
 #[cfg(any(feature = "sqlx-mysql", feature = "sqlx-postgres", feature = "sqlx-sqlite",))]
 pub use sqlx::*;
 #[cfg(feature = "tiberius")]
 pub use tiberius::*;
+pub use transaction::*;
 
 /// Any backend that connects to a database
 pub trait Database {
@@ -22,12 +34,63 @@ pub trait Database {
   /// See [Row].
   type Row: Row;
 
+  /// Starts a transaction or, if a transaction is already in progress, a savepoint.
+  ///
+  /// ```rust
+  /// # async fn fun() -> oapth::Result<()> {
+  /// use oapth::database::Database;
+  /// let mut database = ();
+  /// let mut transaction = database.begin().await?;
+  /// transaction.execute("UPDATE foo SET bar = 1").await?;
+  /// let savepoint = transaction.begin().await?;
+  /// savepoint.rollback().await?;
+  /// transaction.commit().await?;
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  fn begin(&mut self) -> impl Future<Output = crate::Result<Transaction<'_, Self>>>
+  where
+    Self: Sized,
+  {
+    async move {
+      self.begin_transaction().await?;
+      Ok(Transaction::new(self))
+    }
+  }
+
+  /// Low-level operation that starts a transaction or a savepoint. Prefer [Self::begin].
+  fn begin_transaction(&mut self) -> impl Future<Output = crate::Result<()>>;
+
+  /// Low-level operation that commits the current transaction or releases the current savepoint.
+  /// Prefer [Transaction::commit].
+  fn commit_transaction(&mut self) -> impl Future<Output = crate::Result<()>>;
+
   /// Executes a raw SQL command.
   fn execute(&mut self, cmd: &str) -> impl Future<Output = crate::Result<()>>;
 
+  /// Executes a single SQL command whose placeholders are bound to `params` and returns the
+  /// number of affected rows. See [SqlParam].
+  fn execute_with(
+    &mut self,
+    cmd: &str,
+    params: &[SqlParam<'_>],
+  ) -> impl Future<Output = crate::Result<u64>>;
+
+  /// Low-level operation that aborts the current transaction or restores the current savepoint.
+  /// Prefer [Transaction::rollback].
+  fn rollback_transaction(&mut self) -> impl Future<Output = crate::Result<()>>;
+
   /// Retrieves a raw database row.
   fn row(&mut self, cmd: &str) -> impl Future<Output = crate::Result<Self::Row>>;
 
+  /// Retrieves a raw database row of a single SQL command whose placeholders are bound to
+  /// `params`. See [SqlParam].
+  fn row_with(
+    &mut self,
+    cmd: &str,
+    params: &[SqlParam<'_>],
+  ) -> impl Future<Output = crate::Result<Self::Row>>;
+
   /// Retrieves a set of raw database rows.
   fn rows<E>(
     &mut self,
@@ -37,6 +100,17 @@ pub trait Database {
   where
     E: From<crate::Error>;
 
+  /// Retrieves a set of raw database rows of a single SQL command whose placeholders are bound to
+  /// `params`. See [SqlParam].
+  fn rows_with<E>(
+    &mut self,
+    cmd: &str,
+    params: &[SqlParam<'_>],
+    cb: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> impl Future<Output = Result<(), E>>
+  where
+    E: From<crate::Error>;
+
   /// Retrieves a row and maps it to `T`. See [FromRow].
   #[inline]
   fn simple_entity<T>(&mut self, cmd: &str) -> impl Future<Output = Result<T, T::Error>>
@@ -62,6 +136,10 @@ pub trait Database {
     })
   }
 
+  /// Schedules the rollback of the current transaction or savepoint to be performed before the
+  /// next command. Used by dropped [Transaction]s.
+  fn start_rollback(&mut self);
+
   /// Similar to `[Self::execute]` but operations are atomics.
   fn transaction(&mut self, cmd: &str) -> impl Future<Output = crate::Result<()>>;
 }
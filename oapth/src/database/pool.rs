@@ -0,0 +1,789 @@
+use crate::{
+  database::{Database, SqlParam},
+  Config, DatabaseTy,
+};
+#[cfg(feature = "sm")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{
+  fmt::{Debug, Formatter},
+  future::Future,
+  marker::PhantomData,
+  ops::{Deref, DerefMut},
+  time::Duration,
+};
+use std::{sync::Mutex, time::Instant};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Default maximum amount of time [Pool::get] waits for a connection.
+pub const DEFAULT_POOL_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default amount of time a connection can stay idle before being closed.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+/// Default maximum number of connections of a [Pool].
+pub const DEFAULT_POOL_MAX_SIZE: usize = 10;
+
+/// Opens and validates the connections of a [Pool].
+pub trait PoolManager {
+  /// See [Database].
+  type Database: Database;
+
+  /// Opens a new connection.
+  fn connect(&self) -> impl Future<Output = crate::Result<Self::Database>>;
+
+  /// If a connection that was idle can still be used. Defaults to a `SELECT 1` round-trip.
+  #[inline]
+  fn is_valid(&self, database: &mut Self::Database) -> impl Future<Output = bool> {
+    async move { database.execute("SELECT 1").await.is_ok() }
+  }
+}
+
+/// [PoolManager] of the backends that only need a [Config] to connect.
+pub struct ConfigManager<D> {
+  config: Config,
+  phantom: PhantomData<fn() -> D>,
+}
+
+impl<D> ConfigManager<D> {
+  /// Creates a new instance from the connection parameters of `config`.
+  #[inline]
+  pub fn new(config: &Config) -> Self {
+    Self { config: config.clone(), phantom: PhantomData }
+  }
+
+  /// Configuration used to open connections
+  #[inline]
+  pub fn config(&self) -> &Config {
+    &self.config
+  }
+}
+
+impl<D> Debug for ConfigManager<D> {
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("ConfigManager").field("config", &self.config).finish()
+  }
+}
+
+/// Parameters of a [Pool]. See [Config::pool_params].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PoolParams {
+  acquire_timeout: Duration,
+  health_check: bool,
+  idle_timeout: Option<Duration>,
+  max_size: usize,
+}
+
+impl PoolParams {
+  /// Maximum amount of time [Pool::get] waits for a connection.
+  #[inline]
+  pub fn acquire_timeout(&self) -> Duration {
+    self.acquire_timeout
+  }
+
+  /// If idle connections are validated by [PoolManager::is_valid] before being reused.
+  #[inline]
+  pub fn health_check(&self) -> bool {
+    self.health_check
+  }
+
+  /// Amount of time a connection can stay idle before being closed. `None` keeps connections
+  /// indefinitely.
+  #[inline]
+  pub fn idle_timeout(&self) -> Option<Duration> {
+    self.idle_timeout
+  }
+
+  /// Maximum number of connections, idle or not.
+  #[inline]
+  pub fn max_size(&self) -> usize {
+    self.max_size
+  }
+
+  /// Changes the maximum amount of time [Pool::get] waits for a connection.
+  ///
+  /// Defaults to 30 seconds.
+  #[inline]
+  pub fn set_acquire_timeout(&mut self, acquire_timeout: Duration) {
+    self.acquire_timeout = acquire_timeout;
+  }
+
+  /// Changes if idle connections are validated before being reused.
+  ///
+  /// Defaults to `true`.
+  #[inline]
+  pub fn set_health_check(&mut self, health_check: bool) {
+    self.health_check = health_check;
+  }
+
+  /// Changes the amount of time a connection can stay idle before being closed.
+  ///
+  /// Defaults to 10 minutes.
+  #[inline]
+  pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) {
+    self.idle_timeout = idle_timeout;
+  }
+
+  /// Changes the maximum number of connections.
+  ///
+  /// Defaults to 10.
+  #[inline]
+  pub fn set_max_size(&mut self, max_size: usize) {
+    self.max_size = max_size;
+  }
+}
+
+impl Default for PoolParams {
+  #[inline]
+  fn default() -> Self {
+    Self {
+      acquire_timeout: DEFAULT_POOL_ACQUIRE_TIMEOUT,
+      health_check: true,
+      idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+      max_size: DEFAULT_POOL_MAX_SIZE,
+    }
+  }
+}
+
+/// Keeps up to [PoolParams::max_size] connections opened by a [PoolManager].
+///
+/// Connections are lent through [PoolConnection]s and return to the pool when dropped.
+pub struct Pool<M>
+where
+  M: PoolManager,
+{
+  idle: Mutex<Vec<(M::Database, Instant)>>,
+  manager: M,
+  params: PoolParams,
+  semaphore: Semaphore,
+}
+
+impl<M> Pool<M>
+where
+  M: PoolManager,
+{
+  /// Creates a new instance without opening any connection.
+  #[inline]
+  pub fn new(manager: M, params: PoolParams) -> Self {
+    Self {
+      idle: Mutex::new(Vec::new()),
+      manager,
+      params,
+      semaphore: Semaphore::new(params.max_size),
+    }
+  }
+
+  /// Lends an idle connection or opens a new one, waiting at most
+  /// [PoolParams::acquire_timeout].
+  ///
+  /// Idle connections that expired or that failed the health check are closed.
+  #[inline]
+  pub async fn get(&self) -> crate::Result<PoolConnection<'_, M>> {
+    tokio::time::timeout(self.params.acquire_timeout, self.acquire())
+      .await
+      .map_err(|_err| crate::Error::PoolAcquireTimeout)?
+  }
+
+  /// Number of connections that are waiting to be lent
+  #[inline]
+  pub fn idle_len(&self) -> usize {
+    self.idle.lock().map_or(0, |idle| idle.len())
+  }
+
+  /// See [PoolManager].
+  #[inline]
+  pub fn manager(&self) -> &M {
+    &self.manager
+  }
+
+  /// See [PoolParams].
+  #[inline]
+  pub fn params(&self) -> &PoolParams {
+    &self.params
+  }
+
+  async fn acquire(&self) -> crate::Result<PoolConnection<'_, M>> {
+    // The semaphore is never closed
+    let permit = self.semaphore.acquire().await.map_err(|_err| crate::Error::PoolAcquireTimeout)?;
+    while let Some(mut database) = self.pop_idle() {
+      if !self.params.health_check || self.manager.is_valid(&mut database).await {
+        return Ok(PoolConnection::new(database, permit, self));
+      }
+    }
+    let database = self.manager.connect().await?;
+    Ok(PoolConnection::new(database, permit, self))
+  }
+
+  // Expired connections are closed before the most recently used one is returned.
+  fn pop_idle(&self) -> Option<M::Database> {
+    let mut idle = self.idle.lock().ok()?;
+    if let Some(idle_timeout) = self.params.idle_timeout {
+      idle.retain(|(_, last_use)| last_use.elapsed() < idle_timeout);
+    }
+    Some(idle.pop()?.0)
+  }
+}
+
+impl<M> Debug for Pool<M>
+where
+  M: Debug + PoolManager,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("Pool")
+      .field("idle_len", &self.idle_len())
+      .field("manager", &self.manager)
+      .field("params", &self.params)
+      .finish()
+  }
+}
+
+// Commands whose futures are dropped before completion are never marked as finished.
+macro_rules! track_cmd {
+  ($this:ident, $cmd:expr) => {{
+    $this.unfinished_cmds = $this.unfinished_cmds.wrapping_add(1);
+    let rslt = $cmd.await;
+    $this.unfinished_cmds = $this.unfinished_cmds.wrapping_sub(1);
+    rslt
+  }};
+}
+
+/// Connection lent by a [Pool]. Can be used wherever a [Database] is expected, including
+/// `Commands` and `Crud`.
+///
+/// Connections are closed instead of returning to the pool when dropped in the middle of a
+/// transaction that wasn't started by [Database::begin] or in the middle of a command, e.g.
+/// after a timeout. Transactions of [Database::begin] are rolled back before the next command.
+pub struct PoolConnection<'pool, M>
+where
+  M: PoolManager,
+{
+  // Only `None` after being dropped
+  database: Option<M::Database>,
+  _permit: SemaphorePermit<'pool>,
+  pool: &'pool Pool<M>,
+  transaction_depth: usize,
+  // Commands that were interrupted or that left the connection in an unknown state
+  unfinished_cmds: usize,
+}
+
+impl<'pool, M> PoolConnection<'pool, M>
+where
+  M: PoolManager,
+{
+  #[inline]
+  fn new(database: M::Database, permit: SemaphorePermit<'pool>, pool: &'pool Pool<M>) -> Self {
+    Self {
+      database: Some(database),
+      _permit: permit,
+      pool,
+      transaction_depth: 0,
+      unfinished_cmds: 0,
+    }
+  }
+
+  #[inline]
+  fn database(&self) -> &M::Database {
+    match &self.database {
+      Some(database) => database,
+      None => unreachable!(),
+    }
+  }
+
+  #[inline]
+  fn database_mut(&mut self) -> &mut M::Database {
+    match &mut self.database {
+      Some(database) => database,
+      None => unreachable!(),
+    }
+  }
+}
+
+impl<M> Debug for PoolConnection<'_, M>
+where
+  M: PoolManager,
+  M::Database: Debug,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("PoolConnection")
+      .field("database", self.database())
+      .field("transaction_depth", &self.transaction_depth)
+      .field("unfinished_cmds", &self.unfinished_cmds)
+      .finish()
+  }
+}
+
+impl<M> Deref for PoolConnection<'_, M>
+where
+  M: PoolManager,
+{
+  type Target = M::Database;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    self.database()
+  }
+}
+
+impl<M> DerefMut for PoolConnection<'_, M>
+where
+  M: PoolManager,
+{
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.database_mut()
+  }
+}
+
+impl<M> Drop for PoolConnection<'_, M>
+where
+  M: PoolManager,
+{
+  #[inline]
+  fn drop(&mut self) {
+    let Some(database) = self.database.take() else {
+      return;
+    };
+    if self.transaction_depth > 0 || self.unfinished_cmds > 0 {
+      return;
+    }
+    if let Ok(mut idle) = self.pool.idle.lock() {
+      idle.push((database, Instant::now()));
+    }
+  }
+}
+
+impl<M> Database for PoolConnection<'_, M>
+where
+  M: PoolManager,
+{
+  const TY: DatabaseTy = M::Database::TY;
+
+  type Row = <M::Database as Database>::Row;
+
+  #[inline]
+  async fn begin_transaction(&mut self) -> crate::Result<()> {
+    track_cmd!(self, (**self).begin_transaction())?;
+    self.transaction_depth = self.transaction_depth.wrapping_add(1);
+    Ok(())
+  }
+
+  #[inline]
+  async fn commit_transaction(&mut self) -> crate::Result<()> {
+    // Failed commits may or may not finish transactions so the command is kept unfinished
+    self.unfinished_cmds = self.unfinished_cmds.wrapping_add(1);
+    (**self).commit_transaction().await?;
+    self.unfinished_cmds = self.unfinished_cmds.wrapping_sub(1);
+    self.transaction_depth = self.transaction_depth.saturating_sub(1);
+    Ok(())
+  }
+
+  #[inline]
+  async fn execute(&mut self, cmd: &str) -> crate::Result<()> {
+    track_cmd!(self, (**self).execute(cmd))
+  }
+
+  #[inline]
+  async fn execute_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<u64> {
+    track_cmd!(self, (**self).execute_with(cmd, params))
+  }
+
+  #[inline]
+  async fn rollback_transaction(&mut self) -> crate::Result<()> {
+    // Same as `commit_transaction`
+    self.unfinished_cmds = self.unfinished_cmds.wrapping_add(1);
+    (**self).rollback_transaction().await?;
+    self.unfinished_cmds = self.unfinished_cmds.wrapping_sub(1);
+    self.transaction_depth = self.transaction_depth.saturating_sub(1);
+    Ok(())
+  }
+
+  #[inline]
+  async fn row(&mut self, cmd: &str) -> crate::Result<Self::Row> {
+    track_cmd!(self, (**self).row(cmd))
+  }
+
+  #[inline]
+  async fn row_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<Self::Row> {
+    track_cmd!(self, (**self).row_with(cmd, params))
+  }
+
+  #[inline]
+  async fn rows<E>(
+    &mut self,
+    cmd: &str,
+    cb: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+  {
+    track_cmd!(self, (**self).rows(cmd, cb))
+  }
+
+  #[inline]
+  async fn rows_with<E>(
+    &mut self,
+    cmd: &str,
+    params: &[SqlParam<'_>],
+    cb: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+  {
+    track_cmd!(self, (**self).rows_with(cmd, params, cb))
+  }
+
+  #[inline]
+  fn start_rollback(&mut self) {
+    (**self).start_rollback();
+    self.transaction_depth = self.transaction_depth.saturating_sub(1);
+  }
+
+  #[inline]
+  async fn transaction(&mut self, cmd: &str) -> crate::Result<()> {
+    track_cmd!(self, (**self).transaction(cmd))
+  }
+}
+
+#[cfg(feature = "sm")]
+impl<M> crate::sm::SchemaManagement for PoolConnection<'_, M>
+where
+  M: PoolManager,
+  M::Database: crate::sm::SchemaManagement,
+{
+  #[inline]
+  async fn baseline_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    track_cmd!(self, (**self).baseline_migrations(buffer_cmd, mg, migrations))
+  }
+
+  #[inline]
+  async fn clear(
+    &mut self,
+    buffer: (&mut String, &mut Vec<crate::Identifier>),
+  ) -> crate::Result<()> {
+    track_cmd!(self, (**self).clear(buffer))
+  }
+
+  #[inline]
+  async fn create_oapth_tables(&mut self) -> crate::Result<()> {
+    track_cmd!(self, (**self).create_oapth_tables())
+  }
+
+  #[inline]
+  async fn delete_migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    version: i32,
+  ) -> crate::Result<()>
+  where
+    S: AsRef<str>,
+  {
+    track_cmd!(self, (**self).delete_migrations(buffer_cmd, mg, version))
+  }
+
+  #[inline]
+  async fn failed_migrations_len<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    track_cmd!(self, (**self).failed_migrations_len(buffer_cmd, mg))
+  }
+
+  #[inline]
+  async fn has_oapth_tables(&mut self) -> crate::Result<bool> {
+    track_cmd!(self, (**self).has_oapth_tables())
+  }
+
+  #[inline]
+  async fn insert_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+    transaction_mode: crate::sm::TransactionMode,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    track_cmd!(self, (**self).insert_migrations(buffer_cmd, mg, migrations, transaction_mode))
+  }
+
+  #[inline]
+  async fn lock(&mut self, buffer_cmd: &mut String, timeout: Duration) -> crate::Result<()> {
+    track_cmd!(self, (**self).lock(buffer_cmd, timeout))
+  }
+
+  #[inline]
+  async fn migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    results: &mut Vec<crate::sm::DbMigration>,
+  ) -> crate::Result<()>
+  where
+    S: AsRef<str>,
+  {
+    track_cmd!(self, (**self).migrations(buffer_cmd, mg, results))
+  }
+
+  #[inline]
+  async fn repair_migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    entries: &[crate::sm::MigrationRepairEntry],
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    track_cmd!(self, (**self).repair_migrations(buffer_cmd, mg, entries))
+  }
+
+  #[inline]
+  async fn table_names(
+    &mut self,
+    buffer_cmd: &mut String,
+    results: &mut Vec<crate::Identifier>,
+    schema: &str,
+  ) -> crate::Result<()> {
+    track_cmd!(self, (**self).table_names(buffer_cmd, results, schema))
+  }
+
+  #[inline]
+  async fn unlock(&mut self, buffer_cmd: &mut String) -> crate::Result<()> {
+    track_cmd!(self, (**self).unlock(buffer_cmd))
+  }
+
+  #[inline]
+  async fn update_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+    transaction_mode: crate::sm::TransactionMode,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    track_cmd!(self, (**self).update_migrations(buffer_cmd, mg, migrations, transaction_mode))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    database::{Database, Pool, PoolManager, PoolParams, SqlParam},
+    Config, DatabaseTy,
+  };
+  use core::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+  };
+
+  // Every command waits forever
+  #[derive(Debug)]
+  struct PendingDatabase;
+
+  impl Database for PendingDatabase {
+    const TY: DatabaseTy = DatabaseTy::Unit;
+
+    type Row = ();
+
+    async fn begin_transaction(&mut self) -> crate::Result<()> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn commit_transaction(&mut self) -> crate::Result<()> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn execute(&mut self, _: &str) -> crate::Result<()> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn execute_with(&mut self, _: &str, _: &[SqlParam<'_>]) -> crate::Result<u64> {
+      core::future::pending::<()>().await;
+      Ok(0)
+    }
+
+    async fn rollback_transaction(&mut self) -> crate::Result<()> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn row(&mut self, _: &str) -> crate::Result<Self::Row> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn row_with(&mut self, _: &str, _: &[SqlParam<'_>]) -> crate::Result<Self::Row> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn rows<E>(&mut self, _: &str, _: impl FnMut(Self::Row) -> Result<(), E>) -> Result<(), E>
+    where
+      E: From<crate::Error>,
+    {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    async fn rows_with<E>(
+      &mut self,
+      _: &str,
+      _: &[SqlParam<'_>],
+      _: impl FnMut(Self::Row) -> Result<(), E>,
+    ) -> Result<(), E>
+    where
+      E: From<crate::Error>,
+    {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+
+    fn start_rollback(&mut self) {}
+
+    async fn transaction(&mut self, _: &str) -> crate::Result<()> {
+      core::future::pending::<()>().await;
+      Ok(())
+    }
+  }
+
+  struct PendingManager;
+
+  impl PoolManager for PendingManager {
+    type Database = PendingDatabase;
+
+    async fn connect(&self) -> crate::Result<PendingDatabase> {
+      Ok(PendingDatabase)
+    }
+  }
+
+  #[derive(Debug, Default)]
+  struct TestManager {
+    connects: AtomicUsize,
+    is_valid: AtomicBool,
+  }
+
+  impl PoolManager for TestManager {
+    type Database = ();
+
+    async fn connect(&self) -> crate::Result<()> {
+      let _ = self.connects.fetch_add(1, Ordering::Relaxed);
+      Ok(())
+    }
+
+    async fn is_valid(&self, _: &mut ()) -> bool {
+      self.is_valid.load(Ordering::Relaxed)
+    }
+  }
+
+  #[tokio::test]
+  async fn idle_connections_are_reused_while_valid() {
+    let pool = Pool::new(TestManager::default(), PoolParams::default());
+    pool.manager().is_valid.store(true, Ordering::Relaxed);
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().connects.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.idle_len(), 1);
+    pool.manager().is_valid.store(false, Ordering::Relaxed);
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().connects.load(Ordering::Relaxed), 2);
+  }
+
+  #[tokio::test]
+  async fn connections_in_the_middle_of_commands_are_closed() {
+    let mut params = PoolParams::default();
+    params.set_health_check(false);
+    let pool = Pool::new(PendingManager, params);
+    let mut conn = pool.get().await.unwrap();
+    let rslt = tokio::time::timeout(Duration::from_millis(1), conn.execute("")).await;
+    assert!(rslt.is_err());
+    drop(conn);
+    assert_eq!(pool.idle_len(), 0);
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.idle_len(), 1);
+  }
+
+  #[tokio::test]
+  async fn connections_in_the_middle_of_transactions_are_closed() {
+    let mut params = PoolParams::default();
+    params.set_health_check(false);
+    let pool = Pool::new(TestManager::default(), params);
+    let mut conn = pool.get().await.unwrap();
+    conn.begin_transaction().await.unwrap();
+    drop(conn);
+    assert_eq!(pool.idle_len(), 0);
+    let mut conn = pool.get().await.unwrap();
+    conn.begin_transaction().await.unwrap();
+    conn.commit_transaction().await.unwrap();
+    drop(conn);
+    assert_eq!(pool.idle_len(), 1);
+    // Scheduled to be rolled back before the next command
+    let mut conn = pool.get().await.unwrap();
+    drop(conn.begin().await.unwrap());
+    drop(conn);
+    assert_eq!(pool.idle_len(), 1);
+  }
+
+  #[tokio::test]
+  async fn expired_connections_are_closed() {
+    let mut params = PoolParams::default();
+    params.set_health_check(false);
+    params.set_idle_timeout(Some(Duration::ZERO));
+    let pool = Pool::new(TestManager::default(), params);
+    drop((pool.get().await.unwrap(), pool.get().await.unwrap()));
+    assert_eq!(pool.idle_len(), 2);
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.manager().connects.load(Ordering::Relaxed), 3);
+    assert_eq!(pool.idle_len(), 1);
+  }
+
+  #[tokio::test]
+  async fn waits_at_most_the_acquire_timeout() {
+    let mut params = PoolParams::default();
+    params.set_acquire_timeout(Duration::from_millis(10));
+    params.set_max_size(1);
+    let pool = Pool::new(TestManager::default(), params);
+    let conn = pool.get().await.unwrap();
+    assert!(matches!(pool.get().await, Err(crate::Error::PoolAcquireTimeout)));
+    drop(conn);
+    assert!(pool.get().await.is_ok());
+  }
+
+  #[test]
+  fn params_are_read_from_the_url() {
+    let url = "postgres://u:p@h:1/db?pool_max_size=4&sslmode=disable&pool_idle_timeout=0";
+    let params = Config::with_url(url).pool_params().unwrap();
+    assert_eq!(params.max_size(), 4);
+    assert_eq!(params.idle_timeout(), None);
+    assert_eq!(params.acquire_timeout(), super::DEFAULT_POOL_ACQUIRE_TIMEOUT);
+    let manager = super::ConfigManager::<()>::new(&Config::with_url(url));
+    assert_eq!(manager.config().url(), url);
+    let url = "sqlite::memory:?pool_health_check=foo";
+    assert!(Config::with_url(url).pool_params().is_err());
+  }
+}
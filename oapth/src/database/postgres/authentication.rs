@@ -0,0 +1,171 @@
+use alloc::{string::String, vec::Vec};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use core::fmt::Write;
+use hmac::{Hmac, Mac};
+use md5::{Digest, Md5};
+use sha2::Sha256;
+
+pub(crate) const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+// "md5" + md5(md5(password + user) + salt)
+#[inline]
+pub(crate) fn md5_password(password: &str, salt: [u8; 4], user: &str) -> crate::Result<String> {
+  let mut rslt = String::from("md5");
+  let mut inner = String::new();
+  push_hex(&mut inner, &Md5::new().chain_update(password).chain_update(user).finalize())?;
+  let outer = Md5::new().chain_update(inner).chain_update(salt).finalize();
+  push_hex(&mut rslt, &outer)?;
+  Ok(rslt)
+}
+
+/// Client side of a SCRAM-SHA-256 exchange without channel binding, see RFC 5802 and RFC 7677.
+///
+/// Passwords are used as is, i.e., without SASLprep normalization.
+pub(crate) struct Scram {
+  auth_message: String,
+  salted_password: [u8; 32],
+}
+
+impl Scram {
+  #[inline]
+  pub(crate) fn client_first(nonce: &str, user: &str) -> String {
+    let mut rslt = String::from("n,,");
+    push_client_first_bare(&mut rslt, nonce, user);
+    rslt
+  }
+
+  /// Builds the client final message from the server first message.
+  #[inline]
+  pub(crate) fn client_final(
+    client_nonce: &str,
+    password: &str,
+    server_first: &str,
+    user: &str,
+  ) -> crate::Result<(String, Self)> {
+    let mut nonce = None;
+    let mut salt = None;
+    let mut iterations = None;
+    for attribute in server_first.split(',') {
+      if let Some(elem) = attribute.strip_prefix("r=") {
+        nonce = Some(elem);
+      } else if let Some(elem) = attribute.strip_prefix("s=") {
+        salt = Some(STANDARD.decode(elem).map_err(|_err| crate::Error::InvalidPostgresMessage)?);
+      } else if let Some(elem) = attribute.strip_prefix("i=") {
+        iterations =
+          Some(elem.parse::<u32>().map_err(|_err| crate::Error::InvalidPostgresMessage)?);
+      }
+    }
+    let (Some(nonce), Some(salt), Some(iterations)) = (nonce, salt, iterations) else {
+      return Err(crate::Error::InvalidPostgresMessage);
+    };
+    if !nonce.starts_with(client_nonce) || iterations == 0 {
+      return Err(crate::Error::InvalidPostgresMessage);
+    }
+    let salted_password = hi(password.as_bytes(), &salt, iterations);
+    let client_key = hmac(&salted_password, b"Client Key");
+    let stored_key: [u8; 32] = Sha256::digest(client_key).into();
+    let mut rslt = String::from("c=biws,r=");
+    rslt.push_str(nonce);
+    let mut auth_message = String::new();
+    push_client_first_bare(&mut auth_message, client_nonce, user);
+    auth_message.push(',');
+    auth_message.push_str(server_first);
+    auth_message.push(',');
+    auth_message.push_str(&rslt);
+    let client_signature = hmac(&stored_key, auth_message.as_bytes());
+    let mut proof = client_key;
+    for (proof_byte, signature_byte) in proof.iter_mut().zip(client_signature) {
+      *proof_byte ^= signature_byte;
+    }
+    rslt.push_str(",p=");
+    STANDARD.encode_string(proof, &mut rslt);
+    Ok((rslt, Self { auth_message, salted_password }))
+  }
+
+  /// Verifies the signature contained in the server final message.
+  #[inline]
+  pub(crate) fn verify_server_final(&self, server_final: &str) -> crate::Result<()> {
+    let Some(signature) = server_final.strip_prefix("v=") else {
+      return Err(crate::Error::InvalidPostgresMessage);
+    };
+    let server_key = hmac(&self.salted_password, b"Server Key");
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&server_key)
+      .map_err(|_err| crate::Error::InvalidPostgresMessage)?;
+    mac.update(self.auth_message.as_bytes());
+    let decoded =
+      STANDARD.decode(signature).map_err(|_err| crate::Error::InvalidPostgresMessage)?;
+    mac.verify_slice(&decoded).map_err(|_err| crate::Error::InvalidPostgresMessage)
+  }
+}
+
+// PostgreSQL ignores the user name because it is already sent in the startup message.
+#[inline]
+fn push_client_first_bare(buffer: &mut String, nonce: &str, user: &str) {
+  buffer.push_str("n=");
+  buffer.push_str(user);
+  buffer.push_str(",r=");
+  buffer.push_str(nonce);
+}
+
+#[inline]
+fn hi(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+  let mut salt_with_idx = Vec::with_capacity(salt.len().wrapping_add(4));
+  salt_with_idx.extend_from_slice(salt);
+  salt_with_idx.extend_from_slice(&1u32.to_be_bytes());
+  let mut prev = hmac(password, &salt_with_idx);
+  let mut rslt = prev;
+  for _ in 1..iterations {
+    prev = hmac(password, &prev);
+    for (rslt_byte, prev_byte) in rslt.iter_mut().zip(prev) {
+      *rslt_byte ^= prev_byte;
+    }
+  }
+  rslt
+}
+
+#[inline]
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+  let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+  mac.update(data);
+  mac.finalize().into_bytes().into()
+}
+
+#[inline]
+fn push_hex(buffer: &mut String, bytes: &[u8]) -> crate::Result<()> {
+  for byte in bytes {
+    buffer.write_fmt(format_args!("{byte:02x}"))?;
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::database::postgres::authentication::{md5_password, Scram};
+
+  // https://datatracker.ietf.org/doc/html/rfc7677#section-3
+  #[test]
+  fn scram_matches_rfc_7677() {
+    let client_nonce = "rOprNGfwEbeRWgbNEkqO";
+    let server_first = "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+      s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    assert_eq!(Scram::client_first(client_nonce, "user"), "n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+    let (client_final, scram) =
+      Scram::client_final(client_nonce, "pencil", server_first, "user").unwrap();
+    assert_eq!(
+      client_final,
+      "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+      p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+    );
+    scram.verify_server_final("v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=").unwrap();
+    assert!(scram.verify_server_final("v=AAAA").is_err());
+    assert!(Scram::client_final("other", "pencil", server_first, "user").is_err());
+  }
+
+  #[test]
+  fn md5_password_hashes_password_user_and_salt() {
+    assert_eq!(
+      md5_password("password", [1, 2, 3, 4], "user").unwrap(),
+      "md5a3576f1ae039b8996bc4fc2720f9c71a"
+    );
+  }
+}
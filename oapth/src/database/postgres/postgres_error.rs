@@ -0,0 +1,35 @@
+use alloc::string::String;
+
+/// Error reported by a PostgreSQL server through an `ErrorResponse` message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PostgresError {
+  code: String,
+  message: String,
+  severity: String,
+}
+
+impl PostgresError {
+  /// Creates a new instance from all necessary parameters.
+  #[inline]
+  pub fn new(code: String, message: String, severity: String) -> Self {
+    Self { code, message, severity }
+  }
+
+  /// SQLSTATE code, for example, `42P01` for undefined tables.
+  #[inline]
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  /// Primary human-readable message
+  #[inline]
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// `ERROR`, `FATAL` or `PANIC`
+  #[inline]
+  pub fn severity(&self) -> &str {
+    &self.severity
+  }
+}
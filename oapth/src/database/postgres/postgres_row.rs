@@ -0,0 +1,271 @@
+use crate::FromSqlColumn;
+use alloc::{string::String, sync::Arc, vec::Vec};
+use arrayvec::ArrayString;
+use core::ops::Range;
+
+/// Row retrieved by the [crate::database::Postgres] backend.
+///
+/// Values are stored in the text format sent by the server.
+#[derive(Debug)]
+pub struct PostgresRow {
+  bytes: Vec<u8>,
+  columns: Arc<PostgresColumns>,
+  values: Vec<Option<Range<usize>>>,
+}
+
+impl PostgresRow {
+  #[inline]
+  pub(crate) fn new(
+    bytes: Vec<u8>,
+    columns: Arc<PostgresColumns>,
+    values: Vec<Option<Range<usize>>>,
+  ) -> Self {
+    Self { bytes, columns, values }
+  }
+
+  /// Number of columns
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+
+  /// If there are no columns
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+
+  /// Name of the column located at `idx`
+  #[inline]
+  pub fn name(&self, idx: usize) -> Option<&str> {
+    self.columns.names.get(idx).map(String::as_str)
+  }
+
+  /// Text representation of a column. `None` if the value is `NULL`.
+  #[inline]
+  pub fn opt_str<C>(&self, column: C) -> crate::Result<Option<&str>>
+  where
+    C: PostgresColumn,
+  {
+    let idx = column.idx(&self.columns.names).ok_or(crate::Error::InvalidSqlQuery)?;
+    let Some(range) = self.values.get(idx).ok_or(crate::Error::InvalidSqlQuery)?.clone() else {
+      return Ok(None);
+    };
+    let bytes = self.bytes.get(range).ok_or(crate::Error::InvalidPostgresMessage)?;
+    Ok(Some(core::str::from_utf8(bytes).map_err(|_err| crate::Error::InvalidPostgresMessage)?))
+  }
+
+  /// Name of the type of the column located at `idx`. Only built-in types are known.
+  #[inline]
+  pub fn ty(&self, idx: usize) -> Option<&str> {
+    Some(match self.columns.tys.get(idx)? {
+      16 => "bool",
+      17 => "bytea",
+      20 => "int8",
+      21 => "int2",
+      23 => "int4",
+      25 => "text",
+      114 => "json",
+      700 => "float4",
+      701 => "float8",
+      1042 => "bpchar",
+      1043 => "varchar",
+      1082 => "date",
+      1083 => "time",
+      1114 => "timestamp",
+      1184 => "timestamptz",
+      1700 => "numeric",
+      2950 => "uuid",
+      3802 => "jsonb",
+      _ => return None,
+    })
+  }
+
+  #[inline]
+  pub(crate) fn decode<'this, C, T>(&'this self, column: C) -> crate::Result<T>
+  where
+    C: PostgresColumn,
+    T: FromPostgresText<'this>,
+  {
+    T::from_text(self.opt_str(column)?.ok_or(crate::Error::InvalidSqlQuery)?)
+  }
+}
+
+/// Identifies a column of a [PostgresRow] by its index or by its name.
+pub trait PostgresColumn {
+  /// Index of the column
+  fn idx(&self, names: &[String]) -> Option<usize>;
+}
+
+impl PostgresColumn for usize {
+  #[inline]
+  fn idx(&self, names: &[String]) -> Option<usize> {
+    (*self < names.len()).then_some(*self)
+  }
+}
+
+impl PostgresColumn for &str {
+  #[inline]
+  fn idx(&self, names: &[String]) -> Option<usize> {
+    names.iter().position(|name| name == self)
+  }
+}
+
+// Names and type OIDs shared by all rows of the same result.
+#[derive(Debug, Default)]
+pub(crate) struct PostgresColumns {
+  names: Vec<String>,
+  tys: Vec<u32>,
+}
+
+impl PostgresColumns {
+  #[inline]
+  pub(crate) fn with_capacity(capacity: usize) -> Self {
+    Self { names: Vec::with_capacity(capacity), tys: Vec::with_capacity(capacity) }
+  }
+
+  #[inline]
+  pub(crate) fn push(&mut self, name: String, ty: u32) {
+    self.names.push(name);
+    self.tys.push(ty);
+  }
+}
+
+pub(crate) trait FromPostgresText<'text>: Sized {
+  fn from_text(text: &'text str) -> crate::Result<Self>;
+}
+
+impl<'text> FromPostgresText<'text> for &'text str {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    Ok(text)
+  }
+}
+
+impl<'text, const N: usize> FromPostgresText<'text> for ArrayString<N> {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    Ok(text.try_into()?)
+  }
+}
+
+impl<'text> FromPostgresText<'text> for bool {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    match text {
+      "t" => Ok(true),
+      "f" => Ok(false),
+      _ => Err(crate::Error::InvalidSqlQuery),
+    }
+  }
+}
+
+impl<'text> FromPostgresText<'text> for String {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    Ok(text.into())
+  }
+}
+
+// `bytea` values are sent in the hex format, i.e., `\x` followed by two digits per byte.
+impl<'text> FromPostgresText<'text> for Vec<u8> {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    let digits = text.strip_prefix("\\x").ok_or(crate::Error::InvalidSqlQuery)?;
+    digits
+      .as_bytes()
+      .chunks(2)
+      .map(|chunk| {
+        let s = core::str::from_utf8(chunk).map_err(|_err| crate::Error::InvalidSqlQuery)?;
+        u8::from_str_radix(s, 16).map_err(|_err| crate::Error::InvalidSqlQuery)
+      })
+      .collect()
+  }
+}
+
+macro_rules! impl_from_str {
+  ($($ty:ty),*) => {
+    $(
+      impl<'text> FromPostgresText<'text> for $ty {
+        #[inline]
+        fn from_text(text: &'text str) -> crate::Result<Self> {
+          text.parse().map_err(|_err| crate::Error::InvalidSqlQuery)
+        }
+      }
+    )*
+  };
+}
+
+impl_from_str!(f32, f64, i16, i32, i64);
+#[cfg(feature = "rust_decimal")]
+impl_from_str!(rust_decimal::Decimal);
+#[cfg(feature = "serde_json")]
+impl_from_str!(serde_json::Value);
+#[cfg(feature = "uuid")]
+impl_from_str!(uuid::Uuid);
+
+#[cfg(feature = "chrono")]
+impl<'text> FromPostgresText<'text> for chrono::DateTime<chrono::Utc> {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    Ok(chrono::DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z")?.into())
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl<'text> FromPostgresText<'text> for chrono::NaiveDate {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    Ok(chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")?)
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl<'text> FromPostgresText<'text> for chrono::NaiveDateTime {
+  #[inline]
+  fn from_text(text: &'text str) -> crate::Result<Self> {
+    Ok(chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")?)
+  }
+}
+
+macro_rules! impl_from_sql_column {
+  (@impl [$($generics:tt)*] $ty:ty) => {
+    impl<$($generics)*> FromSqlColumn<PostgresRow> for $ty {
+      #[inline]
+      fn from_column_idx(row: &PostgresRow, idx: usize) -> crate::Result<Self> {
+        row.decode(idx)
+      }
+
+      #[inline]
+      fn from_column_name(row: &PostgresRow, name: &str) -> crate::Result<Self> {
+        row.decode(name)
+      }
+    }
+
+    impl<$($generics)*> FromSqlColumn<PostgresRow> for Option<$ty> {
+      #[inline]
+      fn from_column_idx(row: &PostgresRow, idx: usize) -> crate::Result<Self> {
+        row.opt_str(idx)?.map(<$ty>::from_text).transpose()
+      }
+
+      #[inline]
+      fn from_column_name(row: &PostgresRow, name: &str) -> crate::Result<Self> {
+        row.opt_str(name)?.map(<$ty>::from_text).transpose()
+      }
+    }
+  };
+  ($($ty:ty),* $(,)?) => {
+    $(impl_from_sql_column!(@impl [] $ty);)*
+  };
+}
+
+impl_from_sql_column!(@impl [const N: usize] ArrayString<N>);
+impl_from_sql_column!(String, Vec<u8>, bool, f32, f64, i16, i32, i64);
+#[cfg(feature = "chrono")]
+impl_from_sql_column!(chrono::DateTime<chrono::Utc>, chrono::NaiveDate, chrono::NaiveDateTime);
+#[cfg(feature = "rust_decimal")]
+impl_from_sql_column!(rust_decimal::Decimal);
+#[cfg(feature = "serde_json")]
+impl_from_sql_column!(serde_json::Value);
+#[cfg(feature = "uuid")]
+impl_from_sql_column!(uuid::Uuid);
@@ -0,0 +1,63 @@
+/// Typed value bound to a placeholder of a parameterized command.
+///
+/// Placeholders are written as `$1`, `$2`, ... in PostgreSQL, as `@P1`, `@P2`, ... in MS-SQL
+/// and as `?` in MySQL and SQLite.
+///
+/// Prepared statements can be cached by their commands, therefore successive executions of the
+/// same command should bind the same variants, with the exception of [SqlParam::Null].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SqlParam<'value> {
+  /// Boolean
+  Bool(bool),
+  /// Binary data
+  Bytes(&'value [u8]),
+  /// Timestamp with time zone, normalized to UTC
+  #[cfg(feature = "chrono")]
+  DateTime(chrono::DateTime<chrono::Utc>),
+  /// Decimal
+  #[cfg(feature = "rust_decimal")]
+  Decimal(rust_decimal::Decimal),
+  /// 32-bit floating point number
+  F32(f32),
+  /// 64-bit floating point number
+  F64(f64),
+  /// 32-bit integer
+  I32(i32),
+  /// 64-bit integer
+  I64(i64),
+  /// JSON document
+  #[cfg(feature = "serde_json")]
+  Json(&'value serde_json::Value),
+  /// Date without time zone
+  #[cfg(feature = "chrono")]
+  NaiveDate(chrono::NaiveDate),
+  /// Timestamp without time zone
+  #[cfg(feature = "chrono")]
+  NaiveDateTime(chrono::NaiveDateTime),
+  /// SQL `NULL`
+  Null,
+  /// Text
+  Str(&'value str),
+  /// UUID
+  #[cfg(feature = "uuid")]
+  Uuid(uuid::Uuid),
+}
+
+/// Format of timestamps with time zone, which are always normalized to UTC.
+#[cfg(all(feature = "chrono", any(feature = "orm", feature = "postgres", feature = "tiberius")))]
+pub(crate) const DATE_TIME_FMT: &str = "%Y-%m-%d %H:%M:%S%.f+00:00";
+
+// PostgreSQL's text representation of the floating point numbers that aren't finite.
+#[cfg(any(feature = "orm", feature = "postgres"))]
+#[inline]
+pub(crate) fn non_finite_float_str(elem: f64) -> Option<&'static str> {
+  if elem.is_nan() {
+    Some("NaN")
+  } else if elem == f64::INFINITY {
+    Some("Infinity")
+  } else if elem == f64::NEG_INFINITY {
+    Some("-Infinity")
+  } else {
+    None
+  }
+}
@@ -1,8 +1,24 @@
-use crate::{database::Database, Config, DatabaseTy};
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-postgres"))]
+use crate::SslMode;
+use crate::{
+  database::{Database, SqlParam},
+  Config, DatabaseTy, FromSqlColumn,
+};
 #[cfg(feature = "sm")]
 use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
 use futures::TryStreamExt;
-use sqlx_core::{connection::Connection, executor::Executor, pool::PoolConnection, query::query};
+use sqlx_core::{
+  connection::Connection,
+  database::{Database as Db, HasArguments},
+  decode::Decode,
+  executor::Executor,
+  pool::PoolConnection,
+  query::{query, Query},
+  row::Row,
+  transaction::TransactionManager,
+  types::Type,
+};
 
 macro_rules! create_sqlx_backend {
   (
@@ -10,9 +26,13 @@ macro_rules! create_sqlx_backend {
     $backend_name:ident,
     $clear:expr,
     $conn_ty:ty,
-    $create_oapth_tables:expr,
+    $connect_options:expr,
+    ($create_oapth_tables:expr, $has_oapth_tables:expr),
     $db:ty,
     $db_ty:expr,
+    $decimal:expr,
+    ($lock:expr, $unlock:expr),
+    $null:expr,
     ($schema:expr, $mysql_schema:expr),
     $row:ty,
     $tables:expr,
@@ -27,26 +47,72 @@ macro_rules! create_sqlx_backend {
       $(#[$new_doc])+
       #[inline]
       pub async fn new(config: &Config) -> crate::Result<Self> {
-        let conn = <$conn_ty>::connect(config.url()).await?;
+        let options = $connect_options(config)?;
+        let conn = if let Some(elem) = config.connect_timeout()? {
+          sqlx_core::rt::timeout(elem, <$conn_ty>::connect_with(&options))
+            .await
+            .map_err(|_err| crate::Error::ConnectTimeout)??
+        } else {
+          <$conn_ty>::connect_with(&options).await?
+        };
         Ok(Self { conn })
       }
     }
 
+    #[cfg(feature = "pool")]
+    impl crate::database::PoolManager for crate::database::ConfigManager<$backend_name> {
+      type Database = $backend_name;
+
+      #[inline]
+      async fn connect(&self) -> crate::Result<Self::Database> {
+        $backend_name::new(self.config()).await
+      }
+    }
+
     impl Database for $backend_name {
       const TY: DatabaseTy = $db_ty;
 
       type Row = $row;
 
+      #[inline]
+      async fn begin_transaction(&mut self) -> crate::Result<()> {
+        Ok(<$db as Db>::TransactionManager::begin(&mut self.conn).await?)
+      }
+
+      #[inline]
+      async fn commit_transaction(&mut self) -> crate::Result<()> {
+        Ok(<$db as Db>::TransactionManager::commit(&mut self.conn).await?)
+      }
+
       #[inline]
       async fn execute(&mut self, cmd: &str) -> crate::Result<()> {
         Ok(self.conn.execute(cmd).await.map(|_| {})?)
       }
 
+      #[inline]
+      async fn execute_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<u64> {
+        Ok(<$db>::bind_params(query(cmd), params).execute(&mut self.conn).await?.rows_affected())
+      }
+
+      #[inline]
+      async fn rollback_transaction(&mut self) -> crate::Result<()> {
+        Ok(<$db as Db>::TransactionManager::rollback(&mut self.conn).await?)
+      }
+
       #[inline]
       async fn row(&mut self, cmd: &str) -> crate::Result<Self::Row> {
         Ok(query(cmd).fetch_one(&mut self.conn).await?)
       }
 
+      #[inline]
+      async fn row_with(
+        &mut self,
+        cmd: &str,
+        params: &[SqlParam<'_>],
+      ) -> crate::Result<Self::Row> {
+        Ok(<$db>::bind_params(query(cmd), params).fetch_one(&mut self.conn).await?)
+      }
+
       #[inline]
       async fn rows<E>(
         &mut self,
@@ -63,6 +129,28 @@ macro_rules! create_sqlx_backend {
         Ok(())
       }
 
+      #[inline]
+      async fn rows_with<E>(
+        &mut self,
+        cmd: &str,
+        params: &[SqlParam<'_>],
+        mut cb: impl FnMut(Self::Row) -> Result<(), E>,
+      ) -> Result<(), E>
+      where
+        E: From<crate::Error>
+      {
+        let mut stream = <$db>::bind_params(query(cmd), params).fetch(&mut self.conn);
+        while let Some(row) = stream.try_next().await.map_err(From::from)? {
+          cb(row)?;
+        }
+        Ok(())
+      }
+
+      #[inline]
+      fn start_rollback(&mut self) {
+        <$db as Db>::TransactionManager::start_rollback(&mut self.conn);
+      }
+
       #[inline]
       async fn transaction(&mut self, cmd: &str) -> crate::Result<()> {
         let mut transaction = self.conn.begin().await?;
@@ -72,21 +160,101 @@ macro_rules! create_sqlx_backend {
       }
     }
 
+    // `Decimal` and `NULL` are bound through `$decimal` and `$null` because not every backend
+    // supports decimals and each backend has its own way of expressing values without a type.
+    impl<'args> BindParams<'args> for $db {
+      #[inline]
+      fn bind_params(
+        mut query: SqlxQuery<'args, Self>,
+        params: &'args [SqlParam<'args>],
+      ) -> SqlxQuery<'args, Self> {
+        for param in params {
+          query = match *param {
+            SqlParam::Bool(elem) => query.bind(elem),
+            SqlParam::Bytes(elem) => query.bind(elem),
+            #[cfg(feature = "chrono")]
+            SqlParam::DateTime(elem) => query.bind(elem),
+            #[cfg(feature = "rust_decimal")]
+            SqlParam::Decimal(elem) => query.bind(($decimal)(elem)),
+            SqlParam::F32(elem) => query.bind(elem),
+            SqlParam::F64(elem) => query.bind(elem),
+            SqlParam::I32(elem) => query.bind(elem),
+            SqlParam::I64(elem) => query.bind(elem),
+            #[cfg(feature = "serde_json")]
+            SqlParam::Json(elem) => query.bind(elem),
+            #[cfg(feature = "chrono")]
+            SqlParam::NaiveDate(elem) => query.bind(elem),
+            #[cfg(feature = "chrono")]
+            SqlParam::NaiveDateTime(elem) => query.bind(elem),
+            SqlParam::Null => query.bind($null),
+            SqlParam::Str(elem) => query.bind(elem),
+            #[cfg(feature = "uuid")]
+            SqlParam::Uuid(elem) => query.bind(elem),
+          };
+        }
+        query
+      }
+    }
+
+    impl<T> FromSqlColumn<$row> for T
+    where
+      T: for<'row> Decode<'row, $db> + Type<$db>,
+    {
+      #[inline]
+      fn from_column_idx(row: &$row, idx: usize) -> crate::Result<Self> {
+        Ok(row.try_get(idx)?)
+      }
+
+      #[inline]
+      fn from_column_name(row: &$row, name: &str) -> crate::Result<Self> {
+        Ok(row.try_get(name)?)
+      }
+    }
+
     impl Database for PoolConnection<$db> {
       const TY: DatabaseTy = $db_ty;
 
       type Row = $row;
 
+      #[inline]
+      async fn begin_transaction(&mut self) -> crate::Result<()> {
+        Ok(<$db as Db>::TransactionManager::begin(&mut **self).await?)
+      }
+
+      #[inline]
+      async fn commit_transaction(&mut self) -> crate::Result<()> {
+        Ok(<$db as Db>::TransactionManager::commit(&mut **self).await?)
+      }
+
       #[inline]
       async fn execute(&mut self, cmd: &str) -> crate::Result<()> {
         Ok((**self).execute(cmd).await.map(|_| {})?)
       }
 
+      #[inline]
+      async fn execute_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<u64> {
+        Ok(<$db>::bind_params(query(cmd), params).execute(&mut **self).await?.rows_affected())
+      }
+
+      #[inline]
+      async fn rollback_transaction(&mut self) -> crate::Result<()> {
+        Ok(<$db as Db>::TransactionManager::rollback(&mut **self).await?)
+      }
+
       #[inline]
       async fn row(&mut self, cmd: &str) -> crate::Result<Self::Row> {
         Ok(query(cmd).fetch_one(&mut **self).await?)
       }
 
+      #[inline]
+      async fn row_with(
+        &mut self,
+        cmd: &str,
+        params: &[SqlParam<'_>],
+      ) -> crate::Result<Self::Row> {
+        Ok(<$db>::bind_params(query(cmd), params).fetch_one(&mut **self).await?)
+      }
+
       #[inline]
       async fn rows<E>(
         &mut self,
@@ -103,6 +271,28 @@ macro_rules! create_sqlx_backend {
         Ok(())
       }
 
+      #[inline]
+      async fn rows_with<E>(
+        &mut self,
+        cmd: &str,
+        params: &[SqlParam<'_>],
+        mut cb: impl FnMut(Self::Row) -> Result<(), E>,
+      ) -> Result<(), E>
+      where
+        E: From<crate::Error>
+      {
+        let mut stream = <$db>::bind_params(query(cmd), params).fetch(&mut **self);
+        while let Some(row) = stream.try_next().await.map_err(From::from)? {
+          cb(row)?;
+        }
+        Ok(())
+      }
+
+      #[inline]
+      fn start_rollback(&mut self) {
+        <$db as Db>::TransactionManager::start_rollback(&mut **self);
+      }
+
       #[inline]
       async fn transaction(&mut self, cmd: &str) -> crate::Result<()> {
         let mut transaction = self.begin().await?;
@@ -114,6 +304,28 @@ macro_rules! create_sqlx_backend {
 
     #[cfg(feature = "sm")]
     impl crate::sm::SchemaManagement for $backend_name {
+      #[inline]
+      async fn baseline_migrations<'migration, DBS, I, S>(
+        &mut self,
+        buffer_cmd: &mut String,
+        mg: &crate::sm::MigrationGroup<S>,
+        migrations: I,
+      ) -> crate::Result<()>
+      where
+        DBS: AsRef<[DatabaseTy]> + 'migration,
+        I: Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+        S: AsRef<str> + 'migration,
+      {
+        crate::sm::fixed_sql_commands::_baseline_migrations(
+          buffer_cmd,
+          self,
+          mg,
+          migrations,
+          $schema,
+        )
+        .await
+      }
+
       #[inline]
       async fn clear(
         &mut self,
@@ -124,7 +336,7 @@ macro_rules! create_sqlx_backend {
 
       #[inline]
       async fn create_oapth_tables(&mut self) -> crate::Result<()> {
-        self.execute($create_oapth_tables).await
+        $create_oapth_tables(self).await
       }
 
       #[inline]
@@ -147,12 +359,30 @@ macro_rules! create_sqlx_backend {
         .await
       }
 
+      #[inline]
+      async fn failed_migrations_len<S>(
+        &mut self,
+        buffer_cmd: &mut String,
+        mg: &crate::sm::MigrationGroup<S>,
+      ) -> crate::Result<u64>
+      where
+        S: AsRef<str>,
+      {
+        crate::sm::fixed_sql_commands::_failed_migrations_len(buffer_cmd, self, mg, $schema).await
+      }
+
+      #[inline]
+      async fn has_oapth_tables(&mut self) -> crate::Result<bool> {
+        $has_oapth_tables(self).await
+      }
+
       #[inline]
       async fn insert_migrations<'migration, DBS, I, S>(
         &mut self,
         buffer_cmd: &mut String,
         mg: &crate::sm::MigrationGroup<S>,
         migrations: I,
+        transaction_mode: crate::sm::TransactionMode,
       ) -> crate::Result<()>
       where
         DBS: AsRef<[DatabaseTy]> + 'migration,
@@ -165,10 +395,20 @@ macro_rules! create_sqlx_backend {
           mg,
           migrations,
           $schema,
+          transaction_mode,
         )
         .await
       }
 
+      #[inline]
+      async fn lock(
+        &mut self,
+        buffer_cmd: &mut String,
+        timeout: core::time::Duration,
+      ) -> crate::Result<()> {
+        $lock(buffer_cmd, self, timeout).await
+      }
+
       #[inline]
       async fn migrations<S>(
         &mut self,
@@ -189,6 +429,20 @@ macro_rules! create_sqlx_backend {
         .await
       }
 
+      #[inline]
+      async fn repair_migrations<S>(
+        &mut self,
+        buffer_cmd: &mut String,
+        mg: &crate::sm::MigrationGroup<S>,
+        entries: &[crate::sm::MigrationRepairEntry],
+      ) -> crate::Result<u64>
+      where
+        S: AsRef<str>,
+      {
+        crate::sm::fixed_sql_commands::_repair_migrations(buffer_cmd, self, mg, entries, $schema)
+          .await
+      }
+
       #[inline]
       async fn table_names(
         &mut self,
@@ -203,10 +457,50 @@ macro_rules! create_sqlx_backend {
         };
         $tables(buffer_cmd, self, results, actual_schema).await
       }
+
+      #[inline]
+      async fn unlock(&mut self, buffer_cmd: &mut String) -> crate::Result<()> {
+        $unlock(buffer_cmd, self).await
+      }
+
+      #[inline]
+      async fn update_migrations<'migration, DBS, I, S>(
+        &mut self,
+        buffer_cmd: &mut String,
+        mg: &crate::sm::MigrationGroup<S>,
+        migrations: I,
+        transaction_mode: crate::sm::TransactionMode,
+      ) -> crate::Result<()>
+      where
+        DBS: AsRef<[DatabaseTy]> + 'migration,
+        I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+        S: AsRef<str> + 'migration,
+      {
+        crate::sm::fixed_sql_commands::_update_migrations(
+          buffer_cmd,
+          self,
+          mg,
+          migrations,
+          $schema,
+          transaction_mode,
+        )
+        .await
+      }
     }
   };
 }
 
+type SqlxQuery<'args, DB> = Query<'args, DB, <DB as HasArguments<'args>>::Arguments>;
+
+// Implemented by every `sqlx` database because the supported types depend on the enabled
+// features.
+trait BindParams<'args>: sqlx_core::database::Database {
+  fn bind_params(
+    query: SqlxQuery<'args, Self>,
+    params: &'args [SqlParam<'args>],
+  ) -> SqlxQuery<'args, Self>;
+}
+
 #[cfg(feature = "sqlx-mysql")]
 create_sqlx_backend!(
   /// Creates a new instance from all necessary parameters.
@@ -222,9 +516,16 @@ create_sqlx_backend!(
   SqlxMysql,
   crate::sm::fixed_sql_commands::mysql::_clear,
   sqlx_mysql::MySqlConnection,
-  crate::sm::fixed_sql_commands::mysql::_CREATE_MIGRATION_TABLES,
+  mysql_connect_options,
+  (
+    crate::sm::fixed_sql_commands::mysql::_create_oapth_tables,
+    crate::sm::fixed_sql_commands::mysql::_has_oapth_tables
+  ),
   sqlx_mysql::MySql,
   DatabaseTy::MySql,
+  core::convert::identity,
+  (crate::sm::fixed_sql_commands::mysql::_lock, crate::sm::fixed_sql_commands::mysql::_unlock),
+  None::<i64>,
   ("", crate::sm::_OAPTH),
   sqlx_mysql::MySqlRow,
   crate::sm::fixed_sql_commands::mysql::_table_names,
@@ -245,9 +546,19 @@ create_sqlx_backend!(
   SqlxPostgres,
   crate::sm::fixed_sql_commands::postgres::_clear,
   sqlx_postgres::PgConnection,
-  crate::sm::fixed_sql_commands::postgres::_CREATE_MIGRATION_TABLES,
+  pg_connect_options,
+  (
+    crate::sm::fixed_sql_commands::postgres::_create_oapth_tables,
+    crate::sm::fixed_sql_commands::postgres::_has_oapth_tables
+  ),
   sqlx_postgres::Postgres,
   DatabaseTy::Postgres,
+  core::convert::identity,
+  (
+    crate::sm::fixed_sql_commands::postgres::_lock,
+    crate::sm::fixed_sql_commands::postgres::_unlock
+  ),
+  PgNull,
   (crate::sm::_OAPTH_SCHEMA_PREFIX, ""),
   sqlx_postgres::PgRow,
   crate::sm::fixed_sql_commands::postgres::_table_names,
@@ -268,10 +579,122 @@ create_sqlx_backend!(
   SqlxSqlite,
   crate::sm::fixed_sql_commands::sqlite::_clear,
   sqlx_sqlite::SqliteConnection,
-  crate::sm::fixed_sql_commands::sqlite::_CREATE_MIGRATION_TABLES,
+  sqlite_connect_options,
+  (
+    crate::sm::fixed_sql_commands::sqlite::_create_oapth_tables,
+    crate::sm::fixed_sql_commands::sqlite::_has_oapth_tables
+  ),
   sqlx_sqlite::Sqlite,
   DatabaseTy::Sqlite,
+  decimal_text,
+  (crate::sm::fixed_sql_commands::sqlite::_lock, crate::sm::fixed_sql_commands::sqlite::_unlock),
+  None::<i64>,
   ("", ""),
   sqlx_sqlite::SqliteRow,
   crate::sm::fixed_sql_commands::sqlite::_table_names,
 );
+
+// SQLite converts textual numbers into the type of the target column.
+#[cfg(all(feature = "rust_decimal", feature = "sqlx-sqlite"))]
+#[inline]
+fn decimal_text(elem: rust_decimal::Decimal) -> alloc::string::String {
+  alloc::string::ToString::to_string(&elem)
+}
+
+#[cfg(feature = "sqlx-mysql")]
+#[inline]
+fn mysql_connect_options(config: &Config) -> crate::Result<sqlx_mysql::MySqlConnectOptions> {
+  use sqlx_mysql::MySqlSslMode;
+  config.reject_params(&["application_name", "search_path", "trustServerCertificate"])?;
+  let mut options = sqlx_mysql::MySqlConnectOptions::from_str(&config.backend_url())?;
+  if let Some(elem) = config.sslmode()? {
+    options = options.ssl_mode(match elem {
+      SslMode::Disable => MySqlSslMode::Disabled,
+      SslMode::Allow | SslMode::Prefer => MySqlSslMode::Preferred,
+      SslMode::Require => MySqlSslMode::Required,
+      SslMode::VerifyCa => MySqlSslMode::VerifyCa,
+      SslMode::VerifyFull => MySqlSslMode::VerifyIdentity,
+    });
+  }
+  if let Some(elem) = config.sslrootcert() {
+    options = options.ssl_ca(elem);
+  }
+  if let Some(elem) = config.sslcert() {
+    options = options.ssl_client_cert(elem);
+  }
+  if let Some(elem) = config.sslkey() {
+    options = options.ssl_client_key(elem);
+  }
+  Ok(options)
+}
+
+// Declared with the unspecified OID so that PostgreSQL infers the type of `NULL` from its
+// context, which is also what the native backend does.
+#[cfg(feature = "sqlx-postgres")]
+#[derive(Clone, Copy, Debug)]
+struct PgNull;
+
+#[cfg(feature = "sqlx-postgres")]
+impl<'args> sqlx_core::encode::Encode<'args, sqlx_postgres::Postgres> for PgNull {
+  #[inline]
+  fn encode_by_ref(&self, _: &mut sqlx_postgres::PgArgumentBuffer) -> sqlx_core::encode::IsNull {
+    sqlx_core::encode::IsNull::Yes
+  }
+}
+
+#[cfg(feature = "sqlx-postgres")]
+impl Type<sqlx_postgres::Postgres> for PgNull {
+  #[inline]
+  fn type_info() -> sqlx_postgres::PgTypeInfo {
+    sqlx_postgres::PgTypeInfo::with_oid(sqlx_postgres::types::Oid(0))
+  }
+}
+
+#[cfg(feature = "sqlx-postgres")]
+#[inline]
+fn pg_connect_options(config: &Config) -> crate::Result<sqlx_postgres::PgConnectOptions> {
+  use sqlx_postgres::PgSslMode;
+  config.reject_params(&["trustServerCertificate"])?;
+  let mut options = sqlx_postgres::PgConnectOptions::from_str(&config.backend_url())?;
+  if let Some(elem) = config.sslmode()? {
+    options = options.ssl_mode(match elem {
+      SslMode::Disable => PgSslMode::Disable,
+      SslMode::Allow => PgSslMode::Allow,
+      SslMode::Prefer => PgSslMode::Prefer,
+      SslMode::Require => PgSslMode::Require,
+      SslMode::VerifyCa => PgSslMode::VerifyCa,
+      SslMode::VerifyFull => PgSslMode::VerifyFull,
+    });
+  }
+  if let Some(elem) = config.sslrootcert() {
+    options = options.ssl_root_cert(elem);
+  }
+  if let Some(elem) = config.sslcert() {
+    options = options.ssl_client_cert(elem);
+  }
+  if let Some(elem) = config.sslkey() {
+    options = options.ssl_client_key(elem);
+  }
+  if let Some(elem) = config.application_name() {
+    options = options.application_name(elem);
+  }
+  if let Some(elem) = config.search_path() {
+    options = options.options([("search_path", elem)]);
+  }
+  Ok(options)
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+#[inline]
+fn sqlite_connect_options(config: &Config) -> crate::Result<sqlx_sqlite::SqliteConnectOptions> {
+  config.reject_params(&[
+    "application_name",
+    "search_path",
+    "sslcert",
+    "sslkey",
+    "sslmode",
+    "sslrootcert",
+    "trustServerCertificate",
+  ])?;
+  Ok(sqlx_sqlite::SqliteConnectOptions::from_str(&config.backend_url())?)
+}
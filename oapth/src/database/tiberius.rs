@@ -1,16 +1,19 @@
-use crate::{database::Database, DatabaseTy};
+use crate::{
+  database::{Database, SqlParam, TransactionState},
+  DatabaseTy, FromSqlColumn, SslMode,
+};
+use arrayvec::ArrayString;
 use futures::{AsyncRead, AsyncWrite, TryStreamExt};
-use tiberius::{Client, Row};
+use tiberius::{Client, ColumnType, EncryptionLevel, Query, Row};
 
 /// Wraps functionalities for the `tiberius` crate
-///
-/// This Backend currently doesn't support transactions
 #[derive(Debug)]
 pub struct Tiberius<T>
 where
   T: AsyncRead + AsyncWrite + Send + Unpin,
 {
   conn: Client<T>,
+  transaction_state: TransactionState,
 }
 
 impl<T> Tiberius<T>
@@ -33,6 +36,7 @@ where
   /// ```
   #[inline]
   pub async fn new(oapth_config: &crate::Config, tcp: T) -> crate::Result<Self> {
+    oapth_config.reject_params(&["search_path", "sslcert", "sslkey"])?;
     let mut config = tiberius::Config::new();
     config.authentication(tiberius::AuthMethod::sql_server(
       oapth_config.user()?,
@@ -40,18 +44,67 @@ where
     ));
     config.host(oapth_config.host()?);
     config.port(oapth_config.port()?);
-    Self::manage_trust_server_certificate(&mut config, oapth_config.url());
+    if let Ok(name) = oapth_config.name() {
+      config.database(name);
+    }
+    if let Some(application_name) = oapth_config.application_name() {
+      config.application_name(application_name);
+    }
+    Self::manage_encryption(&mut config, oapth_config)?;
     let conn = Client::connect(config, tcp).await?;
-    Ok(Self { conn })
+    Ok(Self { conn, transaction_state: TransactionState::default() })
   }
 
+  // Rollbacks scheduled by dropped transactions are executed before the next command.
   #[inline]
-  fn manage_trust_server_certificate(c: &mut tiberius::Config, url: &str) {
-    let opt = || url.split("trustServerCertificate=").nth(1)?.parse::<bool>().ok();
-    if let Some(e) = opt() {
-      if e {
-        c.trust_cert();
-      }
+  async fn execute_pending_rollback(&mut self) -> crate::Result<()> {
+    if let Some(cmd) = self.transaction_state.pending_rollback(DatabaseTy::Mssql) {
+      let _ = self.conn.execute(cmd, &[]).await?;
+    }
+    Ok(())
+  }
+
+  // `trust_cert` and `trust_cert_ca` are mutually exclusive and `tiberius` panics if both are set.
+  // `Allow` and `Prefer` don't demand encryption, so only the login packet is encrypted.
+  #[inline]
+  fn manage_encryption(
+    c: &mut tiberius::Config,
+    oapth_config: &crate::Config,
+  ) -> crate::Result<()> {
+    let sslmode = oapth_config.sslmode()?;
+    if let Some(elem) = sslmode {
+      c.encryption(match elem {
+        SslMode::Disable => EncryptionLevel::NotSupported,
+        SslMode::Allow | SslMode::Prefer => EncryptionLevel::Off,
+        SslMode::Require | SslMode::VerifyCa | SslMode::VerifyFull => EncryptionLevel::Required,
+      });
+    }
+    if let Some(sslrootcert) = oapth_config.sslrootcert() {
+      c.trust_cert_ca(sslrootcert);
+    } else if oapth_config.trust_server_certificate()? || sslmode == Some(SslMode::Require) {
+      c.trust_cert();
+    }
+    Ok(())
+  }
+}
+
+#[cfg(feature = "pool")]
+impl crate::database::PoolManager
+  for crate::database::ConfigManager<Tiberius<tokio_util::compat::Compat<tokio::net::TcpStream>>>
+{
+  type Database = Tiberius<tokio_util::compat::Compat<tokio::net::TcpStream>>;
+
+  #[inline]
+  async fn connect(&self) -> crate::Result<Self::Database> {
+    use tokio_util::compat::TokioAsyncWriteCompatExt;
+    let fut = async {
+      let tcp = tokio::net::TcpStream::connect(self.config().full_host()?).await?;
+      Tiberius::new(self.config(), tcp.compat_write()).await
+    };
+    if let Some(elem) = self.config().connect_timeout()? {
+      tokio::time::timeout(elem, fut).await.map_err(|_err| crate::Error::ConnectTimeout)?
+    } else {
+      fut.await
     }
   }
 }
@@ -64,17 +117,55 @@ where
 
   type Row = Row;
 
+  #[inline]
+  async fn begin_transaction(&mut self) -> crate::Result<()> {
+    self.execute(&self.transaction_state.begin(Self::TY)).await?;
+    self.transaction_state.began();
+    Ok(())
+  }
+
+  #[inline]
+  async fn commit_transaction(&mut self) -> crate::Result<()> {
+    if let Some(cmd) = self.transaction_state.commit(Self::TY) {
+      self.execute(&cmd).await?;
+    }
+    Ok(())
+  }
+
   #[inline]
   async fn execute(&mut self, cmd: &str) -> crate::Result<()> {
+    self.execute_pending_rollback().await?;
     Ok(self.conn.execute(cmd, &[][..]).await.map(|_| ())?)
   }
 
+  #[inline]
+  async fn execute_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<u64> {
+    self.execute_pending_rollback().await?;
+    Ok(bind_params(cmd, params).execute(&mut self.conn).await?.total())
+  }
+
+  #[inline]
+  async fn rollback_transaction(&mut self) -> crate::Result<()> {
+    if let Some(cmd) = self.transaction_state.rollback(Self::TY) {
+      self.execute(&cmd).await?;
+    }
+    Ok(())
+  }
+
   #[inline]
   async fn row(&mut self, cmd: &str) -> crate::Result<Self::Row> {
+    self.execute_pending_rollback().await?;
     let query_result = self.conn.query(cmd, &[]).await?;
     query_result.into_row().await?.ok_or(crate::Error::InvalidSqlQuery)
   }
 
+  #[inline]
+  async fn row_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<Self::Row> {
+    self.execute_pending_rollback().await?;
+    let query_result = bind_params(cmd, params).query(&mut self.conn).await?;
+    query_result.into_row().await?.ok_or(crate::Error::InvalidSqlQuery)
+  }
+
   #[inline]
   async fn rows<E>(
     &mut self,
@@ -84,6 +175,7 @@ where
   where
     E: From<crate::Error>,
   {
+    self.execute_pending_rollback().await?;
     let query_result = self.conn.query(cmd, &[]).await.map_err(From::from)?;
     let mut stream = query_result.into_row_stream();
     while let Some(row) = stream.try_next().await.map_err(From::from)? {
@@ -93,10 +185,46 @@ where
   }
 
   #[inline]
-  async fn transaction(&mut self, cmd: &str) -> crate::Result<()> {
-    self.execute(cmd).await?;
+  async fn rows_with<E>(
+    &mut self,
+    cmd: &str,
+    params: &[SqlParam<'_>],
+    mut cb: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+  {
+    self.execute_pending_rollback().await?;
+    let query_result = bind_params(cmd, params).query(&mut self.conn).await.map_err(From::from)?;
+    let mut stream = query_result.into_row_stream();
+    while let Some(row) = stream.try_next().await.map_err(From::from)? {
+      cb(row)?;
+    }
     Ok(())
   }
+
+  #[inline]
+  fn start_rollback(&mut self) {
+    self.transaction_state.start_rollback();
+  }
+
+  // With `XACT_ABORT`, the first failing statement of `cmd` aborts the whole transaction instead
+  // of letting the subsequent statements run. The setting is restored to the default afterwards.
+  #[inline]
+  async fn transaction(&mut self, cmd: &str) -> crate::Result<()> {
+    self.execute("SET XACT_ABORT ON").await?;
+    let rslt = async {
+      self.begin_transaction().await?;
+      if let Err(err) = self.execute(cmd).await {
+        let _rslt = self.rollback_transaction().await;
+        return Err(err);
+      }
+      self.commit_transaction().await
+    }
+    .await;
+    let xact_abort_rslt = self.execute("SET XACT_ABORT OFF").await;
+    rslt.and(xact_abort_rslt)
+  }
 }
 
 #[cfg(feature = "sm")]
@@ -104,6 +232,28 @@ impl<T> crate::sm::SchemaManagement for Tiberius<T>
 where
   T: AsyncRead + AsyncWrite + Send + Unpin,
 {
+  #[inline]
+  async fn baseline_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    crate::sm::fixed_sql_commands::_baseline_migrations(
+      buffer_cmd,
+      self,
+      mg,
+      migrations,
+      crate::sm::_OAPTH_SCHEMA_PREFIX,
+    )
+    .await
+  }
+
   #[inline]
   async fn clear(
     &mut self,
@@ -114,7 +264,7 @@ where
 
   #[inline]
   async fn create_oapth_tables(&mut self) -> crate::Result<()> {
-    self.execute(crate::sm::fixed_sql_commands::mssql::_CREATE_MIGRATION_TABLES).await
+    crate::sm::fixed_sql_commands::mssql::_create_oapth_tables(self).await
   }
 
   #[inline]
@@ -137,12 +287,36 @@ where
     .await
   }
 
+  #[inline]
+  async fn failed_migrations_len<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    crate::sm::fixed_sql_commands::_failed_migrations_len(
+      buffer_cmd,
+      self,
+      mg,
+      crate::sm::_OAPTH_SCHEMA_PREFIX,
+    )
+    .await
+  }
+
+  #[inline]
+  async fn has_oapth_tables(&mut self) -> crate::Result<bool> {
+    crate::sm::fixed_sql_commands::mssql::_has_oapth_tables(self).await
+  }
+
   #[inline]
   async fn insert_migrations<'migration, DBS, I, S>(
     &mut self,
     buffer_cmd: &mut String,
     mg: &crate::sm::MigrationGroup<S>,
     migrations: I,
+    transaction_mode: crate::sm::TransactionMode,
   ) -> crate::Result<()>
   where
     DBS: AsRef<[DatabaseTy]> + 'migration,
@@ -155,10 +329,20 @@ where
       mg,
       migrations,
       crate::sm::_OAPTH_SCHEMA_PREFIX,
+      transaction_mode,
     )
     .await
   }
 
+  #[inline]
+  async fn lock(
+    &mut self,
+    buffer_cmd: &mut String,
+    timeout: core::time::Duration,
+  ) -> crate::Result<()> {
+    crate::sm::fixed_sql_commands::mssql::_lock(buffer_cmd, self, timeout).await
+  }
+
   #[inline]
   async fn migrations<S>(
     &mut self,
@@ -179,6 +363,26 @@ where
     .await
   }
 
+  #[inline]
+  async fn repair_migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    entries: &[crate::sm::MigrationRepairEntry],
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    crate::sm::fixed_sql_commands::_repair_migrations(
+      buffer_cmd,
+      self,
+      mg,
+      entries,
+      crate::sm::_OAPTH_SCHEMA_PREFIX,
+    )
+    .await
+  }
+
   #[inline]
   async fn table_names(
     &mut self,
@@ -188,4 +392,155 @@ where
   ) -> crate::Result<()> {
     crate::sm::fixed_sql_commands::mssql::_table_names(buffer_cmd, self, results, schema).await
   }
+
+  #[inline]
+  async fn unlock(&mut self, buffer_cmd: &mut String) -> crate::Result<()> {
+    crate::sm::fixed_sql_commands::mssql::_unlock(buffer_cmd, self).await
+  }
+
+  #[inline]
+  async fn update_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+    transaction_mode: crate::sm::TransactionMode,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    crate::sm::fixed_sql_commands::_update_migrations(
+      buffer_cmd,
+      self,
+      mg,
+      migrations,
+      crate::sm::_OAPTH_SCHEMA_PREFIX,
+      transaction_mode,
+    )
+    .await
+  }
+}
+
+// Placeholders are named `@P1`, `@P2`, ... following the order of `params`.
+#[inline]
+fn bind_params<'args>(cmd: &'args str, params: &'args [SqlParam<'args>]) -> Query<'args> {
+  let mut query = Query::new(cmd);
+  for param in params {
+    match *param {
+      SqlParam::Bool(elem) => query.bind(elem),
+      SqlParam::Bytes(elem) => query.bind(elem),
+      #[cfg(feature = "chrono")]
+      SqlParam::DateTime(elem) => {
+        query.bind(alloc::string::ToString::to_string(&elem.format(crate::database::DATE_TIME_FMT)))
+      }
+      #[cfg(feature = "rust_decimal")]
+      SqlParam::Decimal(elem) => query.bind(tiberius::numeric::Numeric::new_with_scale(
+        elem.mantissa(),
+        // `Decimal` scales never exceed 28
+        u8::try_from(elem.scale()).unwrap_or_default(),
+      )),
+      SqlParam::F32(elem) => query.bind(elem),
+      SqlParam::F64(elem) => query.bind(elem),
+      SqlParam::I32(elem) => query.bind(elem),
+      SqlParam::I64(elem) => query.bind(elem),
+      #[cfg(feature = "serde_json")]
+      SqlParam::Json(elem) => query.bind(alloc::string::ToString::to_string(elem)),
+      #[cfg(feature = "chrono")]
+      SqlParam::NaiveDate(elem) => query.bind(alloc::string::ToString::to_string(&elem)),
+      #[cfg(feature = "chrono")]
+      SqlParam::NaiveDateTime(elem) => query.bind(elem),
+      SqlParam::Null => query.bind(None::<i64>),
+      SqlParam::Str(elem) => query.bind(elem),
+      #[cfg(feature = "uuid")]
+      SqlParam::Uuid(elem) => query.bind(elem),
+    }
+  }
+  query
+}
+
+// Owned strings and bytes are decoded from their borrowed counterparts.
+macro_rules! impl_from_sql_column {
+  ([$($generics:tt)*] $ty:ty, $via:ty, |$elem:ident| $conv:expr) => {
+    impl<$($generics)*> FromSqlColumn<Row> for $ty {
+      #[inline]
+      fn from_column_idx(row: &Row, idx: usize) -> crate::Result<Self> {
+        <Option<$ty>>::from_column_idx(row, idx)?.ok_or(crate::Error::InvalidSqlQuery)
+      }
+
+      #[inline]
+      fn from_column_name(row: &Row, name: &str) -> crate::Result<Self> {
+        <Option<$ty>>::from_column_name(row, name)?.ok_or(crate::Error::InvalidSqlQuery)
+      }
+    }
+
+    impl<$($generics)*> FromSqlColumn<Row> for Option<$ty> {
+      #[inline]
+      fn from_column_idx(row: &Row, idx: usize) -> crate::Result<Self> {
+        row.try_get::<$via, _>(idx)?.map(|$elem| $conv).transpose()
+      }
+
+      #[inline]
+      fn from_column_name(row: &Row, name: &str) -> crate::Result<Self> {
+        row.try_get::<$via, _>(name)?.map(|$elem| $conv).transpose()
+      }
+    }
+  };
+  ($($ty:ty),* $(,)?) => {
+    $(impl_from_sql_column!([] $ty, $ty, |elem| Ok(elem));)*
+  };
+}
+
+impl_from_sql_column!([const N: usize] ArrayString<N>, &str, |elem| Ok(elem.try_into()?));
+impl_from_sql_column!([] String, &str, |elem| Ok(elem.into()));
+impl_from_sql_column!([] Vec<u8>, &[u8], |elem| Ok(elem.into()));
+impl_from_sql_column!(bool, f32, f64, i16, i32, i64);
+#[cfg(feature = "chrono")]
+impl_from_sql_column!(chrono::DateTime<chrono::Utc>, chrono::NaiveDate, chrono::NaiveDateTime);
+#[cfg(feature = "rust_decimal")]
+impl_from_sql_column!(rust_decimal::Decimal);
+#[cfg(feature = "serde_json")]
+impl_from_sql_column!([] serde_json::Value, &str, |elem| {
+  elem.parse().map_err(|_err| crate::Error::InvalidSqlQuery)
+});
+#[cfg(feature = "uuid")]
+impl_from_sql_column!(uuid::Uuid);
+
+// Names of the SQL Server types, used as the declared types of the columns.
+#[inline]
+pub(crate) fn tiberius_column_ty(ty: ColumnType) -> &'static str {
+  match ty {
+    ColumnType::BigBinary => "binary",
+    ColumnType::BigChar => "char",
+    ColumnType::BigVarBin => "varbinary",
+    ColumnType::BigVarChar => "varchar",
+    ColumnType::Bit | ColumnType::Bitn => "bit",
+    ColumnType::Daten => "date",
+    ColumnType::Datetime | ColumnType::Datetimen => "datetime",
+    ColumnType::Datetime2 => "datetime2",
+    ColumnType::Datetime4 => "smalldatetime",
+    ColumnType::DatetimeOffsetn => "datetimeoffset",
+    ColumnType::Decimaln => "decimal",
+    ColumnType::Float4 => "real",
+    ColumnType::Float8 | ColumnType::Floatn => "float",
+    ColumnType::Guid => "uniqueidentifier",
+    ColumnType::Image => "image",
+    ColumnType::Int1 => "tinyint",
+    ColumnType::Int2 => "smallint",
+    ColumnType::Int4 | ColumnType::Intn => "int",
+    ColumnType::Int8 => "bigint",
+    ColumnType::Money => "money",
+    ColumnType::Money4 => "smallmoney",
+    ColumnType::NChar => "nchar",
+    ColumnType::NText => "ntext",
+    ColumnType::NVarchar => "nvarchar",
+    ColumnType::Null => "null",
+    ColumnType::Numericn => "numeric",
+    ColumnType::SSVariant => "sql_variant",
+    ColumnType::Text => "text",
+    ColumnType::Timen => "time",
+    ColumnType::Udt => "udt",
+    ColumnType::Xml => "xml",
+  }
 }
@@ -0,0 +1,415 @@
+use crate::{
+  database::{Database, SqlParam},
+  DatabaseTy,
+};
+#[cfg(any(feature = "postgres", feature = "sm", feature = "tiberius"))]
+use alloc::string::String;
+#[cfg(feature = "sm")]
+use alloc::vec::Vec;
+
+/// Transaction, or savepoint, started by [Database::begin].
+///
+/// Implements [Database] itself, therefore everything that works with a database, like `Crud`,
+/// also works inside a transaction. Calling [Database::begin] on an instance creates a nested
+/// savepoint.
+///
+/// Transactions that are dropped without being committed or rolled back are rolled back before
+/// the next command of the underlying database.
+#[derive(Debug)]
+pub struct Transaction<'db, D>
+where
+  D: Database,
+{
+  database: &'db mut D,
+  is_open: bool,
+}
+
+impl<'db, D> Transaction<'db, D>
+where
+  D: Database,
+{
+  #[inline]
+  pub(crate) fn new(database: &'db mut D) -> Self {
+    Self { database, is_open: true }
+  }
+
+  /// Commits the transaction or releases the savepoint.
+  #[inline]
+  pub async fn commit(mut self) -> crate::Result<()> {
+    self.is_open = false;
+    self.database.commit_transaction().await
+  }
+
+  /// Aborts the transaction or restores the state of the savepoint.
+  #[inline]
+  pub async fn rollback(mut self) -> crate::Result<()> {
+    self.is_open = false;
+    self.database.rollback_transaction().await
+  }
+}
+
+impl<D> Database for Transaction<'_, D>
+where
+  D: Database,
+{
+  const TY: DatabaseTy = D::TY;
+
+  type Row = D::Row;
+
+  #[inline]
+  async fn begin_transaction(&mut self) -> crate::Result<()> {
+    self.database.begin_transaction().await
+  }
+
+  #[inline]
+  async fn commit_transaction(&mut self) -> crate::Result<()> {
+    self.database.commit_transaction().await
+  }
+
+  #[inline]
+  async fn execute(&mut self, cmd: &str) -> crate::Result<()> {
+    self.database.execute(cmd).await
+  }
+
+  #[inline]
+  async fn execute_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<u64> {
+    self.database.execute_with(cmd, params).await
+  }
+
+  #[inline]
+  async fn rollback_transaction(&mut self) -> crate::Result<()> {
+    self.database.rollback_transaction().await
+  }
+
+  #[inline]
+  async fn row(&mut self, cmd: &str) -> crate::Result<Self::Row> {
+    self.database.row(cmd).await
+  }
+
+  #[inline]
+  async fn row_with(&mut self, cmd: &str, params: &[SqlParam<'_>]) -> crate::Result<Self::Row> {
+    self.database.row_with(cmd, params).await
+  }
+
+  #[inline]
+  async fn rows<E>(
+    &mut self,
+    cmd: &str,
+    cb: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+  {
+    self.database.rows(cmd, cb).await
+  }
+
+  #[inline]
+  async fn rows_with<E>(
+    &mut self,
+    cmd: &str,
+    params: &[SqlParam<'_>],
+    cb: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+  {
+    self.database.rows_with(cmd, params, cb).await
+  }
+
+  #[inline]
+  fn start_rollback(&mut self) {
+    self.database.start_rollback();
+  }
+
+  #[inline]
+  async fn transaction(&mut self, cmd: &str) -> crate::Result<()> {
+    let mut transaction = self.begin().await?;
+    transaction.execute(cmd).await?;
+    transaction.commit().await
+  }
+}
+
+impl<D> Drop for Transaction<'_, D>
+where
+  D: Database,
+{
+  #[inline]
+  fn drop(&mut self) {
+    if self.is_open {
+      self.database.start_rollback();
+    }
+  }
+}
+
+// Migrations can also be executed within a transaction.
+#[cfg(feature = "sm")]
+impl<D> crate::sm::SchemaManagement for Transaction<'_, D>
+where
+  D: crate::sm::SchemaManagement,
+{
+  #[inline]
+  async fn baseline_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    self.database.baseline_migrations(buffer_cmd, mg, migrations).await
+  }
+
+  #[inline]
+  async fn clear(
+    &mut self,
+    buffer: (&mut String, &mut Vec<crate::Identifier>),
+  ) -> crate::Result<()> {
+    self.database.clear(buffer).await
+  }
+
+  #[inline]
+  async fn create_oapth_tables(&mut self) -> crate::Result<()> {
+    self.database.create_oapth_tables().await
+  }
+
+  #[inline]
+  async fn delete_migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    version: i32,
+  ) -> crate::Result<()>
+  where
+    S: AsRef<str>,
+  {
+    self.database.delete_migrations(buffer_cmd, mg, version).await
+  }
+
+  #[inline]
+  async fn failed_migrations_len<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    self.database.failed_migrations_len(buffer_cmd, mg).await
+  }
+
+  #[inline]
+  async fn has_oapth_tables(&mut self) -> crate::Result<bool> {
+    self.database.has_oapth_tables().await
+  }
+
+  #[inline]
+  async fn insert_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+    transaction_mode: crate::sm::TransactionMode,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    self.database.insert_migrations(buffer_cmd, mg, migrations, transaction_mode).await
+  }
+
+  #[inline]
+  async fn lock(
+    &mut self,
+    buffer_cmd: &mut String,
+    timeout: core::time::Duration,
+  ) -> crate::Result<()> {
+    self.database.lock(buffer_cmd, timeout).await
+  }
+
+  #[inline]
+  async fn migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    results: &mut Vec<crate::sm::DbMigration>,
+  ) -> crate::Result<()>
+  where
+    S: AsRef<str>,
+  {
+    self.database.migrations(buffer_cmd, mg, results).await
+  }
+
+  #[inline]
+  async fn repair_migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    entries: &[crate::sm::MigrationRepairEntry],
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    self.database.repair_migrations(buffer_cmd, mg, entries).await
+  }
+
+  #[inline]
+  async fn table_names(
+    &mut self,
+    buffer_cmd: &mut String,
+    results: &mut Vec<crate::Identifier>,
+    schema: &str,
+  ) -> crate::Result<()> {
+    self.database.table_names(buffer_cmd, results, schema).await
+  }
+
+  #[inline]
+  async fn unlock(&mut self, buffer_cmd: &mut String) -> crate::Result<()> {
+    self.database.unlock(buffer_cmd).await
+  }
+
+  #[inline]
+  async fn update_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &crate::sm::MigrationGroup<S>,
+    migrations: I,
+    transaction_mode: crate::sm::TransactionMode,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    self.database.update_migrations(buffer_cmd, mg, migrations, transaction_mode).await
+  }
+}
+
+/// Nesting level and pending rollbacks of backends that manage transactions through plain SQL
+/// commands.
+#[cfg(any(feature = "postgres", feature = "tiberius"))]
+#[derive(Debug, Default)]
+pub(crate) struct TransactionState {
+  depth: usize,
+  pending_rollback: Option<usize>,
+}
+
+#[cfg(any(feature = "postgres", feature = "tiberius"))]
+impl TransactionState {
+  /// Command that starts a transaction or a savepoint. The nesting level is only increased by
+  /// [Self::began] after the command succeeds.
+  #[inline]
+  pub(crate) fn begin(&self, ty: DatabaseTy) -> String {
+    match (self.depth, ty) {
+      (0, DatabaseTy::Mssql) => "BEGIN TRANSACTION".into(),
+      (0, _) => "BEGIN".into(),
+      (depth, DatabaseTy::Mssql) => alloc::format!("SAVE TRANSACTION _oapth_savepoint_{depth}"),
+      (depth, _) => alloc::format!("SAVEPOINT _oapth_savepoint_{depth}"),
+    }
+  }
+
+  /// Increases the nesting level after a successful [Self::begin].
+  #[inline]
+  pub(crate) fn began(&mut self) {
+    self.depth = self.depth.wrapping_add(1);
+  }
+
+  /// Command that commits the current transaction or releases the current savepoint. `None` if
+  /// there is nothing to commit.
+  ///
+  /// The nesting level is always decreased because failed commits also finish transactions.
+  #[inline]
+  pub(crate) fn commit(&mut self, ty: DatabaseTy) -> Option<String> {
+    self.depth = self.depth.checked_sub(1)?;
+    match (self.depth, ty) {
+      (0, _) => Some("COMMIT".into()),
+      // SQL Server doesn't release savepoints, their changes are simply kept.
+      (_, DatabaseTy::Mssql) => None,
+      (depth, _) => Some(alloc::format!("RELEASE SAVEPOINT _oapth_savepoint_{depth}")),
+    }
+  }
+
+  /// Command of the rollback scheduled by [Self::start_rollback], if any.
+  #[inline]
+  pub(crate) fn pending_rollback(&mut self, ty: DatabaseTy) -> Option<String> {
+    Some(Self::rollback_cmd(self.pending_rollback.take()?, ty))
+  }
+
+  /// Command that aborts the current transaction or restores the current savepoint. `None` if
+  /// there is nothing to rollback.
+  #[inline]
+  pub(crate) fn rollback(&mut self, ty: DatabaseTy) -> Option<String> {
+    self.depth = self.depth.checked_sub(1)?;
+    Some(Self::rollback_cmd(self.depth, ty))
+  }
+
+  /// Schedules a rollback that should be executed before the next command.
+  #[inline]
+  pub(crate) fn start_rollback(&mut self) {
+    let Some(depth) = self.depth.checked_sub(1) else {
+      return;
+    };
+    self.depth = depth;
+    self.pending_rollback = Some(self.pending_rollback.map_or(depth, |el| el.min(depth)));
+  }
+
+  // SQL Server may have already aborted the whole transaction, e.g. with `XACT_ABORT`, and
+  // only allows restoring savepoints of committable transactions.
+  #[inline]
+  fn rollback_cmd(depth: usize, ty: DatabaseTy) -> String {
+    match (depth, ty) {
+      (0, DatabaseTy::Mssql) => "IF XACT_STATE() <> 0 ROLLBACK".into(),
+      (0, _) => "ROLLBACK".into(),
+      (_, DatabaseTy::Mssql) => {
+        alloc::format!("IF XACT_STATE() = 1 ROLLBACK TRANSACTION _oapth_savepoint_{depth}")
+      }
+      (_, _) => alloc::format!("ROLLBACK TO SAVEPOINT _oapth_savepoint_{depth}"),
+    }
+  }
+}
+
+#[cfg(all(feature = "postgres", test))]
+mod tests {
+  use crate::{database::TransactionState, DatabaseTy};
+
+  #[test]
+  fn dropped_savepoints_are_rolled_back_to_the_outermost_level() {
+    let mut ts = TransactionState::default();
+    assert_eq!(ts.begin(DatabaseTy::Postgres), "BEGIN");
+    ts.began();
+    assert_eq!(ts.begin(DatabaseTy::Postgres), "SAVEPOINT _oapth_savepoint_1");
+    ts.began();
+    assert_eq!(ts.begin(DatabaseTy::Postgres), "SAVEPOINT _oapth_savepoint_2");
+    ts.began();
+    ts.start_rollback();
+    assert_eq!(
+      ts.pending_rollback(DatabaseTy::Postgres).as_deref(),
+      Some("ROLLBACK TO SAVEPOINT _oapth_savepoint_2")
+    );
+    ts.start_rollback();
+    ts.start_rollback();
+    assert_eq!(ts.pending_rollback(DatabaseTy::Postgres).as_deref(), Some("ROLLBACK"));
+    assert_eq!(ts.pending_rollback(DatabaseTy::Postgres), None);
+    assert_eq!(ts.commit(DatabaseTy::Postgres), None);
+  }
+
+  #[test]
+  fn mssql_savepoints_are_not_released() {
+    let mut ts = TransactionState::default();
+    assert_eq!(ts.begin(DatabaseTy::Mssql), "BEGIN TRANSACTION");
+    ts.began();
+    assert_eq!(ts.begin(DatabaseTy::Mssql), "SAVE TRANSACTION _oapth_savepoint_1");
+    ts.began();
+    assert_eq!(ts.commit(DatabaseTy::Mssql), None);
+    assert_eq!(ts.begin(DatabaseTy::Mssql), "SAVE TRANSACTION _oapth_savepoint_1");
+    ts.began();
+    assert_eq!(
+      ts.rollback(DatabaseTy::Mssql).as_deref(),
+      Some("IF XACT_STATE() = 1 ROLLBACK TRANSACTION _oapth_savepoint_1")
+    );
+    assert_eq!(ts.commit(DatabaseTy::Mssql).as_deref(), Some("COMMIT"));
+  }
+}
@@ -1,4 +1,7 @@
-use crate::{database::Database, DatabaseTy};
+use crate::{
+  database::{Database, SqlParam},
+  DatabaseTy,
+};
 #[cfg(feature = "sm")]
 use alloc::{string::String, vec::Vec};
 
@@ -7,16 +10,41 @@ impl Database for () {
 
   type Row = ();
 
+  #[inline]
+  async fn begin_transaction(&mut self) -> crate::Result<()> {
+    Ok(())
+  }
+
+  #[inline]
+  async fn commit_transaction(&mut self) -> crate::Result<()> {
+    Ok(())
+  }
+
   #[inline]
   async fn execute(&mut self, _: &str) -> crate::Result<()> {
     Ok(())
   }
 
+  #[inline]
+  async fn execute_with(&mut self, _: &str, _: &[SqlParam<'_>]) -> crate::Result<u64> {
+    Ok(0)
+  }
+
+  #[inline]
+  async fn rollback_transaction(&mut self) -> crate::Result<()> {
+    Ok(())
+  }
+
   #[inline]
   async fn row(&mut self, _: &str) -> crate::Result<Self::Row> {
     Ok(())
   }
 
+  #[inline]
+  async fn row_with(&mut self, _: &str, _: &[SqlParam<'_>]) -> crate::Result<Self::Row> {
+    Ok(())
+  }
+
   #[inline]
   async fn rows<E>(&mut self, _: &str, _: impl FnMut(Self::Row) -> Result<(), E>) -> Result<(), E>
   where
@@ -25,6 +53,22 @@ impl Database for () {
     Ok(())
   }
 
+  #[inline]
+  async fn rows_with<E>(
+    &mut self,
+    _: &str,
+    _: &[SqlParam<'_>],
+    _: impl FnMut(Self::Row) -> Result<(), E>,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+  {
+    Ok(())
+  }
+
+  #[inline]
+  fn start_rollback(&mut self) {}
+
   #[inline]
   async fn transaction(&mut self, _: &str) -> crate::Result<()> {
     Ok(())
@@ -33,6 +77,21 @@ impl Database for () {
 
 #[cfg(feature = "sm")]
 impl crate::sm::SchemaManagement for () {
+  #[inline]
+  async fn baseline_migrations<'migration, DBS, I, S>(
+    &mut self,
+    _: &mut String,
+    _: &crate::sm::MigrationGroup<S>,
+    _: I,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    Ok(())
+  }
+
   #[inline]
   async fn clear(&mut self, _: (&mut String, &mut Vec<crate::Identifier>)) -> crate::Result<()> {
     Ok(())
@@ -56,12 +115,30 @@ impl crate::sm::SchemaManagement for () {
     Ok(())
   }
 
+  #[inline]
+  async fn failed_migrations_len<S>(
+    &mut self,
+    _: &mut String,
+    _: &crate::sm::MigrationGroup<S>,
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    Ok(0)
+  }
+
+  #[inline]
+  async fn has_oapth_tables(&mut self) -> crate::Result<bool> {
+    Ok(false)
+  }
+
   #[inline]
   async fn insert_migrations<'migration, DBS, I, S>(
     &mut self,
     _: &mut String,
     _: &crate::sm::MigrationGroup<S>,
     _: I,
+    _: crate::sm::TransactionMode,
   ) -> crate::Result<()>
   where
     DBS: AsRef<[DatabaseTy]> + 'migration,
@@ -71,6 +148,11 @@ impl crate::sm::SchemaManagement for () {
     Ok(())
   }
 
+  #[inline]
+  async fn lock(&mut self, _: &mut String, _: core::time::Duration) -> crate::Result<()> {
+    Ok(())
+  }
+
   #[inline]
   async fn migrations<S>(
     &mut self,
@@ -84,6 +166,19 @@ impl crate::sm::SchemaManagement for () {
     Ok(())
   }
 
+  #[inline]
+  async fn repair_migrations<S>(
+    &mut self,
+    _: &mut String,
+    _: &crate::sm::MigrationGroup<S>,
+    _: &[crate::sm::MigrationRepairEntry],
+  ) -> crate::Result<u64>
+  where
+    S: AsRef<str>,
+  {
+    Ok(0)
+  }
+
   #[inline]
   async fn table_names(
     &mut self,
@@ -93,4 +188,25 @@ impl crate::sm::SchemaManagement for () {
   ) -> crate::Result<()> {
     Ok(())
   }
+
+  #[inline]
+  async fn unlock(&mut self, _: &mut String) -> crate::Result<()> {
+    Ok(())
+  }
+
+  #[inline]
+  async fn update_migrations<'migration, DBS, I, S>(
+    &mut self,
+    _: &mut String,
+    _: &crate::sm::MigrationGroup<S>,
+    _: I,
+    _: crate::sm::TransactionMode,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration crate::sm::UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    Ok(())
+  }
 }
@@ -1,5 +1,5 @@
 #[allow(unused_imports)]
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String};
 use core::fmt::{Debug, Display, Formatter};
 
 /// Generic error
@@ -17,15 +17,24 @@ pub enum Error {
   /// See [std::io::Error]
   #[cfg(feature = "std")]
   Io(std::io::Error),
+  /// See [crate::database::PostgresError].
+  #[cfg(feature = "postgres")]
+  Postgres(Box<crate::database::PostgresError>),
   /// See [sqlx_core::error::Error].
   #[cfg(feature = "sqlx-core")]
   Sqlx(Box<sqlx_core::error::Error>),
   /// See [tiberius::error::Error].
   #[cfg(feature = "tiberius")]
   Tiberius(Box<tiberius::error::Error>),
+  /// See [core::num::TryFromIntError]
+  TryFromInt(core::num::TryFromIntError),
 
   // Internal
   //
+  /// A baseline can only be recorded in a group without applied migrations
+  BaselineWithAppliedMigrations,
+  /// A connection couldn't be established within the `connect_timeout` of the configuration
+  ConnectTimeout,
   /// The `seeds` parameter must be provided through the CLI or the configuration file.
   ChecksumMustBeANumber,
   /// Databases must be sorted and unique
@@ -43,14 +52,35 @@ pub enum Error {
   InvalidDatabaseUrl(&'static str),
   /// Backend couldn't perform passed query string
   InvalidSqlQuery,
+  /// A TOML document has invalid syntax or an unexpected value. Contains a description, the line
+  /// and the column.
+  InvalidToml(&'static str, usize, usize),
+  /// The PostgreSQL server sent a message that couldn't be decoded
+  InvalidPostgresMessage,
   /// Invalid URL
   InvalidUrl,
+  /// The migration lock is held by another runner and couldn't be acquired within the configured
+  /// timeout.
+  LockNotAcquired,
   /// Environment variable is not present
   MissingEnvVar,
-  /// TOML parser only supports a subset of the official TOML specification
-  TomlParserOnlySupportsStringsAndArraysOfStrings,
-  /// TOML parser only supports a subset of the official TOML specification
-  TomlValueIsTooLarge,
+  /// Migration group couldn't be found in the configuration file
+  MissingMigrationGroup,
+  /// Entities can only be sorted by the columns of the root table, otherwise the rows of an entity
+  /// wouldn't be next to each other.
+  NonRootOrderByColumn,
+  /// Only PostgreSQL has literals for `NaN` and infinite floating point numbers
+  NonFiniteFloat,
+  /// No pooled connection became available within the acquire timeout
+  PoolAcquireTimeout,
+  /// A TOML document has a key that isn't expected. Contains the key, the line and the column.
+  UnknownTomlKey(String, usize, usize),
+  /// The PostgreSQL server requested an authentication method that isn't supported
+  UnsupportedPostgresAuthentication(u32),
+  /// The backend can't provide the requested level of protection
+  UnsupportedSslMode(crate::SslMode),
+  /// The backend can't honor a query parameter of the database URL
+  UnsupportedUrlParam(&'static str),
   /// Validation - Divergent migrations
   ValidationDivergentMigrations(i32),
   /// Validation - Migrations number
@@ -108,6 +138,14 @@ impl From<std::io::Error> for Error {
   }
 }
 
+#[cfg(feature = "postgres")]
+impl From<crate::database::PostgresError> for Error {
+  #[inline]
+  fn from(from: crate::database::PostgresError) -> Self {
+    Self::Postgres(from.into())
+  }
+}
+
 #[cfg(feature = "sqlx-core")]
 impl From<sqlx_core::error::Error> for Error {
   #[inline]
@@ -123,3 +161,10 @@ impl From<tiberius::error::Error> for Error {
     Self::Tiberius(from.into())
   }
 }
+
+impl From<core::num::TryFromIntError> for Error {
+  #[inline]
+  fn from(from: core::num::TryFromIntError) -> Self {
+    Self::TryFromInt(from)
+  }
+}
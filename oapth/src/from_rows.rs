@@ -3,17 +3,28 @@ use alloc::string::String;
 
 /// An element that can be represented from one or more database row, in other words, tables
 /// with relationships.
-pub trait FromRows: Sized {
+pub trait FromRows<R>: Sized
+where
+  R: Row,
+{
   /// Error
   type Error: From<crate::Error>;
-  /// Underlying database row.
-  type Row: Row;
+  /// Primary key decoded from rows, which identifies all the rows that belong to the same
+  /// entity.
+  type PrimaryKey: PartialEq;
 
   /// Constructs a single instance based on an arbitrary number of rows.
   fn from_rows(
     buffer_cmd: &mut String,
-    curr_row: &Self::Row,
-    rows: &[Self::Row],
+    curr_row: &R,
+    rows: &[R],
     table_suffix: TableSuffix,
   ) -> Result<(usize, Self), Self::Error>;
+
+  /// Decodes the primary key of the instance contained in `row`.
+  fn primary_key(
+    buffer_cmd: &mut String,
+    row: &R,
+    table_suffix: TableSuffix,
+  ) -> Result<Self::PrimaryKey, Self::Error>;
 }
@@ -0,0 +1,30 @@
+use crate::Row;
+
+/// A single value that can be decoded from a column of a database row.
+///
+/// Each backend decodes its own row type and `NULL` values are represented by `Option<T>`.
+pub trait FromSqlColumn<R>: Sized
+where
+  R: Row,
+{
+  /// Decodes the value of the column located at `idx`.
+  fn from_column_idx(row: &R, idx: usize) -> crate::Result<Self>;
+
+  /// Decodes the value of the column identified by `name`.
+  fn from_column_name(row: &R, name: &str) -> crate::Result<Self>;
+}
+
+impl<T> FromSqlColumn<()> for T
+where
+  T: Default,
+{
+  #[inline]
+  fn from_column_idx(_: &(), _: usize) -> crate::Result<Self> {
+    Ok(T::default())
+  }
+
+  #[inline]
+  fn from_column_name(_: &(), _: &str) -> crate::Result<Self> {
+    Ok(T::default())
+  }
+}
@@ -15,6 +15,7 @@ mod database_ty;
 mod error;
 mod from_row;
 mod from_rows;
+mod from_sql_column;
 #[cfg(feature = "orm")]
 pub mod orm;
 mod row;
@@ -23,11 +24,12 @@ pub mod sm;
 #[cfg(test)]
 mod tests;
 
-pub use config::Config;
+pub use config::{Config, ConfigBuilder, SslMode};
 pub use database_ty::*;
 pub use error::*;
 pub use from_row::FromRow;
 pub use from_rows::FromRows;
+pub use from_sql_column::FromSqlColumn;
 pub use row::Row;
 
 #[cfg(feature = "std")]
@@ -5,7 +5,11 @@ mod full_table_association;
 mod no_table_association;
 mod select_limit;
 mod select_order_by;
+mod sql_bindings;
+mod sql_column;
+mod sql_primary_key;
 mod sql_value;
+mod sql_where;
 mod sql_writer;
 mod table;
 mod table_association;
@@ -23,7 +27,11 @@ pub use full_table_association::*;
 pub use no_table_association::*;
 pub use select_limit::*;
 pub use select_order_by::*;
+pub use sql_bindings::*;
+pub use sql_column::*;
+pub use sql_primary_key::*;
 pub use sql_value::*;
+pub use sql_where::*;
 pub use sql_writer::*;
 pub use table::*;
 pub use table_association::*;
@@ -1,16 +1,16 @@
 use crate::{
   database::Database,
   orm::{
-    buffer_write_fmt, seek_related_entities, write_select_field, InitialInsertValue, SelectLimit,
-    SelectOrderBy, SqlWriter, Table, TableParams,
+    seek_related_entities, write_primary_key_values, write_select_field, InitialInsertValue,
+    SelectLimit, SelectOrderBy, SqlBindings, SqlColumn, SqlWhere, SqlWriter, Table, TableParams,
   },
-  FromRows,
+  FromRows, Row,
 };
 use alloc::{string::String, vec::Vec};
 use core::future::Future;
 
 /// Create, read, update and delete entities.
-pub trait Crud: Database {
+pub trait Crud: Database + Sized {
   /// Inserts a new table record represented by `table_params`.
   fn create<'entity, T>(
     &mut self,
@@ -24,16 +24,37 @@ pub trait Crud: Database {
   {
     async move {
       table_params.update_all_table_fields(table);
+      let mut bindings = SqlBindings::new(Self::TY);
       table_params.write_insert::<InitialInsertValue>(
         &mut <_>::default(),
-        &mut *buffer_cmd,
+        (&mut *buffer_cmd, &mut bindings),
         &mut None,
       )?;
-      self.execute(&*buffer_cmd).await.map_err(Into::into)?;
+      let _ = execute_statements(self, buffer_cmd, &bindings).await?;
       Ok(())
     }
   }
 
+  /// Removes the table record represented by `table_params` as well as its associations and
+  /// returns the number of deleted rows.
+  fn delete<'entity, T>(
+    &mut self,
+    buffer_cmd: &mut String,
+    table: &'entity T,
+    table_params: &mut TableParams<'entity, T>,
+  ) -> impl Future<Output = Result<u64, T::Error>>
+  where
+    T: Table<'entity>,
+    T::Associations: SqlWriter<Error = T::Error>,
+  {
+    async move {
+      table_params.update_all_table_fields(table);
+      let mut bindings = SqlBindings::new(Self::TY);
+      table_params.write_delete(&mut <_>::default(), (&mut *buffer_cmd, &mut bindings))?;
+      Ok(execute_statements(self, buffer_cmd, &bindings).await?)
+    }
+  }
+
   /// Fetches all entities from the database.
   fn read_all<'entity, T>(
     &mut self,
@@ -42,52 +63,110 @@ pub trait Crud: Database {
     tp: &TableParams<'entity, T>,
   ) -> impl Future<Output = Result<(), <T as Table<'entity>>::Error>>
   where
-    T: FromRows<Error = <T as Table<'entity>>::Error, Row = Self::Row> + Table<'entity>,
+    T: FromRows<Self::Row, Error = <T as Table<'entity>>::Error> + Table<'entity>,
     T::Associations: SqlWriter<Error = <T as Table<'entity>>::Error>,
   {
     async move {
-      tp.write_select(&mut *buffer_cmd, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| {
+      tp.write_select(
+        &mut *buffer_cmd,
+        (SelectOrderBy::Ascending, &[]),
+        SelectLimit::All,
+        Self::TY,
+        &mut |_| Ok(()),
+      )?;
+      self
+        .rows(buffer_cmd.as_str(), |row| {
+          buffer_rows.push(row);
+          Ok(())
+        })
+        .await?;
+      buffer_cmd.clear();
+      collect_entities_tables((buffer_cmd, buffer_rows), tp, |entity| {
+        results.push(entity);
         Ok(())
       })?;
+      Ok(())
+    }
+  }
+
+  /// Similar to `read_all` but each entity is given to `cb` as soon as all its rows are
+  /// received, which bounds `buffer_rows` to the rows of a single root entity.
+  fn read_all_with_cb<'entity, T>(
+    &mut self,
+    (buffer_cmd, buffer_rows): (&mut String, &mut Vec<Self::Row>),
+    tp: &TableParams<'entity, T>,
+    mut cb: impl FnMut(T) -> Result<(), <T as Table<'entity>>::Error>,
+  ) -> impl Future<Output = Result<(), <T as Table<'entity>>::Error>>
+  where
+    T: FromRows<Self::Row, Error = <T as Table<'entity>>::Error> + Table<'entity>,
+    T::Associations: SqlWriter<Error = <T as Table<'entity>>::Error>,
+  {
+    async move {
+      tp.write_select(
+        &mut *buffer_cmd,
+        (SelectOrderBy::Ascending, &[]),
+        SelectLimit::All,
+        Self::TY,
+        &mut |_| Ok(()),
+      )?;
+      // `buffer_cmd` holds the command while rows are received
+      let mut buffer_aux = String::new();
+      let mut last_id = None;
+      buffer_rows.clear();
       self
-        .rows(&mut *buffer_cmd, |row| {
+        .rows(buffer_cmd.as_str(), |row| {
+          let id = T::primary_key(&mut buffer_aux, &row, tp.table_suffix())?;
+          if last_id.as_ref().is_some_and(|elem| *elem != id) {
+            collect_entities_tables((&mut buffer_aux, &mut *buffer_rows), tp, &mut cb)?;
+            buffer_rows.clear();
+          }
+          last_id = Some(id);
           buffer_rows.push(row);
-          Ok(())
+          Ok::<_, <T as Table<'entity>>::Error>(())
         })
         .await?;
+      collect_entities_tables((&mut buffer_aux, &mut *buffer_rows), tp, &mut cb)?;
+      buffer_rows.clear();
       buffer_cmd.clear();
-      collect_entities_tables((buffer_cmd, buffer_rows), results, tp)?;
       Ok(())
     }
   }
 
-  /// Similar to `read_all` but expects more fine grained parameters.
+  /// Similar to `read_all` but expects more fine grained parameters. Entities can be filtered
+  /// by their own columns or by the columns of their associations, see [SqlWhere], as well as
+  /// sorted by the columns of the root table and paginated, see [SelectLimit::Page].
   fn read_all_with_params<'entity, T>(
     &mut self,
     (buffer_cmd, buffer_rows): (&mut String, &mut Vec<Self::Row>),
-    order_by: SelectOrderBy,
+    order_by: (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
     results: &mut Vec<T>,
     select_limit: SelectLimit,
+    sql_where: Option<&SqlWhere<'_, <T as Table<'entity>>::Error>>,
     tp: &TableParams<'entity, T>,
-    where_str: &str,
   ) -> impl Future<Output = Result<(), <T as Table<'entity>>::Error>>
   where
-    T: FromRows<Error = <T as Table<'entity>>::Error, Row = Self::Row> + Table<'entity>,
+    T: FromRows<Self::Row, Error = <T as Table<'entity>>::Error> + Table<'entity>,
     T::Associations: SqlWriter<Error = <T as Table<'entity>>::Error>,
   {
     async move {
-      tp.write_select(&mut *buffer_cmd, order_by, select_limit, &mut |b| {
-        b.push_str(where_str);
+      let mut bindings = SqlBindings::new(Self::TY);
+      tp.write_select(&mut *buffer_cmd, order_by, select_limit, Self::TY, &mut |b| {
+        if let Some(elem) = sql_where {
+          elem.write(b, &mut bindings)?;
+        }
         Ok(())
       })?;
       self
-        .rows(&mut *buffer_cmd, |row| {
+        .rows_with(buffer_cmd.as_str(), bindings.params(), |row| {
           buffer_rows.push(row);
           Ok(())
         })
         .await?;
       buffer_cmd.clear();
-      collect_entities_tables((buffer_cmd, buffer_rows), results, tp)?;
+      collect_entities_tables((buffer_cmd, buffer_rows), tp, |entity| {
+        results.push(entity);
+        Ok(())
+      })?;
       Ok(())
     }
   }
@@ -100,25 +179,79 @@ pub trait Crud: Database {
     tp: &TableParams<'entity, T>,
   ) -> impl Future<Output = Result<T, <T as Table<'entity>>::Error>>
   where
-    T: FromRows<Error = <T as Table<'entity>>::Error, Row = Self::Row> + Table<'entity>,
+    T: FromRows<Self::Row, Error = <T as Table<'entity>>::Error> + Table<'entity>,
     T::Associations: SqlWriter<Error = <T as Table<'entity>>::Error>,
   {
     async move {
-      tp.write_select(&mut *buffer_cmd, SelectOrderBy::Ascending, SelectLimit::All, &mut |b| {
-        write_select_field(
-          b,
-          T::TABLE_NAME,
-          T::TABLE_NAME_ALIAS,
-          tp.table_suffix(),
-          tp.id_field().name(),
-        )?;
-        buffer_write_fmt(b, format_args!(" = {id}"))
-      })?;
-      let row = self.row(&mut *buffer_cmd).await?;
+      let mut bindings = SqlBindings::new(Self::TY);
+      tp.write_select(
+        &mut *buffer_cmd,
+        (SelectOrderBy::Ascending, &[]),
+        SelectLimit::All,
+        Self::TY,
+        &mut |b| {
+          write_primary_key_values::<T>((b, &mut bindings), id, " AND ", |local_b, name| {
+            write_select_field(
+              local_b,
+              T::TABLE_NAME,
+              T::TABLE_NAME_ALIAS,
+              tp.table_suffix(),
+              name,
+              Self::TY,
+            )
+          })
+        },
+      )?;
+      let row = self.row_with(buffer_cmd.as_str(), bindings.params()).await?;
       buffer_cmd.clear();
       Ok(T::from_rows(&mut *buffer_cmd, &row, &[], tp.table_suffix())?.1)
     }
   }
+
+  /// Updates the table record represented by `table_params` as well as its associations and
+  /// returns the number of updated rows.
+  fn update<'entity, T>(
+    &mut self,
+    buffer_cmd: &mut String,
+    table: &'entity T,
+    table_params: &mut TableParams<'entity, T>,
+  ) -> impl Future<Output = Result<u64, T::Error>>
+  where
+    T: Table<'entity>,
+    T::Associations: SqlWriter<Error = T::Error>,
+  {
+    async move {
+      table_params.update_all_table_fields(table);
+      let mut bindings = SqlBindings::new(Self::TY);
+      table_params.write_update(&mut <_>::default(), (&mut *buffer_cmd, &mut bindings))?;
+      Ok(execute_statements(self, buffer_cmd, &bindings).await?)
+    }
+  }
+
+  /// Inserts the table record represented by `table_params` as well as its associations or
+  /// updates the ones that already exist. Returns the number of affected rows, which MySQL
+  /// reports as two for each updated row.
+  fn upsert<'entity, T>(
+    &mut self,
+    buffer_cmd: &mut String,
+    table: &'entity T,
+    table_params: &mut TableParams<'entity, T>,
+  ) -> impl Future<Output = Result<u64, T::Error>>
+  where
+    T: Table<'entity>,
+    T::Associations: SqlWriter<Error = T::Error>,
+  {
+    async move {
+      table_params.update_all_table_fields(table);
+      let mut bindings = SqlBindings::new(Self::TY);
+      table_params.write_upsert::<InitialInsertValue>(
+        &mut <_>::default(),
+        (&mut *buffer_cmd, &mut bindings),
+        &mut None,
+      )?;
+      Ok(execute_statements(self, buffer_cmd, &bindings).await?)
+    }
+  }
 }
 
 impl<T> Crud for T where T: Database {}
@@ -127,13 +260,14 @@ impl<T> Crud for T where T: Database {}
 ///
 /// One entity can constructed by more than one row.
 #[inline]
-fn collect_entities_tables<'entity, T>(
-  (buffer_cmd, buffer_rows): (&mut String, &mut Vec<T::Row>),
-  results: &mut Vec<T>,
+fn collect_entities_tables<'entity, R, T>(
+  (buffer_cmd, buffer_rows): (&mut String, &mut Vec<R>),
   tp: &TableParams<'entity, T>,
+  mut cb: impl FnMut(T) -> Result<(), <T as Table<'entity>>::Error>,
 ) -> Result<(), <T as Table<'entity>>::Error>
 where
-  T: FromRows<Error = <T as Table<'entity>>::Error> + Table<'entity>,
+  R: Row,
+  T: FromRows<R, Error = <T as Table<'entity>>::Error> + Table<'entity>,
 {
   let mut counter: usize = 0;
 
@@ -143,12 +277,35 @@ where
     }
     let actual_rows = buffer_rows.get(counter..).unwrap_or_default();
     let suffix = tp.table_suffix();
-    let skip = seek_related_entities(buffer_cmd, actual_rows, suffix, suffix, |entitiy| {
-      results.push(entitiy);
-      Ok(())
-    })?;
+    let skip = seek_related_entities(buffer_cmd, actual_rows, suffix, suffix, &mut cb)?;
     counter = counter.wrapping_add(skip);
   }
 
   Ok(())
 }
+
+// Each statement is executed separately with its own bound values but all of them share the same
+// transaction, which means that an entity is never partially written along its associations.
+#[inline]
+async fn execute_statements<D>(
+  database: &mut D,
+  buffer_cmd: &str,
+  bindings: &SqlBindings<'_>,
+) -> crate::Result<u64>
+where
+  D: Database,
+{
+  let mut affected_rows: u64 = 0;
+  let mut transaction = database.begin().await?;
+  for (cmd, params) in bindings.statements(buffer_cmd) {
+    match transaction.execute_with(cmd, params).await {
+      Ok(elem) => affected_rows = affected_rows.wrapping_add(elem),
+      Err(err) => {
+        let _rslt = transaction.rollback().await;
+        return Err(err);
+      }
+    }
+  }
+  transaction.commit().await?;
+  Ok(affected_rows)
+}
@@ -1,6 +1,9 @@
-use crate::orm::{
-  AuxNodes, FullTableAssociation, SelectLimit, SelectOrderBy, SqlWriter, TableAssociations,
-  TableSourceAssociation,
+use crate::{
+  orm::{
+    AuxNodes, FullTableAssociation, SelectLimit, SelectOrderBy, SqlBindings, SqlColumn, SqlWriter,
+    TableAssociations, TableSourceAssociation,
+  },
+  DatabaseTy,
 };
 use alloc::string::String;
 use core::{array, marker::PhantomData};
@@ -33,17 +36,24 @@ where
   type Error = E;
 
   #[inline]
-  fn write_delete(&self, _: &mut AuxNodes, _: &mut String) -> Result<(), Self::Error> {
+  fn write_delete<'this>(
+    &'this self,
+    _: &mut AuxNodes,
+    _: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_insert<V>(
-    &self,
+  fn write_insert<'this, V>(
+    &'this self,
     _: &mut AuxNodes,
-    _: &mut String,
-    _: &mut Option<TableSourceAssociation<'_, V>>,
-  ) -> Result<(), Self::Error> {
+    _: (&mut String, &mut SqlBindings<'this>),
+    _: &mut Option<TableSourceAssociation<'this, V>>,
+  ) -> Result<(), Self::Error>
+  where
+    V: ?Sized,
+  {
     Ok(())
   }
 
@@ -51,30 +61,53 @@ where
   fn write_select(
     &self,
     _: &mut String,
-    _: SelectOrderBy,
+    _: (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
     _: SelectLimit,
+    _: DatabaseTy,
     _: &mut impl FnMut(&mut String) -> Result<(), Self::Error>,
   ) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_select_associations(&self, _: &mut String) -> Result<(), Self::Error> {
+  fn write_select_associations(&self, _: &mut String, _: DatabaseTy) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_select_fields(&self, _: &mut String) -> Result<(), Self::Error> {
+  fn write_select_fields(&self, _: &mut String, _: DatabaseTy) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_select_orders_by(&self, _: &mut String) -> Result<(), Self::Error> {
+  fn write_select_orders_by(
+    &self,
+    _: &mut String,
+    _: SelectOrderBy,
+    _: DatabaseTy,
+  ) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  #[inline]
+  fn write_update<'this>(
+    &'this self,
+    _: &mut AuxNodes,
+    _: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), Self::Error> {
     Ok(())
   }
 
   #[inline]
-  fn write_update(&self, _: &mut AuxNodes, _: &mut String) -> Result<(), Self::Error> {
+  fn write_upsert<'this, V>(
+    &'this self,
+    _: &mut AuxNodes,
+    _: (&mut String, &mut SqlBindings<'this>),
+    _: &mut Option<TableSourceAssociation<'this, V>>,
+  ) -> Result<(), Self::Error>
+  where
+    V: ?Sized,
+  {
     Ok(())
   }
 }
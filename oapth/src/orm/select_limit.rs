@@ -1,8 +1,22 @@
 /// Sql select `LIMIT` clause
+///
+/// MS-SQL uses `OFFSET m ROWS FETCH NEXT n ROWS ONLY` instead of `LIMIT n OFFSET m`.
 #[derive(Clone, Copy, Debug)]
 pub enum SelectLimit {
-  /// LIMIT ALL
+  /// LIMIT ALL, which is omitted by databases other than PostgreSQL
   All,
-  /// LIMIT `n`
+  /// LIMIT `n`. One entity can span more than one joined row, as such, it can be truncated.
   Count(u32),
+  /// Skips the first `offset` root entities and fetches at most `count` of them along with all
+  /// their associations. The primary keys of the root table are selected by a subquery that has
+  /// the same filter and ordering of the outer command.
+  ///
+  /// Keyset pagination is achieved with an `offset` of zero and a filter that only accepts
+  /// primary keys after the last fetched one, see [crate::orm::SqlColumn::gt].
+  Page {
+    /// Maximum number of root entities
+    count: u32,
+    /// Number of skipped root entities
+    offset: u32,
+  },
 }
@@ -0,0 +1,100 @@
+use crate::{
+  database::SqlParam,
+  orm::{buffer_write_fmt, SqlValue},
+  DatabaseTy,
+};
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+/// Values bound to the placeholders of the statements written by the INSERT, UPDATE and DELETE
+/// writers of [crate::orm::SqlWriter].
+///
+/// Placeholders are numbered per statement, which allows the execution of each one of them with
+/// [crate::database::Database::execute_with].
+#[derive(Debug)]
+pub struct SqlBindings<'value> {
+  params: Vec<SqlParam<'value>>,
+  statements: Vec<(Range<usize>, Range<usize>)>,
+  ty: DatabaseTy,
+}
+
+impl<'value> SqlBindings<'value> {
+  /// Creates an empty instance whose placeholders follow the syntax of `ty`.
+  #[inline]
+  pub const fn new(ty: DatabaseTy) -> Self {
+    Self { params: Vec::new(), statements: Vec::new(), ty }
+  }
+
+  /// Removes all bound values and statements.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.params.clear();
+    self.statements.clear();
+  }
+
+  /// All bound values
+  #[inline]
+  pub fn params(&self) -> &[SqlParam<'value>] {
+    &self.params
+  }
+
+  /// Yields each statement written into `buffer_cmd`, including the trailing `;`, along with its
+  /// bound values.
+  #[inline]
+  pub fn statements<'cmd, 'this>(
+    &'this self,
+    buffer_cmd: &'cmd str,
+  ) -> impl Iterator<Item = (&'cmd str, &'this [SqlParam<'value>])> {
+    self.statements.iter().map(move |(cmd, params)| {
+      (
+        buffer_cmd.get(cmd.clone()).unwrap_or_default(),
+        self.params.get(params.clone()).unwrap_or_default(),
+      )
+    })
+  }
+
+  /// Syntax of placeholders and identifiers
+  #[inline]
+  pub const fn ty(&self) -> DatabaseTy {
+    self.ty
+  }
+
+  /// Finishes the statement that starts at the `cmd_start` index of `buffer_cmd`.
+  #[inline]
+  pub(crate) fn push_statement(&mut self, buffer_cmd: &mut String, cmd_start: usize) {
+    let params_start = self.params_start();
+    buffer_cmd.push(';');
+    self.statements.push((cmd_start..buffer_cmd.len(), params_start..self.params.len()));
+  }
+
+  /// Writes a placeholder bound to `value` or `null` if there is nothing to bind.
+  #[inline]
+  pub(crate) fn push_value<E, V>(
+    &mut self,
+    buffer_cmd: &mut String,
+    value: &'value V,
+  ) -> Result<(), E>
+  where
+    E: From<crate::Error>,
+    V: SqlValue<E> + ?Sized,
+  {
+    let param = value.param()?;
+    if let SqlParam::Null = param {
+      buffer_cmd.push_str("null");
+      return Ok(());
+    }
+    self.params.push(param);
+    let n = self.params.len().wrapping_sub(self.params_start());
+    match self.ty {
+      DatabaseTy::Mssql => buffer_write_fmt(buffer_cmd, format_args!("@P{n}"))?,
+      DatabaseTy::Postgres => buffer_write_fmt(buffer_cmd, format_args!("${n}"))?,
+      DatabaseTy::MySql | DatabaseTy::Sqlite | DatabaseTy::Unit => buffer_cmd.push('?'),
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn params_start(&self) -> usize {
+    self.statements.last().map_or(0, |el| el.1.end)
+  }
+}
@@ -0,0 +1,152 @@
+use crate::{
+  orm::{
+    write_full_select_field, write_select_field, write_select_order_by, SelectOrderBy, SqlValue,
+    SqlWhere, Table, TableField, TableParams,
+  },
+  DatabaseTy, TableSuffix,
+};
+use alloc::{string::String, vec::Vec};
+
+/// A column of a table instance that is part of a SELECT command. Used to build [SqlWhere]
+/// filters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SqlColumn {
+  field: &'static str,
+  table: &'static str,
+  table_alias: Option<&'static str>,
+  ts: TableSuffix,
+}
+
+impl SqlColumn {
+  /// Column of `field` in `table`. Associated tables are reached through the `guide` of their
+  /// [crate::orm::TableAssociationWrapper].
+  #[inline]
+  pub fn new<'entity, T, V>(table: &TableParams<'entity, T>, field: &TableField<V>) -> Self
+  where
+    T: Table<'entity>,
+  {
+    Self {
+      field: field.name(),
+      table: T::TABLE_NAME,
+      table_alias: T::TABLE_NAME_ALIAS,
+      ts: table.table_suffix(),
+    }
+  }
+
+  /// `column = value` or `column IS NULL` if `value` is `NULL`
+  #[inline]
+  pub fn eq<E, V>(self, value: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Eq(self, value)
+  }
+
+  /// `column >= value`
+  #[inline]
+  pub fn ge<E, V>(self, value: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Ge(self, value)
+  }
+
+  /// `column > value`
+  #[inline]
+  pub fn gt<E, V>(self, value: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Gt(self, value)
+  }
+
+  /// `column IN (values)`
+  #[inline]
+  pub fn in_list<E, V>(self, values: &[V]) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::InList(self, values.iter().map(|elem| elem as &dyn SqlValue<E>).collect::<Vec<_>>())
+  }
+
+  /// `column IS NULL`
+  #[inline]
+  pub fn is_null<E>(self) -> SqlWhere<'static, E> {
+    SqlWhere::IsNull(self)
+  }
+
+  /// `column <= value`
+  #[inline]
+  pub fn le<E, V>(self, value: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Le(self, value)
+  }
+
+  /// `column LIKE pattern`
+  #[inline]
+  pub fn like<E, V>(self, pattern: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Like(self, pattern)
+  }
+
+  /// `column < value`
+  #[inline]
+  pub fn lt<E, V>(self, value: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Lt(self, value)
+  }
+
+  /// `column <> value` or `column IS NOT NULL` if `value` is `NULL`
+  #[inline]
+  pub fn ne<E, V>(self, value: &V) -> SqlWhere<'_, E>
+  where
+    V: SqlValue<E>,
+  {
+    SqlWhere::Ne(self, value)
+  }
+
+  // If the column belongs to `table` itself instead of one of its associations.
+  #[inline]
+  pub(crate) fn is_of<'entity, T>(&self, table: &TableParams<'entity, T>) -> bool
+  where
+    T: Table<'entity>,
+  {
+    self.table == T::TABLE_NAME
+      && self.table_alias == T::TABLE_NAME_ALIAS
+      && self.ts == table.table_suffix()
+  }
+
+  #[inline]
+  pub(crate) fn write(&self, buffer_cmd: &mut String, ty: DatabaseTy) -> crate::Result<()> {
+    write_select_field(buffer_cmd, self.table, self.table_alias, self.ts, self.field, ty)
+  }
+
+  #[inline]
+  pub(crate) fn write_full(&self, buffer_cmd: &mut String, ty: DatabaseTy) -> crate::Result<()> {
+    write_full_select_field(buffer_cmd, self.table, self.table_alias, self.ts, self.field, ty)
+  }
+
+  #[inline]
+  pub(crate) fn write_order_by(
+    &self,
+    buffer_cmd: &mut String,
+    order_by: SelectOrderBy,
+    ty: DatabaseTy,
+  ) -> crate::Result<()> {
+    write_select_order_by(
+      buffer_cmd,
+      order_by,
+      self.table,
+      self.table_alias,
+      self.ts,
+      self.field,
+      ty,
+    )
+  }
+}
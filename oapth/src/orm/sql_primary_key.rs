@@ -0,0 +1,63 @@
+use crate::orm::SqlValue;
+use core::hash::Hash;
+
+/// Values of the columns that compose the primary key of a table.
+///
+/// Composite keys are represented by tuples whose elements follow the order of
+/// [crate::orm::Table::PRIMARY_KEY_NAMES].
+pub trait SqlPrimaryKey<E>: Copy + Hash {
+  /// The value of the key column located at `idx`.
+  fn column(&self, idx: usize) -> Option<&dyn SqlValue<E>>;
+}
+
+impl<E, T> SqlPrimaryKey<E> for &'_ T
+where
+  T: Hash + SqlValue<E>,
+{
+  #[inline]
+  fn column(&self, idx: usize) -> Option<&dyn SqlValue<E>> {
+    (idx == 0).then_some(*self as &dyn SqlValue<E>)
+  }
+}
+
+macro_rules! impl_single {
+  ($($ty:ty),*) => {
+    $(
+      impl<E> SqlPrimaryKey<E> for $ty
+      where
+        E: From<crate::Error>,
+      {
+        #[inline]
+        fn column(&self, idx: usize) -> Option<&dyn SqlValue<E>> {
+          (idx == 0).then_some(self as &dyn SqlValue<E>)
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! impl_composite {
+  ($( ($($idx:tt $ty:ident),+) )+) => {
+    $(
+      impl<E, $($ty),+> SqlPrimaryKey<E> for ($($ty,)+)
+      where
+        $($ty: Copy + Hash + SqlValue<E>,)+
+      {
+        #[inline]
+        fn column(&self, idx: usize) -> Option<&dyn SqlValue<E>> {
+          match idx {
+            $($idx => Some(&self.$idx),)+
+            _ => None,
+          }
+        }
+      }
+    )+
+  };
+}
+
+impl_single!(i32, i64, u32, u64);
+impl_composite!(
+  (0 A, 1 B)
+  (0 A, 1 B, 2 C)
+  (0 A, 1 B, 2 C, 3 D)
+);
@@ -1,10 +1,18 @@
-use crate::orm::buffer_write_fmt;
-use alloc::string::String;
+use crate::{
+  database::{non_finite_float_str, SqlParam},
+  orm::{buffer_write_fmt, write_escaped_literal},
+  DatabaseTy,
+};
+use alloc::{string::String, vec::Vec};
+use core::fmt::Arguments;
 
 /// Raw SQL representation of a type
 pub trait SqlValue<E> {
-  /// Pushes the representation into `buffer_cmd`.
-  fn write(&self, buffer_cmd: &mut String) -> Result<(), E>;
+  /// Typed value bound to a placeholder of parameterized commands.
+  fn param(&self) -> Result<SqlParam<'_>, E>;
+
+  /// Pushes the literal representation of `ty` into `buffer_cmd`.
+  fn write(&self, buffer_cmd: &mut String, ty: DatabaseTy) -> Result<(), E>;
 }
 
 impl<E, T> SqlValue<E> for &'_ T
@@ -12,8 +20,13 @@ where
   T: SqlValue<E>,
 {
   #[inline]
-  fn write(&self, buffer_cmd: &mut String) -> Result<(), E> {
-    (**self).write(buffer_cmd)
+  fn param(&self) -> Result<SqlParam<'_>, E> {
+    (**self).param()
+  }
+
+  #[inline]
+  fn write(&self, buffer_cmd: &mut String, ty: DatabaseTy) -> Result<(), E> {
+    (**self).write(buffer_cmd, ty)
   }
 }
 
@@ -22,9 +35,18 @@ where
   T: SqlValue<E>,
 {
   #[inline]
-  fn write(&self, buffer_cmd: &mut String) -> Result<(), E> {
+  fn param(&self) -> Result<SqlParam<'_>, E> {
     if let Some(ref elem) = *self {
-      elem.write(buffer_cmd)
+      elem.param()
+    } else {
+      Ok(SqlParam::Null)
+    }
+  }
+
+  #[inline]
+  fn write(&self, buffer_cmd: &mut String, ty: DatabaseTy) -> Result<(), E> {
+    if let Some(ref elem) = *self {
+      elem.write(buffer_cmd, ty)
     } else {
       buffer_cmd.push_str("null");
       Ok(())
@@ -32,28 +54,154 @@ where
   }
 }
 
-macro_rules! impl_display {
-  ($ty:ty $(, $($bounds:tt)+)?) => {
+macro_rules! impl_sql_value {
+  (
+    $ty:ty,
+    |$this:ident| $param:expr,
+    |$buffer_cmd:ident, $db_ty:ident| $write:expr
+    $(, $($bounds:tt)+)?
+  ) => {
     impl<E, $($($bounds)+)?> SqlValue<E> for $ty
     where
       E: From<crate::Error>
     {
       #[inline]
-      fn write(&self, buffer_cmd: &mut String) -> Result<(), E> {
-        buffer_write_fmt(buffer_cmd, format_args!("'{self}'"))
+      fn param(&self) -> Result<SqlParam<'_>, E> {
+        let $this = self;
+        Ok($param)
+      }
+
+      #[inline]
+      fn write(&self, $buffer_cmd: &mut String, $db_ty: DatabaseTy) -> Result<(), E> {
+        let $this = self;
+        $write
       }
     }
   }
 }
 
-impl_display!(&'_ str);
-impl_display!(arrayvec::ArrayString<N>, const N: usize);
-impl_display!(bool);
-impl_display!(i32);
-impl_display!(i64);
-impl_display!(u32);
-impl_display!(u64);
-impl_display!(String);
+macro_rules! impl_number {
+  ($ty:ty, |$this:ident| $param:expr) => {
+    impl_sql_value!($ty, |$this| $param, |buffer_cmd, _ty| buffer_write_fmt(
+      buffer_cmd,
+      format_args!("{}", $this)
+    ));
+  };
+}
+
+macro_rules! impl_float {
+  ($ty:ty, |$this:ident| $param:expr) => {
+    impl_sql_value!($ty, |$this| $param, |buffer_cmd, ty| write_float(
+      buffer_cmd,
+      (*$this).into(),
+      format_args!("{}", $this),
+      ty
+    ));
+  };
+}
 
+macro_rules! impl_text {
+  ($ty:ty, |$this:ident| $param:expr $(, $($bounds:tt)+)?) => {
+    impl_sql_value!(
+      $ty,
+      |$this| $param,
+      |buffer_cmd, ty| Ok(write_escaped_literal(buffer_cmd, format_args!("{}", $this), ty)?)
+      $(, $($bounds)+)?
+    );
+  };
+}
+
+impl_sql_value!(bool, |this| SqlParam::Bool(*this), |buffer_cmd, ty| {
+  buffer_cmd.push_str(match (ty, *this) {
+    (DatabaseTy::Postgres, true) => "true",
+    (DatabaseTy::Postgres, false) => "false",
+    (_, true) => "1",
+    (_, false) => "0",
+  });
+  Ok(())
+});
+impl_number!(i32, |this| SqlParam::I32(*this));
+impl_number!(i64, |this| SqlParam::I64(*this));
+impl_number!(u32, |this| SqlParam::I64((*this).into()));
+impl_number!(u64, |this| SqlParam::I64(i64::try_from(*this).map_err(crate::Error::from)?));
+impl_text!(&'_ str, |this| SqlParam::Str(this));
+impl_text!(arrayvec::ArrayString<N>, |this| SqlParam::Str(this.as_str()), const N: usize);
+impl_text!(String, |this| SqlParam::Str(this.as_str()));
+
+impl_float!(f32, |this| SqlParam::F32(*this));
+impl_float!(f64, |this| SqlParam::F64(*this));
+impl_sql_value!(&'_ [u8], |this| SqlParam::Bytes(this), |buffer_cmd, ty| write_bytes(
+  buffer_cmd, this, ty
+));
+impl_sql_value!(Vec<u8>, |this| SqlParam::Bytes(this), |buffer_cmd, ty| write_bytes(
+  buffer_cmd, this, ty
+));
+
+#[cfg(feature = "chrono")]
+impl_sql_value!(
+  chrono::DateTime<chrono::Utc>,
+  |this| SqlParam::DateTime(*this),
+  |buffer_cmd, ty| {
+    let fmt = match ty {
+      DatabaseTy::MySql => "%Y-%m-%d %H:%M:%S%.f",
+      DatabaseTy::Sqlite => "%Y-%m-%dT%H:%M:%S%.f+00:00",
+      _ => crate::database::DATE_TIME_FMT,
+    };
+    Ok(write_escaped_literal(buffer_cmd, format_args!("{}", this.format(fmt)), ty)?)
+  }
+);
+#[cfg(feature = "chrono")]
+impl_text!(chrono::NaiveDate, |this| SqlParam::NaiveDate(*this));
+#[cfg(feature = "chrono")]
+impl_text!(chrono::NaiveDateTime, |this| SqlParam::NaiveDateTime(*this));
 #[cfg(feature = "rust_decimal")]
-impl_display!(rust_decimal::Decimal);
+impl_number!(rust_decimal::Decimal, |this| SqlParam::Decimal(*this));
+#[cfg(feature = "serde_json")]
+impl_text!(serde_json::Value, |this| SqlParam::Json(this));
+// MySQL and SQLite store UUIDs as 16 bytes.
+#[cfg(feature = "uuid")]
+impl_sql_value!(uuid::Uuid, |this| SqlParam::Uuid(*this), |buffer_cmd, ty| match ty {
+  DatabaseTy::MySql | DatabaseTy::Sqlite => write_bytes(buffer_cmd, this.as_bytes(), ty),
+  _ => Ok(write_escaped_literal(buffer_cmd, format_args!("{this}"), ty)?),
+});
+
+// Hexadecimal literals: `'\x..'` in PostgreSQL, `0x..` in MS-SQL and `X'..'` in the rest.
+#[inline]
+fn write_bytes<E>(buffer_cmd: &mut String, bytes: &[u8], ty: DatabaseTy) -> Result<(), E>
+where
+  E: From<crate::Error>,
+{
+  let (prefix, suffix) = match ty {
+    DatabaseTy::Mssql => ("0x", ""),
+    DatabaseTy::Postgres => ("'\\x", "'"),
+    _ => ("X'", "'"),
+  };
+  buffer_cmd.push_str(prefix);
+  for byte in bytes {
+    buffer_write_fmt(buffer_cmd, format_args!("{byte:02x}"))?;
+  }
+  buffer_cmd.push_str(suffix);
+  Ok(())
+}
+
+#[inline]
+fn write_float<E>(
+  buffer_cmd: &mut String,
+  elem: f64,
+  literal: Arguments<'_>,
+  ty: DatabaseTy,
+) -> Result<(), E>
+where
+  E: From<crate::Error>,
+{
+  match (non_finite_float_str(elem), ty) {
+    (None, _) => buffer_write_fmt(buffer_cmd, literal),
+    (Some(elem), DatabaseTy::Postgres) => {
+      buffer_cmd.push('\'');
+      buffer_cmd.push_str(elem);
+      buffer_cmd.push('\'');
+      Ok(())
+    }
+    (Some(_), _) => Err(E::from(crate::Error::NonFiniteFloat)),
+  }
+}
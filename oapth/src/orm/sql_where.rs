@@ -0,0 +1,157 @@
+use crate::{
+  database::SqlParam,
+  orm::{SqlBindings, SqlColumn, SqlValue},
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::ops::Not;
+
+/// Filter of the SELECT commands written by [crate::orm::SqlWriter::write_select]. Usually
+/// constructed through the methods of [SqlColumn].
+///
+/// Values are never interpolated into the command, they are bound through [SqlBindings]
+/// instead.
+#[allow(
+  // `dyn SqlValue` does not implement `Debug`
+  missing_debug_implementations
+)]
+pub enum SqlWhere<'value, E> {
+  /// Both filters must match
+  And(Box<Self>, Box<Self>),
+  /// `column = value` or `column IS NULL` if `value` is `NULL`
+  Eq(SqlColumn, &'value dyn SqlValue<E>),
+  /// `column >= value`
+  Ge(SqlColumn, &'value dyn SqlValue<E>),
+  /// `column > value`
+  Gt(SqlColumn, &'value dyn SqlValue<E>),
+  /// `column IN (values)`. Nothing matches an empty list.
+  InList(SqlColumn, Vec<&'value dyn SqlValue<E>>),
+  /// `column IS NULL`
+  IsNull(SqlColumn),
+  /// `column <= value`
+  Le(SqlColumn, &'value dyn SqlValue<E>),
+  /// `column LIKE pattern`
+  Like(SqlColumn, &'value dyn SqlValue<E>),
+  /// `column < value`
+  Lt(SqlColumn, &'value dyn SqlValue<E>),
+  /// `column <> value` or `column IS NOT NULL` if `value` is `NULL`
+  Ne(SqlColumn, &'value dyn SqlValue<E>),
+  /// The inner filter must not match
+  Not(Box<Self>),
+  /// At least one of the filters must match
+  Or(Box<Self>, Box<Self>),
+}
+
+impl<'value, E> SqlWhere<'value, E>
+where
+  E: From<crate::Error>,
+{
+  /// Both `self` and `other` must match
+  #[inline]
+  #[must_use]
+  pub fn and(self, other: Self) -> Self {
+    Self::And(Box::new(self), Box::new(other))
+  }
+
+  /// At least `self` or `other` must match
+  #[inline]
+  #[must_use]
+  pub fn or(self, other: Self) -> Self {
+    Self::Or(Box::new(self), Box::new(other))
+  }
+
+  /// Writes the filter into `buffer_cmd` and binds its values to `bindings`.
+  #[inline]
+  pub fn write(
+    &self,
+    buffer_cmd: &mut String,
+    bindings: &mut SqlBindings<'value>,
+  ) -> Result<(), E> {
+    let ty = bindings.ty();
+    let mut write_cmp = |column: &SqlColumn, op: &str, value: &'value dyn SqlValue<E>| {
+      column.write(buffer_cmd, ty)?;
+      buffer_cmd.push_str(op);
+      bindings.push_value(buffer_cmd, value)
+    };
+    match *self {
+      Self::And(ref lhs, ref rhs) => Self::write_binary(buffer_cmd, bindings, lhs, " AND ", rhs)?,
+      Self::Eq(ref column, value) => {
+        if Self::is_null(value)? {
+          column.write(buffer_cmd, ty)?;
+          buffer_cmd.push_str(" IS NULL");
+        } else {
+          write_cmp(column, " = ", value)?;
+        }
+      }
+      Self::Ge(ref column, value) => write_cmp(column, " >= ", value)?,
+      Self::Gt(ref column, value) => write_cmp(column, " > ", value)?,
+      Self::InList(ref column, ref values) => {
+        if values.is_empty() {
+          buffer_cmd.push_str("1 = 0");
+          return Ok(());
+        }
+        column.write(buffer_cmd, ty)?;
+        buffer_cmd.push_str(" IN (");
+        for (idx, value) in values.iter().enumerate() {
+          if idx > 0 {
+            buffer_cmd.push(',');
+          }
+          bindings.push_value(buffer_cmd, *value)?;
+        }
+        buffer_cmd.push(')');
+      }
+      Self::IsNull(ref column) => {
+        column.write(buffer_cmd, ty)?;
+        buffer_cmd.push_str(" IS NULL");
+      }
+      Self::Le(ref column, value) => write_cmp(column, " <= ", value)?,
+      Self::Like(ref column, value) => write_cmp(column, " LIKE ", value)?,
+      Self::Lt(ref column, value) => write_cmp(column, " < ", value)?,
+      Self::Ne(ref column, value) => {
+        if Self::is_null(value)? {
+          column.write(buffer_cmd, ty)?;
+          buffer_cmd.push_str(" IS NOT NULL");
+        } else {
+          write_cmp(column, " <> ", value)?;
+        }
+      }
+      Self::Not(ref elem) => {
+        buffer_cmd.push_str("NOT (");
+        elem.write(buffer_cmd, bindings)?;
+        buffer_cmd.push(')');
+      }
+      Self::Or(ref lhs, ref rhs) => Self::write_binary(buffer_cmd, bindings, lhs, " OR ", rhs)?,
+    }
+    Ok(())
+  }
+
+  // Equality comparisons against `NULL` are never true
+  #[inline]
+  fn is_null(value: &dyn SqlValue<E>) -> Result<bool, E> {
+    Ok(matches!(value.param()?, SqlParam::Null))
+  }
+
+  #[inline]
+  fn write_binary(
+    buffer_cmd: &mut String,
+    bindings: &mut SqlBindings<'value>,
+    lhs: &Self,
+    op: &str,
+    rhs: &Self,
+  ) -> Result<(), E> {
+    buffer_cmd.push('(');
+    lhs.write(buffer_cmd, bindings)?;
+    buffer_cmd.push_str(op);
+    rhs.write(buffer_cmd, bindings)?;
+    buffer_cmd.push(')');
+    Ok(())
+  }
+}
+
+impl<'value, E> Not for SqlWhere<'value, E> {
+  type Output = Self;
+
+  #[inline]
+  fn not(self) -> Self::Output {
+    Self::Not(Box::new(self))
+  }
+}
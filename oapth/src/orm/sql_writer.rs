@@ -2,52 +2,89 @@ mod write_delete;
 mod write_insert;
 mod write_select;
 mod write_update;
-
-use crate::orm::{
-  write_full_select_field, write_select_join, write_select_order_by, AuxNodes, SelectLimit,
-  SelectOrderBy, Table, TableAssociations, TableFields, TableParams, TableSourceAssociation,
+mod write_upsert;
+
+use crate::{
+  orm::{
+    write_full_select_field, write_select_join, write_select_order_by, AuxNodes, SelectLimit,
+    SelectOrderBy, SqlBindings, SqlColumn, SqlValue, Table, TableAssociations, TableFields,
+    TableParams, TableSourceAssociation,
+  },
+  DatabaseTy,
 };
 use alloc::string::String;
-use core::{fmt::Display, marker::PhantomData};
+use core::marker::PhantomData;
 
-/// Writes raw SQL commands
+/// Writes raw SQL commands following the syntax of a [DatabaseTy]
 pub trait SqlWriter {
   /// See [crate::Error].
   type Error: From<crate::Error>;
 
-  /// Writes an entire DELETE command
-  fn write_delete(&self, aux: &mut AuxNodes, buffer_cmd: &mut String) -> Result<(), Self::Error>;
+  /// Writes an entire DELETE command whose values are bound through [SqlBindings]
+  fn write_delete<'this>(
+    &'this self,
+    aux: &mut AuxNodes,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), Self::Error>;
 
-  /// Writes an entire INSERT command
-  fn write_insert<V>(
-    &self,
+  /// Writes an entire INSERT command whose values are bound through [SqlBindings]
+  fn write_insert<'this, V>(
+    &'this self,
     aux: &mut AuxNodes,
-    buffer_cmd: &mut String,
-    table_source_association: &mut Option<TableSourceAssociation<'_, V>>,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+    table_source_association: &mut Option<TableSourceAssociation<'this, V>>,
   ) -> Result<(), Self::Error>
   where
-    V: Display;
+    V: SqlValue<Self::Error> + ?Sized;
 
-  /// Writes an entire SELECT command
+  /// Writes an entire SELECT command whose identifiers are quoted according to `ty`.
+  ///
+  /// Rows are sorted by the columns of `order_by` and then by all primary keys.
   fn write_select(
     &self,
     buffer_cmd: &mut String,
-    order_by: SelectOrderBy,
+    order_by: (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
     limit: SelectLimit,
+    ty: DatabaseTy,
     where_cb: &mut impl FnMut(&mut String) -> Result<(), Self::Error>,
   ) -> Result<(), Self::Error>;
 
   /// Only writes JOIN commands that belong to SELECT
-  fn write_select_associations(&self, buffer_cmd: &mut String) -> Result<(), Self::Error>;
+  fn write_select_associations(
+    &self,
+    buffer_cmd: &mut String,
+    ty: DatabaseTy,
+  ) -> Result<(), Self::Error>;
 
   /// Only writes querying fields that belong to SELECT
-  fn write_select_fields(&self, buffer_cmd: &mut String) -> Result<(), Self::Error>;
+  fn write_select_fields(&self, buffer_cmd: &mut String, ty: DatabaseTy)
+    -> Result<(), Self::Error>;
 
   /// Only writes ORDER BY commands that belong to SELECT
-  fn write_select_orders_by(&self, buffer_cmd: &mut String) -> Result<(), Self::Error>;
+  fn write_select_orders_by(
+    &self,
+    buffer_cmd: &mut String,
+    order_by: SelectOrderBy,
+    ty: DatabaseTy,
+  ) -> Result<(), Self::Error>;
 
-  /// Writes an entire UPDATE command
-  fn write_update(&self, aux: &mut AuxNodes, buffer_cmd: &mut String) -> Result<(), Self::Error>;
+  /// Writes an entire UPDATE command whose values are bound through [SqlBindings]
+  fn write_update<'this>(
+    &'this self,
+    aux: &mut AuxNodes,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), Self::Error>;
+
+  /// Writes an entire INSERT command that updates conflicting rows. PostgreSQL and SQLite use
+  /// `ON CONFLICT`, MySQL uses `ON DUPLICATE KEY UPDATE` and MS-SQL uses `MERGE`.
+  fn write_upsert<'this, V>(
+    &'this self,
+    aux: &mut AuxNodes,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+    table_source_association: &mut Option<TableSourceAssociation<'this, V>>,
+  ) -> Result<(), Self::Error>
+  where
+    V: SqlValue<Self::Error> + ?Sized;
 }
 
 impl<'entity, T> SqlWriter for TableParams<'entity, T>
@@ -58,85 +95,117 @@ where
   type Error = T::Error;
 
   #[inline]
-  fn write_delete(&self, aux: &mut AuxNodes, buffer_cmd: &mut String) -> Result<(), Self::Error> {
-    SqlWriterLogic::write_delete(aux, buffer_cmd, self)
+  fn write_delete<'this>(
+    &'this self,
+    aux: &mut AuxNodes,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), Self::Error> {
+    SqlWriterLogic::write_delete(aux, buffer, self)
   }
 
   #[inline]
-  fn write_insert<V>(
-    &self,
+  fn write_insert<'this, V>(
+    &'this self,
     aux: &mut AuxNodes,
-    buffer_cmd: &mut String,
-    tsa: &mut Option<TableSourceAssociation<'_, V>>,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+    tsa: &mut Option<TableSourceAssociation<'this, V>>,
   ) -> Result<(), Self::Error>
   where
-    V: Display,
+    V: SqlValue<Self::Error> + ?Sized,
   {
-    SqlWriterLogic::write_insert(aux, buffer_cmd, self, tsa)
+    SqlWriterLogic::write_insert(aux, buffer, self, tsa)
   }
 
   #[inline]
   fn write_select(
     &self,
     buffer_cmd: &mut String,
-    order_by: SelectOrderBy,
+    order_by: (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
     select_limit: SelectLimit,
+    ty: DatabaseTy,
     where_cb: &mut impl FnMut(&mut String) -> Result<(), Self::Error>,
   ) -> Result<(), Self::Error> {
-    SqlWriterLogic::write_select(buffer_cmd, order_by, select_limit, self, where_cb)
+    SqlWriterLogic::write_select(buffer_cmd, order_by, select_limit, self, ty, where_cb)
   }
 
   #[inline]
-  fn write_select_associations(&self, buffer_cmd: &mut String) -> Result<(), Self::Error> {
+  fn write_select_associations(
+    &self,
+    buffer_cmd: &mut String,
+    ty: DatabaseTy,
+  ) -> Result<(), Self::Error> {
     for full_association in self.associations().full_associations() {
-      write_select_join(buffer_cmd, T::TABLE_NAME, self.table_suffix(), full_association)?;
+      write_select_join(buffer_cmd, T::TABLE_NAME, self.table_suffix(), full_association, ty)?;
       buffer_cmd.push(' ');
     }
-    self.associations().write_select_associations(buffer_cmd)?;
+    self.associations().write_select_associations(buffer_cmd, ty)?;
     Ok(())
   }
 
   #[inline]
-  fn write_select_fields(&self, buffer_cmd: &mut String) -> Result<(), Self::Error> {
-    write_full_select_field(
-      buffer_cmd,
-      T::TABLE_NAME,
-      T::TABLE_NAME_ALIAS,
-      self.table_suffix(),
-      self.id_field().name(),
-    )?;
-    buffer_cmd.push(',');
-    for field in self.fields().field_names() {
+  fn write_select_fields(
+    &self,
+    buffer_cmd: &mut String,
+    ty: DatabaseTy,
+  ) -> Result<(), Self::Error> {
+    for field in T::PRIMARY_KEY_NAMES.iter().copied().chain(self.fields().field_names()) {
       write_full_select_field(
         buffer_cmd,
         T::TABLE_NAME,
         T::TABLE_NAME_ALIAS,
         self.table_suffix(),
         field,
+        ty,
       )?;
       buffer_cmd.push(',');
     }
-    self.associations().write_select_fields(buffer_cmd)?;
+    self.associations().write_select_fields(buffer_cmd, ty)?;
     Ok(())
   }
 
   #[inline]
-  fn write_select_orders_by(&self, buffer_cmd: &mut String) -> Result<(), Self::Error> {
-    write_select_order_by(
-      buffer_cmd,
-      T::TABLE_NAME,
-      T::TABLE_NAME_ALIAS,
-      self.table_suffix(),
-      self.id_field().name(),
-    )?;
-    buffer_cmd.push(',');
-    self.associations().write_select_orders_by(buffer_cmd)?;
+  fn write_select_orders_by(
+    &self,
+    buffer_cmd: &mut String,
+    order_by: SelectOrderBy,
+    ty: DatabaseTy,
+  ) -> Result<(), Self::Error> {
+    for field in T::PRIMARY_KEY_NAMES {
+      write_select_order_by(
+        buffer_cmd,
+        order_by,
+        T::TABLE_NAME,
+        T::TABLE_NAME_ALIAS,
+        self.table_suffix(),
+        field,
+        ty,
+      )?;
+      buffer_cmd.push(',');
+    }
+    self.associations().write_select_orders_by(buffer_cmd, order_by, ty)?;
     Ok(())
   }
 
   #[inline]
-  fn write_update(&self, aux: &mut AuxNodes, buffer_cmd: &mut String) -> Result<(), Self::Error> {
-    SqlWriterLogic::write_update(aux, buffer_cmd, self)
+  fn write_update<'this>(
+    &'this self,
+    aux: &mut AuxNodes,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), Self::Error> {
+    SqlWriterLogic::write_update(aux, buffer, self)
+  }
+
+  #[inline]
+  fn write_upsert<'this, V>(
+    &'this self,
+    aux: &mut AuxNodes,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+    tsa: &mut Option<TableSourceAssociation<'this, V>>,
+  ) -> Result<(), Self::Error>
+  where
+    V: SqlValue<Self::Error> + ?Sized,
+  {
+    SqlWriterLogic::write_upsert(aux, buffer, self, tsa)
   }
 }
 
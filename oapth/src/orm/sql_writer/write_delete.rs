@@ -1,6 +1,6 @@
 use crate::orm::{
-  buffer_write_fmt, node_was_already_visited, AuxNodes, SqlValue, SqlWriter, SqlWriterLogic, Table,
-  TableParams,
+  node_was_already_visited, write_escaped_identifier, write_primary_key_values, AuxNodes,
+  SqlBindings, SqlWriter, SqlWriterLogic, Table, TableParams,
 };
 use alloc::string::String;
 
@@ -10,30 +10,36 @@ where
   T::Associations: SqlWriter<Error = T::Error>,
 {
   #[inline]
-  pub(crate) fn write_delete(
+  pub(crate) fn write_delete<'this>(
     aux: &mut AuxNodes,
-    buffer_cmd: &mut String,
-    table: &TableParams<'entity, T>,
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
   ) -> Result<(), T::Error> {
     if node_was_already_visited(aux, table)? {
       return Ok(());
     }
-    table.associations().write_delete(aux, buffer_cmd)?;
-    Self::write_delete_manager(buffer_cmd, table)?;
+    table.associations().write_delete(aux, (&mut *buffer_cmd, &mut *bindings))?;
+    Self::write_delete_manager((buffer_cmd, bindings), table)?;
     Ok(())
   }
 
-  fn write_delete_manager(
-    buffer_cmd: &mut String,
-    table: &TableParams<'entity, T>,
+  fn write_delete_manager<'this>(
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
   ) -> Result<(), T::Error> {
     let id_value = if let Some(el) = table.id_field().value() { el } else { return Ok(()) };
-    buffer_write_fmt(
-      buffer_cmd,
-      format_args!("DELETE FROM {} WHERE {}=", T::TABLE_NAME, T::PRIMARY_KEY_NAME),
+    let len_before_delete = buffer_cmd.len();
+    let ty = bindings.ty();
+    buffer_cmd.push_str("DELETE FROM ");
+    write_escaped_identifier(buffer_cmd, format_args!("{}", T::TABLE_NAME), ty)?;
+    buffer_cmd.push_str(" WHERE ");
+    write_primary_key_values::<T>(
+      (&mut *buffer_cmd, &mut *bindings),
+      id_value,
+      " AND ",
+      |b, n| write_escaped_identifier(b, format_args!("{n}"), ty),
     )?;
-    id_value.write(buffer_cmd)?;
-    buffer_cmd.push(';');
+    bindings.push_statement(buffer_cmd, len_before_delete);
     Ok(())
   }
 }
@@ -1,9 +1,12 @@
-use crate::orm::{
-  buffer_write_fmt, node_was_already_visited, truncate_if_ends_with_char, AuxNodes, SqlValue,
-  SqlWriter, SqlWriterLogic, Table, TableFields, TableParams, TableSourceAssociation,
+use crate::{
+  orm::{
+    node_was_already_visited, truncate_if_ends_with_char, write_escaped_identifier, AuxNodes,
+    SqlBindings, SqlPrimaryKey, SqlValue, SqlWriter, SqlWriterLogic, Table, TableFields,
+    TableParams, TableSourceAssociation,
+  },
+  DatabaseTy,
 };
 use alloc::string::String;
-use core::fmt::Display;
 
 impl<'entity, T> SqlWriterLogic<'entity, T>
 where
@@ -11,73 +14,114 @@ where
   T::Associations: SqlWriter<Error = T::Error>,
 {
   #[inline]
-  pub(crate) fn write_insert<V>(
+  pub(crate) fn write_insert<'this, V>(
     aux: &mut AuxNodes,
-    buffer_cmd: &mut String,
-    table: &TableParams<'entity, T>,
-    tsa: &mut Option<TableSourceAssociation<'_, V>>,
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
+    tsa: &mut Option<TableSourceAssociation<'this, V>>,
   ) -> Result<(), T::Error>
   where
-    V: Display,
+    V: SqlValue<T::Error> + ?Sized,
   {
     if node_was_already_visited(aux, table)? {
       return Ok(());
     }
 
-    let elem_opt = || {
-      if let Some(ref el) = *tsa {
-        (el.source_field() != table.id_field().name()).then_some(el)
-      } else {
-        None
-      }
-    };
-
-    if let Some(elem) = elem_opt() {
-      Self::write_insert_manager(
-        buffer_cmd,
-        table,
-        |local| buffer_write_fmt(local, format_args!(",{}", elem.source_field())),
-        |local| buffer_write_fmt(local, format_args!("'{}',", elem.source_value())),
-      )?;
+    let elem_opt = if let Some(ref el) = *tsa {
+      (el.source_field() != table.id_field().name()).then_some(el)
     } else {
-      Self::write_insert_manager(buffer_cmd, table, |_| Ok(()), |_| Ok(()))?;
-    }
+      None
+    };
+    Self::write_insert_manager((&mut *buffer_cmd, &mut *bindings), table, elem_opt)?;
 
-    let mut new_tsa = table.id_field().value().as_ref().map(TableSourceAssociation::new);
-    table.associations().write_insert(aux, buffer_cmd, &mut new_tsa)?;
+    let mut new_tsa = table
+      .id_field()
+      .value()
+      .as_ref()
+      .and_then(|elem| elem.column(0))
+      .map(TableSourceAssociation::new);
+    table.associations().write_insert(aux, (buffer_cmd, bindings), &mut new_tsa)?;
 
     Ok(())
   }
 
-  fn write_insert_manager(
+  // Columns of INSERT statements, optionally qualified by `prefix`.
+  pub(crate) fn write_insert_columns<V>(
     buffer_cmd: &mut String,
+    prefix: Option<&str>,
     table: &TableParams<'entity, T>,
-    foreign_key_name_cb: impl Fn(&mut String) -> crate::Result<()>,
-    foreign_key_value_cb: impl Fn(&mut String) -> crate::Result<()>,
-  ) -> Result<(), T::Error> {
-    let len_before_insert = buffer_cmd.len();
-
-    buffer_write_fmt(buffer_cmd, format_args!("INSERT INTO \"{}\" (", T::TABLE_NAME))?;
-    buffer_cmd.push_str(table.id_field().name());
-    for field in table.fields().field_names() {
-      buffer_write_fmt(buffer_cmd, format_args!(",{field}"))?;
+    tsa: Option<&TableSourceAssociation<'_, V>>,
+    ty: DatabaseTy,
+  ) -> Result<(), T::Error>
+  where
+    V: ?Sized,
+  {
+    let columns = T::PRIMARY_KEY_NAMES
+      .iter()
+      .copied()
+      .chain(table.fields().field_names())
+      .chain(tsa.map(TableSourceAssociation::source_field));
+    for (idx, column) in columns.enumerate() {
+      if idx > 0 {
+        buffer_cmd.push(',');
+      }
+      if let Some(elem) = prefix {
+        write_escaped_identifier(buffer_cmd, format_args!("{elem}"), ty)?;
+        buffer_cmd.push('.');
+      }
+      write_escaped_identifier(buffer_cmd, format_args!("{column}"), ty)?;
     }
-    foreign_key_name_cb(&mut *buffer_cmd)?;
+    Ok(())
+  }
 
-    buffer_cmd.push_str(") VALUES (");
+  // Placeholders of INSERT statements. Returns `false` if there is nothing to insert.
+  pub(crate) fn write_insert_values<'this, V>(
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
+    tsa: Option<&TableSourceAssociation<'this, V>>,
+  ) -> Result<bool, T::Error>
+  where
+    V: SqlValue<T::Error> + ?Sized,
+  {
     let len_before_values = buffer_cmd.len();
-    if let &Some(elem) = table.id_field().value() {
-      elem.write(buffer_cmd)?;
+    if let Some(elem) = table.id_field().value() {
+      for idx in 0..T::PRIMARY_KEY_NAMES.len() {
+        bindings.push_value(buffer_cmd, elem.column(idx).ok_or(crate::Error::InvalidSqlQuery)?)?;
+        buffer_cmd.push(',');
+      }
+    }
+    table.fields().write_insert_values((&mut *buffer_cmd, &mut *bindings))?;
+    if buffer_cmd.len() == len_before_values {
+      return Ok(false);
+    }
+    if let Some(elem) = tsa {
+      bindings.push_value(buffer_cmd, elem.source_value())?;
       buffer_cmd.push(',');
     }
-    table.fields().write_insert_values(buffer_cmd)?;
+    truncate_if_ends_with_char(buffer_cmd, ',');
+    Ok(true)
+  }
 
-    if buffer_cmd.len() == len_before_values {
-      buffer_cmd.truncate(len_before_insert);
+  fn write_insert_manager<'this, V>(
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
+    tsa: Option<&TableSourceAssociation<'this, V>>,
+  ) -> Result<(), T::Error>
+  where
+    V: SqlValue<T::Error> + ?Sized,
+  {
+    let len_before_insert = buffer_cmd.len();
+    let ty = bindings.ty();
+    buffer_cmd.push_str("INSERT INTO ");
+    write_escaped_identifier(buffer_cmd, format_args!("{}", T::TABLE_NAME), ty)?;
+    buffer_cmd.push_str(" (");
+    Self::write_insert_columns(buffer_cmd, None, table, tsa, ty)?;
+    buffer_cmd.push_str(") VALUES (");
+    if Self::write_insert_values((&mut *buffer_cmd, &mut *bindings), table, tsa)? {
+      buffer_cmd.push(')');
+      bindings.push_statement(buffer_cmd, len_before_insert);
     } else {
-      foreign_key_value_cb(&mut *buffer_cmd)?;
-      truncate_if_ends_with_char(buffer_cmd, ',');
-      buffer_cmd.push_str(");");
+      buffer_cmd.truncate(len_before_insert);
     }
     Ok(())
   }
@@ -1,6 +1,10 @@
-use crate::orm::{
-  buffer_write_fmt, truncate_if_ends_with_char, truncate_if_ends_with_str, SelectLimit,
-  SelectOrderBy, SqlWriter, SqlWriterLogic, Table, TableParams,
+use crate::{
+  orm::{
+    buffer_write_fmt, truncate_if_ends_with_char, truncate_if_ends_with_str,
+    write_escaped_identifier, SelectLimit, SelectOrderBy, SqlColumn, SqlWriter, SqlWriterLogic,
+    Table, TableField, TableParams,
+  },
+  DatabaseTy,
 };
 use alloc::string::String;
 
@@ -12,38 +16,154 @@ where
   #[inline]
   pub(crate) fn write_select(
     buffer_cmd: &mut String,
-    order_by: SelectOrderBy,
+    (order_by, order_by_columns): (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
     select_limit: SelectLimit,
     table: &TableParams<'entity, T>,
+    ty: DatabaseTy,
     where_cb: &mut impl FnMut(&mut String) -> Result<(), T::Error>,
   ) -> Result<(), T::Error> {
+    // Root primary keys come right after the provided columns, which keeps the rows of each root
+    // entity next to each other.
+    if order_by_columns.iter().any(|(column, _)| !column.is_of(table)) {
+      return Err(crate::Error::NonRootOrderByColumn.into());
+    }
     buffer_cmd.push_str("SELECT ");
-    table.write_select_fields(buffer_cmd)?;
+    table.write_select_fields(buffer_cmd, ty)?;
+    truncate_if_ends_with_char(buffer_cmd, ',');
+    Self::write_select_from(buffer_cmd, table, ty)?;
+    buffer_cmd.push_str(" WHERE ");
+    let where_start = buffer_cmd.len();
+    where_cb(buffer_cmd)?;
+    if let SelectLimit::Page { count, offset } = select_limit {
+      if buffer_cmd.len() > where_start {
+        buffer_cmd.insert(where_start, '(');
+        buffer_cmd.push_str(") AND ");
+      }
+      Self::write_select_page(
+        buffer_cmd,
+        (order_by, order_by_columns),
+        (count, offset),
+        table,
+        ty,
+        where_cb,
+      )?;
+    }
+    truncate_if_ends_with_str(buffer_cmd, " WHERE ");
+    buffer_cmd.push_str(" ORDER BY ");
+    for (column, column_order_by) in order_by_columns {
+      column.write_order_by(buffer_cmd, *column_order_by, ty)?;
+      buffer_cmd.push(',');
+    }
+    table.write_select_orders_by(buffer_cmd, order_by, ty)?;
     truncate_if_ends_with_char(buffer_cmd, ',');
-    buffer_write_fmt(
+    match (ty, select_limit) {
+      (
+        DatabaseTy::Mssql | DatabaseTy::MySql | DatabaseTy::Sqlite,
+        SelectLimit::All | SelectLimit::Page { .. },
+      ) => {}
+      (DatabaseTy::Mssql, SelectLimit::Count(n)) => {
+        buffer_write_fmt(buffer_cmd, format_args!(" OFFSET 0 ROWS FETCH NEXT {n} ROWS ONLY"))?;
+      }
+      (DatabaseTy::Postgres | DatabaseTy::Unit, SelectLimit::All | SelectLimit::Page { .. }) => {
+        buffer_cmd.push_str(" LIMIT ALL");
+      }
+      (_, SelectLimit::Count(n)) => buffer_write_fmt(buffer_cmd, format_args!(" LIMIT {n}"))?,
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn write_select_from(
+    buffer_cmd: &mut String,
+    table: &TableParams<'entity, T>,
+    ty: DatabaseTy,
+  ) -> Result<(), T::Error> {
+    buffer_cmd.push_str(" FROM ");
+    write_escaped_identifier(buffer_cmd, format_args!("{}", T::TABLE_NAME), ty)?;
+    buffer_cmd.push_str(" AS ");
+    write_escaped_identifier(
       buffer_cmd,
-      format_args!(
-        " FROM \"{table}\" AS \"{table}{suffix}\" ",
-        suffix = table.table_suffix(),
-        table = T::TABLE_NAME
-      ),
+      format_args!("{}{}", T::TABLE_NAME_ALIAS.unwrap_or(T::TABLE_NAME), table.table_suffix()),
+      ty,
     )?;
-    table.write_select_associations(buffer_cmd)?;
+    buffer_cmd.push(' ');
+    table.write_select_associations(buffer_cmd, ty)?;
+    Ok(())
+  }
+
+  // Root primary keys are selected by a derived table because MySQL doesn't support `LIMIT`
+  // directly inside subqueries. `EXISTS` is used instead of `IN` to also match composite keys.
+  //
+  // Ordering columns belong to the root table, as such, the derived table has one row per root
+  // entity.
+  #[inline]
+  fn write_select_page(
+    buffer_cmd: &mut String,
+    (order_by, order_by_columns): (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
+    (count, offset): (u32, u32),
+    table: &TableParams<'entity, T>,
+    ty: DatabaseTy,
+    where_cb: &mut impl FnMut(&mut String) -> Result<(), T::Error>,
+  ) -> Result<(), T::Error> {
+    let ids = || {
+      T::PRIMARY_KEY_NAMES.iter().map(|name| SqlColumn::new(table, &TableField::<()>::new(name)))
+    };
+    buffer_cmd.push_str("EXISTS (SELECT 1 FROM (SELECT DISTINCT ");
+    for id in ids() {
+      id.write_full(buffer_cmd, ty)?;
+      buffer_cmd.push(',');
+    }
+    for (column, _) in order_by_columns.iter().filter(|(column, _)| !ids().any(|id| id == *column))
+    {
+      column.write_full(buffer_cmd, ty)?;
+      buffer_cmd.push(',');
+    }
+    truncate_if_ends_with_char(buffer_cmd, ',');
+    Self::write_select_from(buffer_cmd, table, ty)?;
     buffer_cmd.push_str(" WHERE ");
     where_cb(buffer_cmd)?;
     truncate_if_ends_with_str(buffer_cmd, " WHERE ");
     buffer_cmd.push_str(" ORDER BY ");
-    table.write_select_orders_by(buffer_cmd)?;
+    for (column, column_order_by) in order_by_columns {
+      column.write_order_by(buffer_cmd, *column_order_by, ty)?;
+      buffer_cmd.push(',');
+    }
+    for id in ids() {
+      id.write_order_by(buffer_cmd, order_by, ty)?;
+      buffer_cmd.push(',');
+    }
     truncate_if_ends_with_char(buffer_cmd, ',');
-    match order_by {
-      SelectOrderBy::Ascending => buffer_cmd.push_str(" ASC"),
-      SelectOrderBy::Descending => buffer_cmd.push_str(" DESC"),
+    if let DatabaseTy::Mssql = ty {
+      buffer_write_fmt(
+        buffer_cmd,
+        format_args!(" OFFSET {offset} ROWS FETCH NEXT {count} ROWS ONLY"),
+      )?;
+    } else {
+      buffer_write_fmt(buffer_cmd, format_args!(" LIMIT {count} OFFSET {offset}"))?;
     }
-    buffer_cmd.push_str(" LIMIT ");
-    match select_limit {
-      SelectLimit::All => buffer_cmd.push_str("ALL"),
-      SelectLimit::Count(n) => buffer_write_fmt(buffer_cmd, format_args!("{n}"))?,
+    buffer_cmd.push_str(") AS ");
+    write_escaped_identifier(buffer_cmd, format_args!("page"), ty)?;
+    buffer_cmd.push_str(" WHERE ");
+    for (idx, (id, name)) in ids().zip(T::PRIMARY_KEY_NAMES).enumerate() {
+      if idx > 0 {
+        buffer_cmd.push_str(" AND ");
+      }
+      write_escaped_identifier(buffer_cmd, format_args!("page"), ty)?;
+      buffer_cmd.push('.');
+      write_escaped_identifier(
+        buffer_cmd,
+        format_args!(
+          "{}{}__{}",
+          T::TABLE_NAME_ALIAS.unwrap_or(T::TABLE_NAME),
+          table.table_suffix(),
+          name
+        ),
+        ty,
+      )?;
+      buffer_cmd.push_str(" = ");
+      id.write(buffer_cmd, ty)?;
     }
+    buffer_cmd.push(')');
     Ok(())
   }
 }
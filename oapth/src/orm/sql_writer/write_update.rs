@@ -1,6 +1,7 @@
 use crate::orm::{
-  buffer_write_fmt, node_was_already_visited, truncate_if_ends_with_char, AuxNodes, SqlValue,
-  SqlWriter, SqlWriterLogic, Table, TableFields, TableParams,
+  node_was_already_visited, truncate_if_ends_with_char, write_escaped_identifier,
+  write_primary_key_values, AuxNodes, SqlBindings, SqlWriter, SqlWriterLogic, Table, TableFields,
+  TableParams,
 };
 use alloc::string::String;
 
@@ -10,37 +11,46 @@ where
   T::Associations: SqlWriter<Error = T::Error>,
 {
   #[inline]
-  pub(crate) fn write_update(
+  pub(crate) fn write_update<'this>(
     aux: &mut AuxNodes,
-    buffer_cmd: &mut String,
-    table: &TableParams<'entity, T>,
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
   ) -> Result<(), T::Error> {
     if node_was_already_visited(aux, table)? {
       return Ok(());
     }
-    Self::write_update_manager(buffer_cmd, table)?;
-    table.associations().write_update(aux, buffer_cmd)?;
+    Self::write_update_manager((&mut *buffer_cmd, &mut *bindings), table)?;
+    table.associations().write_update(aux, (buffer_cmd, bindings))?;
     Ok(())
   }
 
-  fn write_update_manager(
-    buffer_cmd: &mut String,
-    table: &TableParams<'entity, T>,
+  fn write_update_manager<'this>(
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
   ) -> Result<(), T::Error> {
     let id_value = if let Some(el) = table.id_field().value() { el } else { return Ok(()) };
+    let len_before_update = buffer_cmd.len();
 
-    buffer_write_fmt(buffer_cmd, format_args!("UPDATE {} SET ", T::TABLE_NAME))?;
+    let ty = bindings.ty();
+    buffer_cmd.push_str("UPDATE ");
+    write_escaped_identifier(buffer_cmd, format_args!("{}", T::TABLE_NAME), ty)?;
+    buffer_cmd.push_str(" SET ");
 
-    buffer_write_fmt(buffer_cmd, format_args!("{}=", table.id_field().name()))?;
-    id_value.write(buffer_cmd)?;
+    write_primary_key_values::<T>((&mut *buffer_cmd, &mut *bindings), id_value, ",", |b, n| {
+      write_escaped_identifier(b, format_args!("{n}"), ty)
+    })?;
     buffer_cmd.push(',');
-    table.fields().write_update_values(buffer_cmd)?;
+    table.fields().write_update_values((&mut *buffer_cmd, &mut *bindings))?;
     truncate_if_ends_with_char(buffer_cmd, ',');
 
     buffer_cmd.push_str(" WHERE ");
-    buffer_write_fmt(buffer_cmd, format_args!("{}=", T::PRIMARY_KEY_NAME))?;
-    id_value.write(buffer_cmd)?;
-    buffer_cmd.push(';');
+    write_primary_key_values::<T>(
+      (&mut *buffer_cmd, &mut *bindings),
+      id_value,
+      " AND ",
+      |b, n| write_escaped_identifier(b, format_args!("{n}"), ty),
+    )?;
+    bindings.push_statement(buffer_cmd, len_before_update);
 
     Ok(())
   }
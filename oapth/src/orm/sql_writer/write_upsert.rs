@@ -0,0 +1,159 @@
+use crate::{
+  orm::{
+    node_was_already_visited, write_escaped_identifier, AuxNodes, SqlBindings, SqlPrimaryKey,
+    SqlValue, SqlWriter, SqlWriterLogic, Table, TableFields, TableParams, TableSourceAssociation,
+  },
+  DatabaseTy,
+};
+use alloc::string::String;
+
+impl<'entity, T> SqlWriterLogic<'entity, T>
+where
+  T: Table<'entity>,
+  T::Associations: SqlWriter<Error = T::Error>,
+{
+  #[inline]
+  pub(crate) fn write_upsert<'this, V>(
+    aux: &mut AuxNodes,
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
+    tsa: &mut Option<TableSourceAssociation<'this, V>>,
+  ) -> Result<(), T::Error>
+  where
+    V: SqlValue<T::Error> + ?Sized,
+  {
+    if node_was_already_visited(aux, table)? {
+      return Ok(());
+    }
+
+    let elem_opt = if let Some(ref el) = *tsa {
+      (el.source_field() != table.id_field().name()).then_some(el)
+    } else {
+      None
+    };
+    Self::write_upsert_manager((&mut *buffer_cmd, &mut *bindings), table, elem_opt)?;
+
+    let mut new_tsa = table
+      .id_field()
+      .value()
+      .as_ref()
+      .and_then(|elem| elem.column(0))
+      .map(TableSourceAssociation::new);
+    table.associations().write_upsert(aux, (buffer_cmd, bindings), &mut new_tsa)?;
+
+    Ok(())
+  }
+
+  // Every non-primary column is overwritten by `ty`'s reference to the rejected value.
+  fn write_upsert_assignments<V>(
+    buffer_cmd: &mut String,
+    table: &TableParams<'entity, T>,
+    tsa: Option<&TableSourceAssociation<'_, V>>,
+    ty: DatabaseTy,
+  ) -> Result<bool, T::Error>
+  where
+    V: ?Sized,
+  {
+    let columns = table.fields().field_names().chain(tsa.map(TableSourceAssociation::source_field));
+    let mut has_assignments = false;
+    for column in columns {
+      if has_assignments {
+        buffer_cmd.push(',');
+      }
+      has_assignments = true;
+      write_escaped_identifier(buffer_cmd, format_args!("{column}"), ty)?;
+      buffer_cmd.push('=');
+      match ty {
+        DatabaseTy::Mssql => buffer_cmd.push_str("[source]."),
+        DatabaseTy::MySql => buffer_cmd.push_str("VALUES("),
+        DatabaseTy::Postgres | DatabaseTy::Sqlite | DatabaseTy::Unit => {
+          buffer_cmd.push_str("EXCLUDED.");
+        }
+      }
+      write_escaped_identifier(buffer_cmd, format_args!("{column}"), ty)?;
+      if let DatabaseTy::MySql = ty {
+        buffer_cmd.push(')');
+      }
+    }
+    Ok(has_assignments)
+  }
+
+  fn write_upsert_manager<'this, V>(
+    (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+    table: &'this TableParams<'entity, T>,
+    tsa: Option<&TableSourceAssociation<'this, V>>,
+  ) -> Result<(), T::Error>
+  where
+    V: SqlValue<T::Error> + ?Sized,
+  {
+    let len_before_upsert = buffer_cmd.len();
+    let ty = bindings.ty();
+    if let DatabaseTy::Mssql = ty {
+      buffer_cmd.push_str("MERGE INTO ");
+      write_escaped_identifier(buffer_cmd, format_args!("{}", T::TABLE_NAME), ty)?;
+      buffer_cmd.push_str(" AS [target] USING (VALUES (");
+      if !Self::write_insert_values((&mut *buffer_cmd, &mut *bindings), table, tsa)? {
+        buffer_cmd.truncate(len_before_upsert);
+        return Ok(());
+      }
+      buffer_cmd.push_str(")) AS [source] (");
+      Self::write_insert_columns(buffer_cmd, None, table, tsa, ty)?;
+      buffer_cmd.push_str(") ON ");
+      for (idx, name) in T::PRIMARY_KEY_NAMES.iter().enumerate() {
+        if idx > 0 {
+          buffer_cmd.push_str(" AND ");
+        }
+        buffer_cmd.push_str("[target].");
+        write_escaped_identifier(buffer_cmd, format_args!("{name}"), ty)?;
+        buffer_cmd.push_str(" = [source].");
+        write_escaped_identifier(buffer_cmd, format_args!("{name}"), ty)?;
+      }
+      let len_before_matched = buffer_cmd.len();
+      buffer_cmd.push_str(" WHEN MATCHED THEN UPDATE SET ");
+      if !Self::write_upsert_assignments(buffer_cmd, table, tsa, ty)? {
+        buffer_cmd.truncate(len_before_matched);
+      }
+      buffer_cmd.push_str(" WHEN NOT MATCHED THEN INSERT (");
+      Self::write_insert_columns(buffer_cmd, None, table, tsa, ty)?;
+      buffer_cmd.push_str(") VALUES (");
+      Self::write_insert_columns(buffer_cmd, Some("source"), table, tsa, ty)?;
+      buffer_cmd.push(')');
+    } else {
+      buffer_cmd.push_str("INSERT INTO ");
+      write_escaped_identifier(buffer_cmd, format_args!("{}", T::TABLE_NAME), ty)?;
+      buffer_cmd.push_str(" (");
+      Self::write_insert_columns(buffer_cmd, None, table, tsa, ty)?;
+      buffer_cmd.push_str(") VALUES (");
+      if !Self::write_insert_values((&mut *buffer_cmd, &mut *bindings), table, tsa)? {
+        buffer_cmd.truncate(len_before_upsert);
+        return Ok(());
+      }
+      buffer_cmd.push(')');
+      if let DatabaseTy::MySql = ty {
+        buffer_cmd.push_str(" ON DUPLICATE KEY UPDATE ");
+        if !Self::write_upsert_assignments(buffer_cmd, table, tsa, ty)? {
+          write_escaped_identifier(buffer_cmd, format_args!("{}", T::PRIMARY_KEY_NAME), ty)?;
+          buffer_cmd.push('=');
+          write_escaped_identifier(buffer_cmd, format_args!("{}", T::PRIMARY_KEY_NAME), ty)?;
+        }
+      } else {
+        buffer_cmd.push_str(" ON CONFLICT (");
+        for (idx, name) in T::PRIMARY_KEY_NAMES.iter().enumerate() {
+          if idx > 0 {
+            buffer_cmd.push(',');
+          }
+          write_escaped_identifier(buffer_cmd, format_args!("{name}"), ty)?;
+        }
+        buffer_cmd.push_str(") DO ");
+        let len_before_update = buffer_cmd.len();
+        buffer_cmd.push_str("UPDATE SET ");
+        if !Self::write_upsert_assignments(buffer_cmd, table, tsa, ty)? {
+          buffer_cmd.truncate(len_before_update);
+          buffer_cmd.push_str("NOTHING");
+        }
+      }
+    }
+    bindings.push_statement(buffer_cmd, len_before_upsert);
+    Ok(())
+  }
+}
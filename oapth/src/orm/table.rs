@@ -1,13 +1,14 @@
 use crate::{
-  orm::{FromSuffixRslt, SqlValue, TableAssociations, TableFields, TableParams},
+  orm::{FromSuffixRslt, SqlPrimaryKey, TableAssociations, TableFields, TableParams},
   TableSuffix,
 };
-use core::{fmt::Display, hash::Hash};
 
 /// All SQL definitions of an entity table.
 pub trait Table<'entity>: Sized {
-  /// Table primary key name
+  /// Table primary key name. The first column of composite keys
   const PRIMARY_KEY_NAME: &'static str;
+  /// All columns that compose the primary key, starting with `PRIMARY_KEY_NAME`
+  const PRIMARY_KEY_NAMES: &'static [&'static str] = &[Self::PRIMARY_KEY_NAME];
   /// Table name specified in the database
   const TABLE_NAME: &'static str;
   /// Optional table alias specified in the database
@@ -19,8 +20,8 @@ pub trait Table<'entity>: Sized {
   type Error: From<crate::Error>;
   /// All table fields minus the primary key. For more information, see [TableFields]
   type Fields: TableFields<Self::Error>;
-  /// Table primary key value type
-  type PrimaryKeyValue: Copy + Display + Hash + SqlValue<Self::Error>;
+  /// Table primary key value type. Composite keys are tuples, see [SqlPrimaryKey]
+  type PrimaryKeyValue: SqlPrimaryKey<Self::Error>;
 
   /// Implementation should provide all related fields and associations
   fn type_instances(ts: TableSuffix) -> FromSuffixRslt<'entity, Self>;
@@ -1,3 +1,4 @@
+use crate::orm::SqlBindings;
 use alloc::string::String;
 use core::array;
 
@@ -9,11 +10,17 @@ pub trait TableFields<E> {
   /// Yields all table field names.
   fn field_names(&self) -> Self::FieldNames;
 
-  /// Writes the table instance values for INSERT statements.
-  fn write_insert_values(&self, buffer_cmd: &mut String) -> Result<(), E>;
-
-  /// Writes the table instance values for UPDATE statements.
-  fn write_update_values(&self, buffer_cmd: &mut String) -> Result<(), E>;
+  /// Writes the placeholders of the table instance values for INSERT statements.
+  fn write_insert_values<'this>(
+    &'this self,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), E>;
+
+  /// Writes the placeholders of the table instance values for UPDATE statements.
+  fn write_update_values<'this>(
+    &'this self,
+    buffer: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), E>;
 }
 
 impl<E> TableFields<E> for ()
@@ -28,12 +35,18 @@ where
   }
 
   #[inline]
-  fn write_insert_values(&self, _: &mut String) -> Result<(), E> {
+  fn write_insert_values<'this>(
+    &'this self,
+    _: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), E> {
     Ok(())
   }
 
   #[inline]
-  fn write_update_values(&self, _: &mut String) -> Result<(), E> {
+  fn write_update_values<'this>(
+    &'this self,
+    _: (&mut String, &mut SqlBindings<'this>),
+  ) -> Result<(), E> {
     Ok(())
   }
 }
@@ -1,11 +1,17 @@
 /// Used by nodes that need source (backward) information
 #[derive(Clone, Copy, Debug)]
-pub struct TableSourceAssociation<'value, V = &'static str> {
+pub struct TableSourceAssociation<'value, V = &'static str>
+where
+  V: ?Sized,
+{
   source_field: &'static str,
   source_value: &'value V,
 }
 
-impl<'value, V> TableSourceAssociation<'value, V> {
+impl<'value, V> TableSourceAssociation<'value, V>
+where
+  V: ?Sized,
+{
   #[inline]
   pub(crate) const fn new(source_value: &'value V) -> Self {
     Self { source_field: "", source_value }
@@ -1,11 +1,14 @@
-use crate::orm::{
-  buffer_write_fmt, AuxNodes, FullTableAssociation, SelectLimit, SelectOrderBy, SqlValue,
-  SqlWriter, Table, TableAssociationWrapper, TableAssociations, TableField, TableFields,
-  TableParams, TableSourceAssociation,
+use crate::{
+  orm::{
+    write_escaped_identifier, AuxNodes, FullTableAssociation, SelectLimit, SelectOrderBy,
+    SqlBindings, SqlColumn, SqlValue, SqlWriter, Table, TableAssociationWrapper, TableAssociations,
+    TableField, TableFields, TableParams, TableSourceAssociation,
+  },
+  DatabaseTy,
 };
 use alloc::string::String;
 use cl_aux::SingleTypeStorage;
-use core::{array, fmt::Display};
+use core::array;
 
 macro_rules! double_tuple_impls {
   ($(
@@ -50,35 +53,35 @@ macro_rules! double_tuple_impls {
         type Error = ERR;
 
         #[inline]
-        fn write_delete(
-          &self,
+        fn write_delete<'this>(
+          &'this self,
           aux: &mut AuxNodes,
-          buffer_cmd: &mut String,
+          (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
         ) -> Result<(), Self::Error> {
           $(
             for elem in self.$idx.tables.as_ref() {
-              elem.write_delete(aux, buffer_cmd)?;
+              elem.write_delete(aux, (&mut *buffer_cmd, &mut *bindings))?;
             }
           )+
           Ok(())
         }
 
         #[inline]
-        fn write_insert<VALUE>(
-          &self,
+        fn write_insert<'this, VALUE>(
+          &'this self,
           aux: &mut AuxNodes,
-          buffer_cmd: &mut String,
-          table_source_association: &mut Option<TableSourceAssociation<'_, VALUE>>
+          (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+          table_source_association: &mut Option<TableSourceAssociation<'this, VALUE>>
         ) -> Result<(), Self::Error>
         where
-          VALUE: Display
+          VALUE: SqlValue<Self::Error> + ?Sized
         {
           $(
             if let Some(ref mut elem) = table_source_association.as_mut() {
               *elem.source_field_mut() = self.$idx.association.to_id();
             }
             for elem in self.$idx.tables.as_ref() {
-              elem.write_insert(aux, buffer_cmd, table_source_association)?;
+              elem.write_insert(aux, (&mut *buffer_cmd, &mut *bindings), table_source_association)?;
             }
           )+
           Ok(())
@@ -88,12 +91,13 @@ macro_rules! double_tuple_impls {
         fn write_select(
           &self,
           buffer_cmd: &mut String,
-          order_by: SelectOrderBy,
+          order_by: (SelectOrderBy, &[(SqlColumn, SelectOrderBy)]),
           limit: SelectLimit,
+          ty: DatabaseTy,
           where_cb: &mut impl FnMut(&mut String) -> Result<(), Self::Error>,
         ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select(buffer_cmd, order_by, limit, where_cb)?;
+            self.$idx.guide.write_select(buffer_cmd, order_by, limit, ty, where_cb)?;
           )+
           Ok(())
         }
@@ -101,10 +105,11 @@ macro_rules! double_tuple_impls {
         #[inline]
         fn write_select_associations(
           &self,
-            buffer_cmd: &mut String,
+          buffer_cmd: &mut String,
+          ty: DatabaseTy,
         ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select_associations(buffer_cmd)?;
+            self.$idx.guide.write_select_associations(buffer_cmd, ty)?;
           )+
           Ok(())
         }
@@ -112,31 +117,58 @@ macro_rules! double_tuple_impls {
         #[inline]
         fn write_select_fields(
           &self,
-            buffer_cmd: &mut String,
+          buffer_cmd: &mut String,
+          ty: DatabaseTy,
         ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select_fields(buffer_cmd)?;
+            self.$idx.guide.write_select_fields(buffer_cmd, ty)?;
           )+
           Ok(())
         }
 
         #[inline]
-        fn write_select_orders_by(&self, buffer_cmd: &mut String) -> Result<(), Self::Error> {
+        fn write_select_orders_by(
+          &self,
+          buffer_cmd: &mut String,
+          order_by: SelectOrderBy,
+          ty: DatabaseTy,
+        ) -> Result<(), Self::Error> {
           $(
-            self.$idx.guide.write_select_orders_by(buffer_cmd)?;
+            self.$idx.guide.write_select_orders_by(buffer_cmd, order_by, ty)?;
           )+
           Ok(())
         }
 
         #[inline]
-        fn write_update(
-          &self,
+        fn write_update<'this>(
+          &'this self,
           aux: &mut AuxNodes,
-          buffer_cmd: &mut String,
+          (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
         ) -> Result<(), Self::Error> {
           $(
             for elem in self.$idx.tables.as_ref() {
-              elem.write_update(aux, buffer_cmd)?;
+              elem.write_update(aux, (&mut *buffer_cmd, &mut *bindings))?;
+            }
+          )+
+          Ok(())
+        }
+
+        #[inline]
+        fn write_upsert<'this, VALUE>(
+          &'this self,
+          aux: &mut AuxNodes,
+          (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+          table_source_association: &mut Option<TableSourceAssociation<'this, VALUE>>
+        ) -> Result<(), Self::Error>
+        where
+          VALUE: SqlValue<Self::Error> + ?Sized
+        {
+          $(
+            if let Some(ref mut elem) = table_source_association.as_mut() {
+              *elem.source_field_mut() = self.$idx.association.to_id();
+            }
+            for elem in self.$idx.tables.as_ref() {
+              elem.write_upsert(aux, (&mut *buffer_cmd, &mut *bindings), table_source_association)?;
             }
           )+
           Ok(())
@@ -165,10 +197,13 @@ macro_rules! tuple_impls {
         }
 
         #[inline]
-        fn write_insert_values(&self, buffer_cmd: &mut String) -> Result<(), ERR> {
+        fn write_insert_values<'this>(
+          &'this self,
+          (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+        ) -> Result<(), ERR> {
           $(
-            if let &Some(ref elem) = self.$idx.value() {
-              elem.write(buffer_cmd)?;
+            if let Some(elem) = self.$idx.value() {
+              bindings.push_value(buffer_cmd, elem)?;
               buffer_cmd.push(',');
             }
           )+
@@ -176,11 +211,15 @@ macro_rules! tuple_impls {
         }
 
         #[inline]
-        fn write_update_values(&self, buffer_cmd: &mut String) -> Result<(), ERR> {
+        fn write_update_values<'this>(
+          &'this self,
+          (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+        ) -> Result<(), ERR> {
           $(
-            if let &Some(ref elem) = self.$idx.value() {
-              buffer_write_fmt(buffer_cmd, format_args!("{}=", self.$idx.name()))?;
-              elem.write(buffer_cmd)?;
+            if let Some(elem) = self.$idx.value() {
+              write_escaped_identifier(buffer_cmd, format_args!("{}", self.$idx.name()), bindings.ty())?;
+              buffer_cmd.push('=');
+              bindings.push_value(buffer_cmd, elem)?;
               buffer_cmd.push(',');
             }
           )+
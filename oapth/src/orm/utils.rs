@@ -1,10 +1,12 @@
 mod fx_hasher;
 
 use crate::{
-  orm::{AuxNodes, FullTableAssociation, Table, TableParams},
-  FromRows, Row, TableSuffix,
+  orm::{
+    AuxNodes, FullTableAssociation, SelectOrderBy, SqlBindings, SqlPrimaryKey, Table, TableParams,
+  },
+  DatabaseTy, FromRows, FromSqlColumn, Row, TableSuffix,
 };
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use core::fmt::{Arguments, Write};
 pub(crate) use fx_hasher::*;
 
@@ -17,19 +19,99 @@ where
   buffer_cmd.write_fmt(args).map_err(|err| E::from(crate::Error::Fmt(err)))
 }
 
+/// Constructs every distinct `T` found in `rows` under the suffix `ts`, which is how
+/// collections of associated entities are gathered from joined rows.
+///
+/// Rows whose primary key can not be decoded, like the NULL values of unmatched joins, are
+/// ignored.
+#[inline]
+pub fn collect_related_entities<'entity, E, R, T>(
+  buffer_cmd: &mut String,
+  rows: &[R],
+  ts: TableSuffix,
+  mut cb: impl FnMut(T) -> Result<(), E>,
+) -> Result<(), E>
+where
+  E: From<crate::Error>,
+  R: Row,
+  T: FromRows<R, Error = E> + Table<'entity, Error = E>,
+{
+  let mut seen = Vec::new();
+  for (idx, row) in rows.iter().enumerate() {
+    let Ok(key) = T::primary_key(buffer_cmd, row, ts) else {
+      continue;
+    };
+    if seen.contains(&key) {
+      continue;
+    }
+    seen.push(key);
+    let curr_rows = rows.get(idx..).unwrap_or_default();
+    cb(T::from_rows(buffer_cmd, row, curr_rows, ts)?.1)?;
+  }
+  Ok(())
+}
+
+/// Decodes the value of the `{table}{suffix}__{field}` column of `row`.
+#[inline]
+pub fn decode_column_alias<T, R>(
+  buffer_cmd: &mut String,
+  row: &R,
+  table: &str,
+  ts: TableSuffix,
+  field: &str,
+) -> crate::Result<T>
+where
+  R: Row,
+  T: FromSqlColumn<R>,
+{
+  let len = buffer_cmd.len();
+  write_column_alias(buffer_cmd, table, ts, field)?;
+  let rslt = T::from_column_name(row, buffer_cmd.get(len..).unwrap_or_default());
+  buffer_cmd.truncate(len);
+  rslt
+}
+
+/// The number of consecutive rows, starting from the first one, that share the primary key of
+/// `T` under the suffix `ts`.
+#[inline]
+pub fn entity_rows_len<R, T>(
+  buffer_cmd: &mut String,
+  rows: &[R],
+  ts: TableSuffix,
+) -> Result<usize, T::Error>
+where
+  R: Row,
+  T: FromRows<R>,
+{
+  let mut iter = rows.iter();
+  let Some(first) = iter.next() else {
+    return Ok(0);
+  };
+  let first_key = T::primary_key(buffer_cmd, first, ts)?;
+  let mut len: usize = 1;
+  for row in iter {
+    if T::primary_key(buffer_cmd, row, ts).ok().as_ref() != Some(&first_key) {
+      break;
+    }
+    len = len.wrapping_add(1);
+  }
+  Ok(len)
+}
+
 /// Seeks all rows that equals `T`'s primary key and suffix. Can be `T` itself or any other
 /// associated/related entity.
 #[inline]
-pub fn seek_related_entities<'entity, E, T>(
+pub fn seek_related_entities<'entity, E, R, T>(
   buffer_cmd: &mut String,
-  rows: &[T::Row],
+  rows: &[R],
   ts: TableSuffix,
   ts_related: TableSuffix,
   mut cb: impl FnMut(T) -> Result<(), E>,
 ) -> Result<usize, E>
 where
   E: From<crate::Error>,
-  T: FromRows<Error = E> + Table<'entity, Error = E>,
+  R: Row,
+  T: FromRows<R, Error = E> + Table<'entity, Error = E>,
 {
   if rows.is_empty() {
     return Ok(0);
@@ -43,9 +125,7 @@ where
 
   let first_rslt = T::from_rows(buffer_cmd, first_row, rows, ts_related);
   let (mut counter, mut previous) = if let Ok((skip, entity)) = first_rslt {
-    write_column_alias(buffer_cmd, T::TABLE_NAME, ts, T::PRIMARY_KEY_NAME)?;
-    let previous = first_row.i64_from_name(buffer_cmd.as_ref()).map_err(Into::into)?;
-    buffer_cmd.clear();
+    let previous = T::primary_key(buffer_cmd, first_row, ts)?;
     cb(entity)?;
     (skip, previous)
   } else {
@@ -66,10 +146,7 @@ where
 
     let curr_rows = rows.get(counter..).unwrap_or_default();
     let (skip, entity) = T::from_rows(buffer_cmd, row, curr_rows, ts_related)?;
-
-    write_column_alias(buffer_cmd, T::TABLE_NAME, ts, T::PRIMARY_KEY_NAME)?;
-    let curr = row.i64_from_name(buffer_cmd.as_ref()).map_err(Into::into)?;
-    buffer_cmd.clear();
+    let curr = T::primary_key(buffer_cmd, row, ts)?;
     if previous == curr {
       cb(entity)?;
       counter = counter.wrapping_add(skip);
@@ -94,6 +171,53 @@ pub fn write_column_alias(
   Ok(())
 }
 
+/// Writes `identifier` delimited by the quotes of `ty`. Inner closing quotes are doubled.
+///
+/// MS-SQL uses brackets, MySQL uses backticks and the others use double quotes.
+#[inline]
+pub fn write_escaped_identifier(
+  buffer_cmd: &mut String,
+  identifier: Arguments<'_>,
+  ty: DatabaseTy,
+) -> crate::Result<()> {
+  let (open, close) = match ty {
+    DatabaseTy::Mssql => ('[', ']'),
+    DatabaseTy::MySql => ('`', '`'),
+    DatabaseTy::Postgres | DatabaseTy::Sqlite | DatabaseTy::Unit => ('"', '"'),
+  };
+  buffer_cmd.push(open);
+  EscapedWriter { buffer_cmd, escape: |c| (c == close).then_some(close) }.write_fmt(identifier)?;
+  buffer_cmd.push(close);
+  Ok(())
+}
+
+/// Writes `literal` as a string literal of `ty`. Inner single quotes are doubled and MySQL
+/// backslashes are escaped.
+///
+/// MS-SQL literals are prefixed by `N` to preserve non-ASCII characters.
+#[inline]
+pub fn write_escaped_literal(
+  buffer_cmd: &mut String,
+  literal: Arguments<'_>,
+  ty: DatabaseTy,
+) -> crate::Result<()> {
+  if let DatabaseTy::Mssql = ty {
+    buffer_cmd.push('N');
+  }
+  buffer_cmd.push('\'');
+  EscapedWriter {
+    buffer_cmd,
+    escape: |c| match c {
+      '\'' => Some('\''),
+      '\\' if ty == DatabaseTy::MySql => Some('\\'),
+      _ => None,
+    },
+  }
+  .write_fmt(literal)?;
+  buffer_cmd.push('\'');
+  Ok(())
+}
+
 pub(crate) fn node_was_already_visited<'entity, T>(
   aux: &mut AuxNodes,
   table: &TableParams<'entity, T>,
@@ -118,6 +242,31 @@ where
   Ok(false)
 }
 
+// Writes `"a"=$1{separator}"b"=$2` for every primary key column of `T`. Columns are written by
+// `column_cb`.
+#[inline]
+pub(crate) fn write_primary_key_values<'entity, 'this, T>(
+  (buffer_cmd, bindings): (&mut String, &mut SqlBindings<'this>),
+  key: &'this T::PrimaryKeyValue,
+  separator: &str,
+  mut column_cb: impl FnMut(&mut String, &'static str) -> crate::Result<()>,
+) -> Result<(), T::Error>
+where
+  'entity: 'this,
+  T: Table<'entity> + 'this,
+{
+  for (idx, name) in T::PRIMARY_KEY_NAMES.iter().enumerate() {
+    let value = key.column(idx).ok_or(crate::Error::InvalidSqlQuery)?;
+    if idx > 0 {
+      buffer_cmd.push_str(separator);
+    }
+    column_cb(buffer_cmd, name)?;
+    buffer_cmd.push('=');
+    bindings.push_value(buffer_cmd, value)?;
+  }
+  Ok(())
+}
+
 #[inline]
 pub(crate) fn truncate_if_ends_with_char(buffer_cmd: &mut String, c: char) {
   if buffer_cmd.ends_with(c) {
@@ -139,11 +288,12 @@ pub(crate) fn write_full_select_field(
   table_alias: Option<&str>,
   ts: TableSuffix,
   field: &str,
+  ty: DatabaseTy,
 ) -> crate::Result<()> {
   let actual_table = table_alias.unwrap_or(table);
-  write_select_field(buffer_cmd, table, table_alias, ts, field)?;
-  buffer_cmd.write_fmt(format_args!(" AS {actual_table}{ts}__{field}"))?;
-  Ok(())
+  write_select_field(buffer_cmd, table, table_alias, ts, field, ty)?;
+  buffer_cmd.push_str(" AS ");
+  write_escaped_identifier(buffer_cmd, format_args!("{actual_table}{ts}__{field}"), ty)
 }
 
 #[inline]
@@ -153,10 +303,12 @@ pub(crate) fn write_select_field(
   table_alias: Option<&str>,
   ts: TableSuffix,
   field: &str,
+  ty: DatabaseTy,
 ) -> crate::Result<()> {
   let actual_table = table_alias.unwrap_or(table);
-  buffer_cmd.write_fmt(format_args!("\"{actual_table}{ts}\".{field}"))?;
-  Ok(())
+  write_escaped_identifier(buffer_cmd, format_args!("{actual_table}{ts}"), ty)?;
+  buffer_cmd.push('.');
+  write_escaped_identifier(buffer_cmd, format_args!("{field}"), ty)
 }
 
 #[inline]
@@ -165,31 +317,70 @@ pub(crate) fn write_select_join(
   from_table: &str,
   from_table_suffix: TableSuffix,
   full_association: FullTableAssociation,
+  ty: DatabaseTy,
 ) -> crate::Result<()> {
   let association = full_association.association();
-  buffer_cmd.write_fmt(format_args!(
-    "LEFT JOIN \"{table_relationship}\" AS \"{table_relationship_alias}{to_table_suffix}\" ON \
-     \"{from_table}{from_table_suffix}\".{table_id} = \
-     \"{table_relationship_alias}{to_table_suffix}\".{table_relationship_id}",
-    table_id = association.from_id(),
-    table_relationship = full_association.to_table(),
-    table_relationship_alias =
-      full_association.to_table_alias().unwrap_or_else(|| full_association.to_table()),
-    table_relationship_id = association.to_id(),
-    to_table_suffix = full_association.to_table_suffix(),
-  ))?;
+  let to_table = full_association.to_table();
+  let to_table_alias = full_association.to_table_alias();
+  let to_table_suffix = full_association.to_table_suffix();
+  buffer_cmd.push_str("LEFT JOIN ");
+  write_escaped_identifier(buffer_cmd, format_args!("{to_table}"), ty)?;
+  buffer_cmd.push_str(" AS ");
+  write_escaped_identifier(
+    buffer_cmd,
+    format_args!("{}{to_table_suffix}", to_table_alias.unwrap_or(to_table)),
+    ty,
+  )?;
+  buffer_cmd.push_str(" ON ");
+  write_select_field(buffer_cmd, from_table, None, from_table_suffix, association.from_id(), ty)?;
+  buffer_cmd.push_str(" = ");
+  write_select_field(
+    buffer_cmd,
+    to_table,
+    to_table_alias,
+    to_table_suffix,
+    association.to_id(),
+    ty,
+  )?;
   Ok(())
 }
 
 #[inline]
 pub(crate) fn write_select_order_by(
   buffer_cmd: &mut String,
+  order_by: SelectOrderBy,
   table: &str,
   table_alias: Option<&str>,
   ts: TableSuffix,
   field: &str,
+  ty: DatabaseTy,
 ) -> crate::Result<()> {
-  let actual_table = table_alias.unwrap_or(table);
-  buffer_cmd.write_fmt(format_args!("\"{actual_table}{ts}\".{field}",))?;
+  write_select_field(buffer_cmd, table, table_alias, ts, field, ty)?;
+  match order_by {
+    SelectOrderBy::Ascending => buffer_cmd.push_str(" ASC"),
+    SelectOrderBy::Descending => buffer_cmd.push_str(" DESC"),
+  }
   Ok(())
 }
+
+// Prefixes every character that needs escaping with the character returned by `escape`.
+struct EscapedWriter<'buffer, F> {
+  buffer_cmd: &'buffer mut String,
+  escape: F,
+}
+
+impl<F> Write for EscapedWriter<'_, F>
+where
+  F: FnMut(char) -> Option<char>,
+{
+  #[inline]
+  fn write_str(&mut self, s: &str) -> core::fmt::Result {
+    for c in s.chars() {
+      if let Some(elem) = (self.escape)(c) {
+        self.buffer_cmd.push(elem);
+      }
+      self.buffer_cmd.push(c);
+    }
+    Ok(())
+  }
+}
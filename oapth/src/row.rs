@@ -1,8 +1,39 @@
+use crate::FromSqlColumn;
+
 macro_rules! create_and_impl_row {
   ($(($(#[$mac:meta])* $name:ident, $param:ty, $ret:ty, $dummy:expr)),* $(,)?) => {
       /// Raw database row
       pub trait Row {
         $($(#[$mac])* fn $name(&self, _elem: $param) -> crate::Result<$ret>;)*
+
+        /// Name of the column located at `idx`.
+        fn column_name(&self, idx: usize) -> Option<&str>;
+
+        /// Type declared by the database for the column located at `idx`.
+        fn column_ty(&self, idx: usize) -> Option<&str>;
+
+        /// Number of columns.
+        fn columns_len(&self) -> usize;
+
+        /// Decodes the value of the column located at `idx`. See [FromSqlColumn].
+        #[inline]
+        fn value_from_idx<T>(&self, idx: usize) -> crate::Result<T>
+        where
+          Self: Sized,
+          T: FromSqlColumn<Self>,
+        {
+          T::from_column_idx(self, idx)
+        }
+
+        /// Decodes the value of the column identified by `name`. See [FromSqlColumn].
+        #[inline]
+        fn value_from_name<T>(&self, name: &str) -> crate::Result<T>
+        where
+          Self: Sized,
+          T: FromSqlColumn<Self>,
+        {
+          T::from_column_name(self, name)
+        }
       }
 
       impl Row for () {
@@ -12,6 +43,46 @@ macro_rules! create_and_impl_row {
             Ok($dummy)
           }
         )*
+
+        #[inline]
+        fn column_name(&self, _: usize) -> Option<&str> {
+          None
+        }
+
+        #[inline]
+        fn column_ty(&self, _: usize) -> Option<&str> {
+          None
+        }
+
+        #[inline]
+        fn columns_len(&self) -> usize {
+          0
+        }
+      }
+
+      #[cfg(feature = "postgres")]
+      impl Row for crate::database::PostgresRow {
+        $(
+          #[inline]
+          fn $name(&self, _elem: $param) -> crate::Result<$ret> {
+            self.decode(_elem)
+          }
+        )*
+
+        #[inline]
+        fn column_name(&self, idx: usize) -> Option<&str> {
+          self.name(idx)
+        }
+
+        #[inline]
+        fn column_ty(&self, idx: usize) -> Option<&str> {
+          self.ty(idx)
+        }
+
+        #[inline]
+        fn columns_len(&self) -> usize {
+          self.len()
+        }
       }
 
       #[cfg(feature = "sqlx-mysql")]
@@ -23,6 +94,8 @@ macro_rules! create_and_impl_row {
             Ok(self.try_get(_elem)?)
           }
         )*
+
+        _impl_sqlx_row_metadata!();
       }
 
       #[cfg(feature = "sqlx-postgres")]
@@ -34,6 +107,8 @@ macro_rules! create_and_impl_row {
             Ok(self.try_get(_elem)?)
           }
         )*
+
+        _impl_sqlx_row_metadata!();
       }
 
       #[cfg(feature = "sqlx-sqlite")]
@@ -45,6 +120,8 @@ macro_rules! create_and_impl_row {
             Ok(self.try_get(_elem)?)
           }
         )*
+
+        _impl_sqlx_row_metadata!();
       }
 
       #[cfg(feature = "tiberius")]
@@ -55,10 +132,48 @@ macro_rules! create_and_impl_row {
             self.try_get(_elem)?.ok_or(crate::Error::InvalidSqlQuery)
           }
         )*
+
+        #[inline]
+        fn column_name(&self, idx: usize) -> Option<&str> {
+          Some(self.columns().get(idx)?.name())
+        }
+
+        #[inline]
+        fn column_ty(&self, idx: usize) -> Option<&str> {
+          Some(crate::database::tiberius_column_ty(self.columns().get(idx)?.column_type()))
+        }
+
+        #[inline]
+        fn columns_len(&self) -> usize {
+          self.columns().len()
+        }
       }
   };
 }
 
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+macro_rules! _impl_sqlx_row_metadata {
+  () => {
+    #[inline]
+    fn column_name(&self, idx: usize) -> Option<&str> {
+      use sqlx_core::{column::Column, row::Row};
+      Some(self.columns().get(idx)?.name())
+    }
+
+    #[inline]
+    fn column_ty(&self, idx: usize) -> Option<&str> {
+      use sqlx_core::{column::Column, row::Row, type_info::TypeInfo};
+      Some(self.columns().get(idx)?.type_info().name())
+    }
+
+    #[inline]
+    fn columns_len(&self) -> usize {
+      use sqlx_core::row::Row;
+      self.columns().len()
+    }
+  };
+}
+
 create_and_impl_row!(
   (
     /// Retrieves a `&str` from a column index.
@@ -12,19 +12,23 @@ pub mod migration_parser;
 mod repeatability;
 #[cfg(feature = "std")]
 pub mod toml_parser;
+mod transaction_mode;
 pub mod utils;
 
 pub use commands::*;
 pub use repeatability::Repeatability;
+pub use transaction_mode::TransactionMode;
 #[cfg(all(feature = "_integration-tests", test))]
 mod integration_tests;
 use crate::{database::Database, DatabaseTy, Identifier};
 use alloc::{string::String, vec::Vec};
-use core::future::Future;
+use core::{future::Future, time::Duration};
 pub use migration::*;
 
 /// Default batch size
 pub const DEFAULT_BATCH_SIZE: usize = 128;
+/// Default amount of time a runner waits for the migration lock held by another runner.
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
 pub(crate) const _OAPTH: &str = "oapth";
 pub(crate) const _OAPTH_SCHEMA_PREFIX: &str = "_oapth.";
 
@@ -40,6 +44,18 @@ pub type EmbeddedMigrationsTy = &'static [(
 
 /// Contains methods responsible to manage database migrations.
 pub trait SchemaManagement: Database {
+  /// Records a set of migrations as applied without executing them.
+  fn baseline_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &MigrationGroup<S>,
+    migrations: I,
+  ) -> impl Future<Output = crate::Result<()>>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration;
+
   /// Clears all database resources.
   fn clear(
     &mut self,
@@ -59,18 +75,45 @@ pub trait SchemaManagement: Database {
   where
     S: AsRef<str>;
 
-  /// Inserts a new set of migrations,
+  /// Number of records left behind by failed executions of the given `mg` group.
+  fn failed_migrations_len<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &MigrationGroup<S>,
+  ) -> impl Future<Output = crate::Result<u64>>
+  where
+    S: AsRef<str>;
+
+  /// If the tables created by [Self::create_oapth_tables] exist.
+  fn has_oapth_tables(&mut self) -> impl Future<Output = crate::Result<bool>>;
+
+  /// Inserts a new set of migrations grouped into transactions according to `transaction_mode`.
   fn insert_migrations<'migration, DBS, I, S>(
     &mut self,
     buffer_cmd: &mut String,
     mg: &MigrationGroup<S>,
     migrations: I,
+    transaction_mode: TransactionMode,
   ) -> impl Future<Output = crate::Result<()>>
   where
     DBS: AsRef<[DatabaseTy]> + 'migration,
     I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
     S: AsRef<str> + 'migration;
 
+  /// Acquires an exclusive lock that prevents concurrent runners from modifying the same
+  /// database, waiting at most `timeout`.
+  ///
+  /// Returns [crate::Error::LockNotAcquired] if the lock is still held by someone else after
+  /// `timeout`.
+  ///
+  /// The lock of SQLite is the `_oapth_lock` table, which stays behind if a runner is killed
+  /// while holding it and must then be manually dropped.
+  fn lock(
+    &mut self,
+    buffer_cmd: &mut String,
+    timeout: Duration,
+  ) -> impl Future<Output = crate::Result<()>>;
+
   /// Retrieves all migrations of the given `mg` group.
   fn migrations<S>(
     &mut self,
@@ -81,6 +124,17 @@ pub trait SchemaManagement: Database {
   where
     S: AsRef<str>;
 
+  /// Removes records of failed executions of the given `mg` group and realigns the names and
+  /// checksums of `entries`, returning the number of removed records.
+  fn repair_migrations<S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &MigrationGroup<S>,
+    entries: &[MigrationRepairEntry],
+  ) -> impl Future<Output = crate::Result<u64>>
+  where
+    S: AsRef<str>;
+
   /// Retrieves all tables contained in a schema. If the implementation does not supports schemas,
   /// the parameter is ignored.
   fn table_names(
@@ -89,4 +143,21 @@ pub trait SchemaManagement: Database {
     results: &mut Vec<Identifier>,
     schema: &str,
   ) -> impl Future<Output = crate::Result<()>>;
+
+  /// Releases the lock previously acquired by [Self::lock].
+  fn unlock(&mut self, buffer_cmd: &mut String) -> impl Future<Output = crate::Result<()>>;
+
+  /// Executes again a set of already applied repeatable migrations, updating their stored
+  /// checksums and repeatabilities. Transactions are grouped according to `transaction_mode`.
+  fn update_migrations<'migration, DBS, I, S>(
+    &mut self,
+    buffer_cmd: &mut String,
+    mg: &MigrationGroup<S>,
+    migrations: I,
+    transaction_mode: TransactionMode,
+  ) -> impl Future<Output = crate::Result<()>>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration;
 }
@@ -1,22 +1,36 @@
+mod baseline;
 #[cfg(feature = "sm-dev")]
 mod clear;
 mod migrate;
+mod plan;
+mod repair;
 mod rollback;
 #[cfg(feature = "sm-dev")]
 mod seed;
+mod status;
 mod validate;
 
 use crate::{
   database::Database,
-  sm::{UserMigration, DEFAULT_BATCH_SIZE},
+  sm::{
+    MigrationGroup, SchemaManagement, TransactionMode, UserMigration, DEFAULT_BATCH_SIZE,
+    DEFAULT_LOCK_TIMEOUT,
+  },
   DatabaseTy,
 };
+use alloc::string::String;
+use core::time::Duration;
+
+pub(crate) type MigrationFromGroups<'slice, 'migration_group, 'migration_slice, DBS, S> =
+  &'slice [(&'migration_group MigrationGroup<S>, &'migration_slice [UserMigration<DBS, S>])];
 
 /// SQL commands facade
 #[derive(Debug)]
 pub struct Commands<D> {
   batch_size: usize,
   pub(crate) database: D,
+  lock_timeout: Duration,
+  transaction_mode: TransactionMode,
 }
 
 impl<D> Commands<D>
@@ -26,7 +40,12 @@ where
   /// Creates a new instance from a given Backend and batch size.
   #[inline]
   pub fn new(batch_size: usize, database: D) -> Self {
-    Self { batch_size, database }
+    Self {
+      batch_size,
+      database,
+      lock_timeout: DEFAULT_LOCK_TIMEOUT,
+      transaction_mode: TransactionMode::PerMigration,
+    }
   }
 
   /// Creates a new instance from a given Backend.
@@ -34,7 +53,12 @@ where
   /// Batch size will default to 128.
   #[inline]
   pub fn with_database(database: D) -> Self {
-    Self { batch_size: DEFAULT_BATCH_SIZE, database }
+    Self {
+      batch_size: DEFAULT_BATCH_SIZE,
+      database,
+      lock_timeout: DEFAULT_LOCK_TIMEOUT,
+      transaction_mode: TransactionMode::PerMigration,
+    }
   }
 
   /// Batch size
@@ -43,6 +67,35 @@ where
     self.batch_size
   }
 
+  /// Maximum amount of time `migrate`, `rollback` and `clear` wait for the migration lock.
+  #[inline]
+  pub fn lock_timeout(&self) -> Duration {
+    self.lock_timeout
+  }
+
+  /// Changes the maximum amount of time `migrate`, `rollback` and `clear` wait for the migration
+  /// lock.
+  ///
+  /// Defaults to 30 seconds.
+  #[inline]
+  pub fn set_lock_timeout(&mut self, lock_timeout: Duration) {
+    self.lock_timeout = lock_timeout;
+  }
+
+  /// Changes how `migrate` groups migrations into transactions.
+  ///
+  /// Defaults to [TransactionMode::PerMigration].
+  #[inline]
+  pub fn set_transaction_mode(&mut self, transaction_mode: TransactionMode) {
+    self.transaction_mode = transaction_mode;
+  }
+
+  /// See [TransactionMode].
+  #[inline]
+  pub fn transaction_mode(&self) -> TransactionMode {
+    self.transaction_mode
+  }
+
   #[inline]
   fn filter_by_db<'migration, DBS, I, S>(
     migrations: I,
@@ -55,3 +108,29 @@ where
     migrations.filter(move |m| if m.dbs().is_empty() { true } else { m.dbs().contains(&D::TY) })
   }
 }
+
+impl<D> Commands<D>
+where
+  D: SchemaManagement,
+{
+  #[inline]
+  async fn acquire_lock(&mut self, buffer_cmd: &mut String) -> crate::Result<()> {
+    buffer_cmd.clear();
+    self.database.lock(buffer_cmd, self.lock_timeout).await
+  }
+
+  // The lock is always released but errors of the locked operation take precedence.
+  #[inline]
+  async fn release_lock<T>(
+    &mut self,
+    buffer_cmd: &mut String,
+    rslt: crate::Result<T>,
+  ) -> crate::Result<T> {
+    buffer_cmd.clear();
+    let unlock_rslt = self.database.unlock(buffer_cmd).await;
+    buffer_cmd.clear();
+    let value = rslt?;
+    unlock_rslt?;
+    Ok(value)
+  }
+}
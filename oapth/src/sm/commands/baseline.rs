@@ -0,0 +1,72 @@
+use crate::{
+  sm::{Commands, DbMigration, MigrationGroup, SchemaManagement, UserMigration},
+  DatabaseTy,
+};
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use {
+  crate::sm::utils::{group_and_migrations_from_path, parse_root_toml},
+  std::path::Path,
+};
+
+impl<D> Commands<D>
+where
+  D: SchemaManagement,
+{
+  /// Records every migration whose version is less than or equal to `version` as applied without
+  /// executing them, which allows the adoption of databases whose schemas were created by other
+  /// means.
+  ///
+  /// Stored names and checksums are the ones of the provided migrations. Fails if the group
+  /// already has applied migrations.
+  #[inline]
+  pub async fn baseline<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+    version: i32,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    buffer_db_migrations.clear();
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+      if !buffer_db_migrations.is_empty() {
+        buffer_db_migrations.clear();
+        return Err(crate::Error::BaselineWithAppliedMigrations);
+      }
+      let migrations = Self::filter_by_db(user_migrations).filter(|m| m.version() <= version);
+      self.database.baseline_migrations(buffer_cmd, mg, migrations).await
+    }
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
+  }
+
+  /// Applies `baseline` to the group named `mg_name` according to the configuration file.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub async fn baseline_from_toml_path(
+    &mut self,
+    buffer: (&mut String, &mut Vec<DbMigration>),
+    path: &Path,
+    mg_name: &str,
+    version: i32,
+  ) -> crate::Result<()> {
+    let (migration_groups, _) = parse_root_toml(path)?;
+    for mg_path in migration_groups {
+      let (mg, migrations) = group_and_migrations_from_path(&mg_path, Ord::cmp)?;
+      if mg.name() != mg_name {
+        continue;
+      }
+      let user_migrations = migrations.collect::<crate::Result<Vec<_>>>()?;
+      return self.baseline(buffer, &mg, user_migrations.iter(), version).await;
+    }
+    Err(crate::Error::MissingMigrationGroup)
+  }
+}
@@ -9,8 +9,15 @@ where
   D: SchemaManagement,
 {
   /// Tries to clear all objects of a database, including separated namespaces/schemas.
+  ///
+  /// The migration lock is held during the whole operation, see [SchemaManagement::lock].
   #[inline]
-  pub async fn clear(&mut self, buffer: (&mut String, &mut Vec<Identifier>)) -> crate::Result<()> {
-    self.database.clear(buffer).await
+  pub async fn clear(
+    &mut self,
+    (buffer_cmd, buffer_idents): (&mut String, &mut Vec<Identifier>),
+  ) -> crate::Result<()> {
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt = self.database.clear((buffer_cmd, buffer_idents)).await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 }
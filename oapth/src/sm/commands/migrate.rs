@@ -1,5 +1,8 @@
 use crate::{
-  sm::{Commands, DbMigration, MigrationGroup, SchemaManagement, UserMigration},
+  sm::{
+    commands::MigrationFromGroups, utils::needs_rerun, Commands, DbMigration, MigrationGroup,
+    SchemaManagement, UserMigration,
+  },
   DatabaseTy,
 };
 use alloc::{string::String, vec::Vec};
@@ -9,15 +12,14 @@ use {
   std::path::{Path, PathBuf},
 };
 
-type MigrationFromGroups<'slice, 'migration_group, 'migration_slice, DBS, S> =
-  &'slice [(&'migration_group MigrationGroup<S>, &'migration_slice [UserMigration<DBS, S>])];
-
 impl<D> Commands<D>
 where
   D: SchemaManagement,
 {
   /// Migrates everything inside a group that is greater than the last migration version within the
-  /// database
+  /// database and executes again applied repeatable migrations that need to, see [crate::sm::Repeatability].
+  ///
+  /// The migration lock is held during the whole operation, see [SchemaManagement::lock].
   #[inline]
   pub async fn migrate<'migration, DBS, I, S>(
     &mut self,
@@ -31,9 +33,14 @@ where
     S: AsRef<str> + 'migration,
   {
     buffer_db_migrations.clear();
-    self.database.create_oapth_tables().await?;
-    self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
-    self.do_migrate((buffer_cmd, buffer_db_migrations), mg, user_migrations).await
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+      self.do_migrate((buffer_cmd, buffer_db_migrations), mg, user_migrations).await
+    }
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 
   /// Applies `migrate` to a set of migrations according to a given directory
@@ -41,11 +48,16 @@ where
   #[inline]
   pub async fn migrate_from_dir(
     &mut self,
-    buffer: (&mut String, &mut Vec<DbMigration>),
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
     path: &Path,
   ) -> crate::Result<()> {
-    self.database.create_oapth_tables().await?;
-    self.do_migrate_from_dir(buffer, path).await
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      self.do_migrate_from_dir((buffer_cmd, buffer_db_migrations), path).await
+    }
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 
   /// Applies `migrate` to a set of migration groups according to the configuration file.
@@ -73,12 +85,17 @@ where
     DBS: AsRef<[DatabaseTy]>,
     S: AsRef<str>,
   {
-    self.database.create_oapth_tables().await?;
-    for (mg, m) in groups {
-      self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
-      self.do_migrate((buffer_cmd, buffer_db_migrations), mg, m.iter()).await?;
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      for (mg, m) in groups {
+        self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+        self.do_migrate((buffer_cmd, buffer_db_migrations), mg, m.iter()).await?;
+      }
+      Ok(())
     }
-    Ok(())
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 
   /// Applies `migrate` to the set of provided migration groups paths.
@@ -89,12 +106,17 @@ where
     (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
     migration_groups: &[PathBuf],
   ) -> crate::Result<()> {
-    self.database.create_oapth_tables().await?;
     crate::sm::utils::is_sorted_and_unique(migration_groups)?;
-    for mg in migration_groups {
-      self.do_migrate_from_dir((buffer_cmd, buffer_db_migrations), mg).await?;
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      for mg in migration_groups {
+        self.do_migrate_from_dir((buffer_cmd, buffer_db_migrations), mg).await?;
+      }
+      Ok(())
     }
-    Ok(())
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 
   #[inline]
@@ -109,18 +131,31 @@ where
     I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
     S: AsRef<str> + 'migration,
   {
-    let filtered_by_db = Self::filter_by_db(user_migrations);
-    Self::do_validate(buffer_db_migrations, filtered_by_db.clone())?;
-    if let Some(last_db_mig_version) = buffer_db_migrations.last().map(DbMigration::version) {
-      let to_apply = filtered_by_db.filter(move |e| e.version() > last_db_mig_version);
-      self.database.insert_migrations(buffer_cmd, mg, to_apply).await?;
-    } else {
-      self.database.insert_migrations(buffer_cmd, mg, filtered_by_db).await?;
-    }
+    let to_apply = Self::pending_migrations(buffer_db_migrations, user_migrations.clone())?;
+    self.database.insert_migrations(buffer_cmd, mg, to_apply, self.transaction_mode).await?;
+    let db_migrations = &*buffer_db_migrations;
+    let to_rerun = Self::filter_by_db(user_migrations).filter(|m| needs_rerun(db_migrations, m));
+    self.database.update_migrations(buffer_cmd, mg, to_rerun, self.transaction_mode).await?;
     buffer_db_migrations.clear();
     Ok(())
   }
 
+  #[inline]
+  pub(crate) fn pending_migrations<'migration, DBS, I, S>(
+    db_migrations: &[DbMigration],
+    user_migrations: I,
+  ) -> crate::Result<impl Clone + Iterator<Item = &'migration UserMigration<DBS, S>>>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    let filtered_by_db = Self::filter_by_db(user_migrations);
+    Self::do_validate(db_migrations, filtered_by_db.clone())?;
+    let last_db_mig_version = db_migrations.last().map(DbMigration::version);
+    Ok(filtered_by_db.filter(move |e| last_db_mig_version.map_or(true, |el| e.version() > el)))
+  }
+
   #[cfg(feature = "std")]
   #[inline]
   async fn do_migrate_from_dir(
@@ -0,0 +1,132 @@
+use crate::{
+  sm::{
+    commands::MigrationFromGroups, utils::needs_rerun, Commands, DbMigration, MigrationGroup,
+    MigrationPlan, SchemaManagement, UserMigration, UserMigrationOwned,
+  },
+  DatabaseTy,
+};
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use {
+  crate::sm::utils::{group_and_migrations_from_path, parse_root_toml},
+  std::path::{Path, PathBuf},
+};
+
+impl<D> Commands<D>
+where
+  D: SchemaManagement,
+{
+  /// Reports everything that `migrate` would apply to a group, including repeatable migrations
+  /// that would be executed again, without actually executing any migration.
+  ///
+  /// Provided migrations are validated against the ones stored in the database and nothing is
+  /// modified. Databases without the internal oapth tables are reported as having nothing
+  /// applied.
+  #[inline]
+  pub async fn plan<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationPlan>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    buffer_db_migrations.clear();
+    if self.database.has_oapth_tables().await? {
+      self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+    }
+    Self::do_plan(buffer_db_migrations, mg, user_migrations)
+  }
+
+  /// Applies `plan` to a set of migrations according to a given set of groups
+  #[inline]
+  pub async fn plan_from_groups<DBS, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    groups: MigrationFromGroups<'_, '_, '_, DBS, S>,
+    results: &mut Vec<MigrationPlan>,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]>,
+    S: AsRef<str>,
+  {
+    let has_oapth_tables = self.database.has_oapth_tables().await?;
+    for (mg, m) in groups {
+      if has_oapth_tables {
+        self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+      }
+      results.push(Self::do_plan(buffer_db_migrations, mg, m.iter())?);
+    }
+    Ok(())
+  }
+
+  /// Applies `plan` to a set of migration groups according to the configuration file.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub async fn plan_from_toml_path(
+    &mut self,
+    buffer: (&mut String, &mut Vec<DbMigration>),
+    path: &Path,
+    results: &mut Vec<MigrationPlan>,
+  ) -> crate::Result<()> {
+    let (mut migration_groups, _) = parse_root_toml(path)?;
+    migration_groups.sort_unstable();
+    self.plan_from_groups_paths(buffer, &migration_groups, results).await
+  }
+
+  /// Applies `plan` to the set of provided migration groups paths.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub async fn plan_from_groups_paths(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    migration_groups: &[PathBuf],
+    results: &mut Vec<MigrationPlan>,
+  ) -> crate::Result<()> {
+    let has_oapth_tables = self.database.has_oapth_tables().await?;
+    crate::sm::utils::is_sorted_and_unique(migration_groups)?;
+    for path in migration_groups {
+      let (mg, migrations) = group_and_migrations_from_path(path, Ord::cmp)?;
+      let user_migrations = migrations.collect::<crate::Result<Vec<_>>>()?;
+      if has_oapth_tables {
+        self.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await?;
+      }
+      results.push(Self::do_plan(buffer_db_migrations, &mg, user_migrations.iter())?);
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn do_plan<'migration, DBS, I, S>(
+    buffer_db_migrations: &mut Vec<DbMigration>,
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationPlan>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    let mut migrations = Vec::new();
+    let db_migrations = &*buffer_db_migrations;
+    let to_apply = Self::pending_migrations(db_migrations, user_migrations.clone())?;
+    let to_rerun = Self::filter_by_db(user_migrations).filter(|m| needs_rerun(db_migrations, m));
+    for migration in to_apply.chain(to_rerun) {
+      migrations.push(UserMigrationOwned::from_all_parts(
+        migration.checksum(),
+        migration.dbs().try_into()?,
+        migration.name().into(),
+        migration.repeatability(),
+        migration.sql_down().into(),
+        migration.sql_up().into(),
+        migration.transactional(),
+        migration.version(),
+      ));
+    }
+    buffer_db_migrations.clear();
+    Ok(MigrationPlan::new(MigrationGroup::new(mg.name().into(), mg.version()), migrations))
+  }
+}
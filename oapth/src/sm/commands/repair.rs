@@ -0,0 +1,243 @@
+use crate::{
+  sm::{
+    commands::MigrationFromGroups, Commands, DbMigration, MigrationGroup, MigrationRepair,
+    MigrationRepairEntry, SchemaManagement, UserMigration,
+  },
+  DatabaseTy,
+};
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use {
+  crate::sm::utils::{group_and_migrations_from_path, parse_root_toml},
+  std::path::Path,
+};
+
+impl<D> Commands<D>
+where
+  D: SchemaManagement,
+{
+  /// Rewrites the names and checksums of stored migrations that diverge from the provided
+  /// migrations and removes records left behind by failed executions.
+  ///
+  /// Checksums of repeatable migrations are kept because they determine future executions.
+  /// Stored migrations without a local counterpart are not modified.
+  #[inline]
+  pub async fn repair<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationRepair>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    buffer_db_migrations.clear();
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt = async {
+      self.database.create_oapth_tables().await?;
+      self.do_repair((buffer_cmd, buffer_db_migrations), mg, user_migrations).await
+    }
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
+  }
+
+  /// Reports everything that `repair` would realign or remove in a group without modifying any
+  /// stored migration.
+  #[inline]
+  pub async fn repair_preview<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationRepair>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    buffer_db_migrations.clear();
+    self.database.create_oapth_tables().await?;
+    self.do_repair_preview((buffer_cmd, buffer_db_migrations), mg, user_migrations).await
+  }
+
+  /// Applies `repair_preview` to a set of migrations according to a given set of groups
+  #[inline]
+  pub async fn repair_preview_from_groups<DBS, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    groups: MigrationFromGroups<'_, '_, '_, DBS, S>,
+    results: &mut Vec<MigrationRepair>,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]>,
+    S: AsRef<str>,
+  {
+    self.database.create_oapth_tables().await?;
+    for (mg, m) in groups {
+      results.push(self.do_repair_preview((buffer_cmd, buffer_db_migrations), mg, m.iter()).await?);
+    }
+    Ok(())
+  }
+
+  /// Applies `repair_preview` to a set of migration groups according to the configuration file.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub async fn repair_preview_from_toml_path(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    path: &Path,
+    results: &mut Vec<MigrationRepair>,
+  ) -> crate::Result<()> {
+    let (mut migration_groups, _) = parse_root_toml(path)?;
+    migration_groups.sort_unstable();
+    self.database.create_oapth_tables().await?;
+    for mg_path in migration_groups {
+      let (mg, migrations) = group_and_migrations_from_path(&mg_path, Ord::cmp)?;
+      let user_migrations = migrations.collect::<crate::Result<Vec<_>>>()?;
+      let repair = self
+        .do_repair_preview((buffer_cmd, buffer_db_migrations), &mg, user_migrations.iter())
+        .await?;
+      results.push(repair);
+    }
+    Ok(())
+  }
+
+  /// Applies `repair` to a set of migrations according to a given set of groups
+  #[inline]
+  pub async fn repair_from_groups<DBS, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    groups: MigrationFromGroups<'_, '_, '_, DBS, S>,
+    results: &mut Vec<MigrationRepair>,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]>,
+    S: AsRef<str>,
+  {
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      for (mg, m) in groups {
+        results.push(self.do_repair((buffer_cmd, buffer_db_migrations), mg, m.iter()).await?);
+      }
+      Ok(())
+    }
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
+  }
+
+  /// Applies `repair` to a set of migration groups according to the configuration file.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub async fn repair_from_toml_path(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    path: &Path,
+    results: &mut Vec<MigrationRepair>,
+  ) -> crate::Result<()> {
+    let (mut migration_groups, _) = parse_root_toml(path)?;
+    migration_groups.sort_unstable();
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      self.database.create_oapth_tables().await?;
+      for mg_path in migration_groups {
+        let (mg, migrations) = group_and_migrations_from_path(&mg_path, Ord::cmp)?;
+        let user_migrations = migrations.collect::<crate::Result<Vec<_>>>()?;
+        let repair =
+          self.do_repair((buffer_cmd, buffer_db_migrations), &mg, user_migrations.iter()).await?;
+        results.push(repair);
+      }
+      Ok(())
+    }
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
+  }
+
+  #[inline]
+  async fn do_repair<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationRepair>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    let entries =
+      self.repair_entries((buffer_cmd, buffer_db_migrations), mg, user_migrations).await?;
+    let deleted_failed = self.database.repair_migrations(buffer_cmd, mg, &entries).await?;
+    Ok(MigrationRepair::new(
+      deleted_failed,
+      entries,
+      MigrationGroup::new(mg.name().into(), mg.version()),
+    ))
+  }
+
+  #[inline]
+  async fn do_repair_preview<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationRepair>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    let entries =
+      self.repair_entries((buffer_cmd, buffer_db_migrations), mg, user_migrations).await?;
+    let deleted_failed = self.database.failed_migrations_len(buffer_cmd, mg).await?;
+    Ok(MigrationRepair::new(
+      deleted_failed,
+      entries,
+      MigrationGroup::new(mg.name().into(), mg.version()),
+    ))
+  }
+
+  // Stored migrations whose names or checksums diverge from the provided migrations.
+  #[inline]
+  async fn repair_entries<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<Vec<MigrationRepairEntry>>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+    let mut entries = Vec::new();
+    for migration in Self::filter_by_db(user_migrations) {
+      let Ok(idx) =
+        buffer_db_migrations.binary_search_by(|m| m.version().cmp(&migration.version()))
+      else {
+        continue;
+      };
+      let Some(db_migration) = buffer_db_migrations.get(idx) else {
+        continue;
+      };
+      let new_checksum = if migration.repeatability().is_some() {
+        db_migration.checksum()
+      } else {
+        migration.checksum()
+      };
+      if db_migration.checksum() == new_checksum && db_migration.name() == migration.name() {
+        continue;
+      }
+      entries.push(MigrationRepairEntry::new(
+        (db_migration.checksum(), new_checksum),
+        (db_migration.name().into(), migration.name().into()),
+        migration.version(),
+      ));
+    }
+    buffer_db_migrations.clear();
+    Ok(entries)
+  }
+}
@@ -15,7 +15,8 @@ where
 {
   /// Rollbacks the migrations of a group to a given `version`.
   ///
-  /// Before issuing a rollback, all migrations are validated.
+  /// Before issuing a rollback, all migrations are validated. The migration lock is held during
+  /// the whole operation, see [SchemaManagement::lock].
   #[inline]
   pub async fn rollback<'migration, DBS, I, S>(
     &mut self,
@@ -29,17 +30,9 @@ where
     I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
     S: AsRef<str> + 'migration,
   {
-    self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
-    let filtered_by_db = Self::filter_by_db(migrations);
-    Self::do_validate(buffer_db_migrations, filtered_by_db.clone())?;
-    for elem in filtered_by_db.map(UserMigration::sql_down) {
-      buffer_cmd.push_str(elem.as_ref());
-    }
-    self.database.transaction(buffer_cmd).await?;
-    buffer_cmd.clear();
-    self.database.delete_migrations(buffer_cmd, mg, version).await?;
-    buffer_db_migrations.clear();
-    Ok(())
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt = self.do_rollback((buffer_cmd, buffer_db_migrations), mg, migrations, version).await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 
   /// Applies `rollback` to a set of groups according to the configuration file
@@ -56,10 +49,15 @@ where
       return Err(crate::Error::DifferentRollbackVersions);
     }
     migration_groups.sort_by(|a, b| b.cmp(a));
-    for (mg, &version) in migration_groups.into_iter().zip(versions) {
-      self.do_rollback_from_dir((buffer_cmd, buffer_db_migrations), &mg, version).await?;
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt: crate::Result<()> = async {
+      for (mg, &version) in migration_groups.into_iter().zip(versions) {
+        self.do_rollback_from_dir((buffer_cmd, buffer_db_migrations), &mg, version).await?;
+      }
+      Ok(())
     }
-    Ok(())
+    .await;
+    self.release_lock(buffer_cmd, rslt).await
   }
 
   /// Applies `rollback` to a set of migrations according to a given directory
@@ -67,11 +65,39 @@ where
   #[cfg(feature = "std")]
   pub async fn rollback_from_dir(
     &mut self,
-    buffer: (&mut String, &mut Vec<DbMigration>),
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
     path: &Path,
     version: i32,
   ) -> crate::Result<()> {
-    self.do_rollback_from_dir(buffer, path, version).await
+    self.acquire_lock(buffer_cmd).await?;
+    let rslt = self.do_rollback_from_dir((buffer_cmd, buffer_db_migrations), path, version).await;
+    self.release_lock(buffer_cmd, rslt).await
+  }
+
+  #[inline]
+  async fn do_rollback<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    migrations: I,
+    version: i32,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+    let filtered_by_db = Self::filter_by_db(migrations);
+    Self::do_validate(buffer_db_migrations, filtered_by_db.clone())?;
+    for elem in filtered_by_db.map(UserMigration::sql_down) {
+      buffer_cmd.push_str(elem.as_ref());
+    }
+    self.database.transaction(buffer_cmd).await?;
+    buffer_cmd.clear();
+    self.database.delete_migrations(buffer_cmd, mg, version).await?;
+    buffer_db_migrations.clear();
+    Ok(())
   }
 
   #[inline]
@@ -90,7 +116,7 @@ where
       migrations,
       self.batch_size(),
       self
-        .rollback((buffer_cmd, buffer_db_migrations), &mg, tmp_migrations.iter(), version)
+        .do_rollback((buffer_cmd, buffer_db_migrations), &mg, tmp_migrations.iter(), version)
         .await?
     );
     Ok(())
@@ -0,0 +1,162 @@
+use crate::{
+  sm::{
+    commands::MigrationFromGroups, utils::is_rerun_needed, Commands, DbMigration, MigrationGroup,
+    MigrationStatus, MigrationStatusEntry, MigrationStatusReport, SchemaManagement, UserMigration,
+  },
+  DatabaseTy,
+};
+use alloc::{string::String, vec::Vec};
+use core::cmp::Ordering;
+#[cfg(feature = "std")]
+use {
+  crate::sm::utils::{group_and_migrations_from_path, parse_root_toml},
+  std::path::Path,
+};
+
+impl<D> Commands<D>
+where
+  D: SchemaManagement,
+{
+  /// Compares every migration of a group stored in the database with the provided migrations
+  /// and reports the status of each one of them.
+  ///
+  /// Unlike `validate`, divergences are not returned as errors and nothing is modified.
+  /// Databases without the internal oapth tables are reported as having nothing applied.
+  #[inline]
+  pub async fn status<'migration, DBS, I, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> crate::Result<MigrationStatusReport>
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    buffer_db_migrations.clear();
+    if self.database.has_oapth_tables().await? {
+      self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+    }
+    Ok(Self::do_status(buffer_db_migrations, mg, user_migrations))
+  }
+
+  /// Applies `status` to a set of migrations according to a given set of groups
+  #[inline]
+  pub async fn status_from_groups<DBS, S>(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    groups: MigrationFromGroups<'_, '_, '_, DBS, S>,
+    results: &mut Vec<MigrationStatusReport>,
+  ) -> crate::Result<()>
+  where
+    DBS: AsRef<[DatabaseTy]>,
+    S: AsRef<str>,
+  {
+    let has_oapth_tables = self.database.has_oapth_tables().await?;
+    for (mg, m) in groups {
+      if has_oapth_tables {
+        self.database.migrations(buffer_cmd, mg, buffer_db_migrations).await?;
+      }
+      results.push(Self::do_status(buffer_db_migrations, mg, m.iter()));
+    }
+    Ok(())
+  }
+
+  /// Applies `status` to a set of migration groups according to the configuration file.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub async fn status_from_toml_path(
+    &mut self,
+    (buffer_cmd, buffer_db_migrations): (&mut String, &mut Vec<DbMigration>),
+    path: &Path,
+    results: &mut Vec<MigrationStatusReport>,
+  ) -> crate::Result<()> {
+    let (mut migration_groups, _) = parse_root_toml(path)?;
+    migration_groups.sort_unstable();
+    let has_oapth_tables = self.database.has_oapth_tables().await?;
+    for mg_path in migration_groups {
+      let (mg, migrations) = group_and_migrations_from_path(&mg_path, Ord::cmp)?;
+      let user_migrations = migrations.collect::<crate::Result<Vec<_>>>()?;
+      if has_oapth_tables {
+        self.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await?;
+      }
+      results.push(Self::do_status(buffer_db_migrations, &mg, user_migrations.iter()));
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn do_status<'migration, DBS, I, S>(
+    buffer_db_migrations: &mut Vec<DbMigration>,
+    mg: &MigrationGroup<S>,
+    user_migrations: I,
+  ) -> MigrationStatusReport
+  where
+    DBS: AsRef<[DatabaseTy]> + 'migration,
+    I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+    S: AsRef<str> + 'migration,
+  {
+    let mut entries = Vec::new();
+    let mut db_iter = buffer_db_migrations.iter().peekable();
+    let mut user_iter = Self::filter_by_db(user_migrations).peekable();
+    loop {
+      let ordering = match (db_iter.peek(), user_iter.peek()) {
+        (None, None) => break,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(db), Some(user)) => db.version().cmp(&user.version()),
+      };
+      match (ordering, db_iter.peek().copied(), user_iter.peek().copied()) {
+        (Ordering::Equal, Some(db), Some(user)) => {
+          entries.push(MigrationStatusEntry::new(
+            Some(*db.created_on()),
+            user.name().into(),
+            Self::applied_status(db, user),
+            user.version(),
+          ));
+          let _ = db_iter.next();
+          let _ = user_iter.next();
+        }
+        (Ordering::Greater, _, Some(user)) => {
+          entries.push(MigrationStatusEntry::new(
+            None,
+            user.name().into(),
+            MigrationStatus::Pending,
+            user.version(),
+          ));
+          let _ = user_iter.next();
+        }
+        (_, Some(db), _) => {
+          entries.push(MigrationStatusEntry::new(
+            Some(*db.created_on()),
+            db.name().into(),
+            MigrationStatus::MissingFromDisk,
+            db.version(),
+          ));
+          let _ = db_iter.next();
+        }
+        _ => break,
+      }
+    }
+    buffer_db_migrations.clear();
+    MigrationStatusReport::new(entries, MigrationGroup::new(mg.name().into(), mg.version()))
+  }
+
+  #[inline]
+  fn applied_status<DBS, S>(db: &DbMigration, user: &UserMigration<DBS, S>) -> MigrationStatus
+  where
+    DBS: AsRef<[DatabaseTy]>,
+    S: AsRef<str>,
+  {
+    if db.name() != user.name() {
+      MigrationStatus::NameMismatch
+    } else if is_rerun_needed(db, user) {
+      MigrationStatus::RepeatableRerunNeeded
+    } else if user.repeatability().is_none() && db.checksum() != user.checksum() {
+      MigrationStatus::ChecksumMismatch
+    } else {
+      MigrationStatus::Applied
+    }
+  }
+}
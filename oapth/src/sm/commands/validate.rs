@@ -1,7 +1,7 @@
 use crate::{
   sm::{
-    utils::is_migration_divergent, Commands, DbMigration, MigrationGroup, Repeatability,
-    SchemaManagement, UserMigration,
+    utils::is_migration_divergent, Commands, DbMigration, MigrationGroup, SchemaManagement,
+    UserMigration,
   },
   DatabaseTy,
 };
@@ -75,13 +75,8 @@ where
   {
     let mut migrations_len: usize = 0;
     for migration in migrations {
-      match migration.repeatability() {
-        Some(Repeatability::Always) => {}
-        _ => {
-          if is_migration_divergent(db_migrations, migration) {
-            return Err(crate::Error::ValidationDivergentMigrations(migration.version()));
-          }
-        }
+      if is_migration_divergent(db_migrations, migration) {
+        return Err(crate::Error::ValidationDivergentMigrations(migration.version()));
       }
       migrations_len = migrations_len.saturating_add(1);
     }
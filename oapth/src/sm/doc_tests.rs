@@ -18,6 +18,7 @@ use crate::sm::{MigrationGroup, UserMigrationRef};
 ///     "CREATE TABLE author (id INT NOT NULL PRIMARY KEY, name VARCHAR(50) NOT NULL)",
 ///     "DROP TABLE author",
 ///   ],
+///   true,
 ///   1
 /// );
 /// ```
@@ -31,6 +32,7 @@ pub fn migration() -> UserMigrationRef<'static, 'static> {
       "CREATE TABLE author (id INT NOT NULL PRIMARY KEY, name VARCHAR(50) NOT NULL)",
       "DROP TABLE author",
     ],
+    true,
     1,
   )
   .unwrap()
@@ -4,9 +4,13 @@
 macro_rules! oapth_migration_columns {
   () => {
     "_oapth_migration_omg_version INT NOT NULL, \
+    applied_by VARCHAR(256) NULL, \
     checksum VARCHAR(20) NOT NULL, \
+    duration_ms BIGINT NULL, \
     name VARCHAR(128) NOT NULL, \
+    oapth_version VARCHAR(32) NULL, \
     repeatability INTEGER NULL, \
+    success INT NOT NULL DEFAULT 1, \
     version INT NOT NULL, \
     CONSTRAINT _oapth_migration_unq UNIQUE (version, _oapth_migration_omg_version)"
   };
@@ -32,12 +36,38 @@ pub(crate) mod sqlite;
 
 use crate::{
   database::Database,
-  sm::{MigrationGroup, UserMigration},
+  sm::{MigrationGroup, MigrationRepairEntry, Repeatability, TransactionMode, UserMigration},
   DatabaseTy,
 };
 use alloc::{string::String, vec::Vec};
 use core::fmt::Write;
 
+const _OAPTH_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Records are inserted as if migrations were successfully executed.
+#[inline]
+pub(crate) async fn _baseline_migrations<'migration, D, DBS, I, S>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  mg: &MigrationGroup<S>,
+  migrations: I,
+  schema_prefix: &str,
+) -> crate::Result<()>
+where
+  D: Database,
+  DBS: AsRef<[DatabaseTy]> + 'migration,
+  I: Iterator<Item = &'migration UserMigration<DBS, S>>,
+  S: AsRef<str> + 'migration,
+{
+  _push_migration_group(buffer_cmd, mg, schema_prefix)?;
+  for migration in migrations {
+    _push_record(buffer_cmd, D::TY, mg, migration, true, schema_prefix, true)?;
+  }
+  db.transaction(&*buffer_cmd).await?;
+  buffer_cmd.clear();
+  Ok(())
+}
+
 #[inline]
 pub(crate) async fn _delete_migrations<D, S>(
   buffer_cmd: &mut String,
@@ -68,6 +98,7 @@ pub(crate) async fn _insert_migrations<'migration, D, DBS, I, S>(
   mg: &MigrationGroup<S>,
   migrations: I,
   schema_prefix: &str,
+  transaction_mode: TransactionMode,
 ) -> crate::Result<()>
 where
   D: Database,
@@ -75,43 +106,10 @@ where
   I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
   S: AsRef<str> + 'migration,
 {
-  buffer_cmd.write_fmt(format_args!(
-    "INSERT INTO {schema_prefix}_oapth_migration_group (version, name)
-    SELECT * FROM (SELECT {mg_version} AS version, '{mg_name}' AS name) AS tmp
-    WHERE NOT EXISTS (
-      SELECT 1 FROM {schema_prefix}_oapth_migration_group WHERE version = {mg_version}
-    );",
-    mg_name = mg.name(),
-    mg_version = mg.version(),
-    schema_prefix = schema_prefix
-  ))?;
+  _push_migration_group(buffer_cmd, mg, schema_prefix)?;
   db.execute(&*buffer_cmd).await?;
   buffer_cmd.clear();
-
-  for migration in migrations.clone() {
-    buffer_cmd.push_str(migration.sql_up());
-  }
-  db.transaction(&*buffer_cmd).await?;
-  buffer_cmd.clear();
-
-  for migration in migrations {
-    buffer_cmd.write_fmt(format_args!(
-      "INSERT INTO {schema_prefix}_oapth_migration (
-        version, _oapth_migration_omg_version, checksum, name
-      ) VALUES (
-        {m_version}, {mg_version}, '{m_checksum}', '{m_name}'
-      );",
-      m_checksum = migration.checksum(),
-      m_name = migration.name(),
-      m_version = migration.version(),
-      mg_version = mg.version(),
-      schema_prefix = schema_prefix,
-    ))?;
-  }
-  db.transaction(&*buffer_cmd).await?;
-  buffer_cmd.clear();
-
-  Ok(())
+  _execute_migrations(buffer_cmd, db, mg, migrations, true, schema_prefix, transaction_mode).await
 }
 
 #[inline]
@@ -132,16 +130,20 @@ where
       _oapth_migration.version, \
       _oapth_migration_group.version as omg_version, \
       _oapth_migration_group.name as omg_name, \
+      _oapth_migration.applied_by, \
       _oapth_migration.checksum, \
       _oapth_migration.created_on, \
+      _oapth_migration.duration_ms, \
       _oapth_migration.name, \
-      _oapth_migration.repeatability \
+      _oapth_migration.oapth_version, \
+      _oapth_migration.repeatability, \
+      _oapth_migration.success \
     FROM \
       {schema_prefix}_oapth_migration_group \
     JOIN \
       {schema_prefix}_oapth_migration ON _oapth_migration._oapth_migration_omg_version = _oapth_migration_group.version \
     WHERE \
-      _oapth_migration_group.version = {mg_version} \
+      _oapth_migration_group.version = {mg_version} AND _oapth_migration.success = 1 \
     ORDER BY \
       _oapth_migration.version ASC;",
   )).map_err(From::from)?;
@@ -149,3 +151,311 @@ where
   buffer_cmd.clear();
   Ok(())
 }
+
+#[inline]
+pub(crate) async fn _failed_migrations_len<D, S>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  mg: &MigrationGroup<S>,
+  schema_prefix: &str,
+) -> crate::Result<u64>
+where
+  D: Database,
+  S: AsRef<str>,
+{
+  buffer_cmd.write_fmt(format_args!(
+    "SELECT version FROM {schema_prefix}_oapth_migration \
+    WHERE _oapth_migration_omg_version = {mg_version} AND success = 0",
+    mg_version = mg.version(),
+  ))?;
+  let mut len: u64 = 0;
+  db.rows(&*buffer_cmd, |_| {
+    len = len.wrapping_add(1);
+    Ok::<_, crate::Error>(())
+  })
+  .await?;
+  buffer_cmd.clear();
+  Ok(len)
+}
+
+#[inline]
+pub(crate) async fn _repair_migrations<D, S>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  mg: &MigrationGroup<S>,
+  entries: &[MigrationRepairEntry],
+  schema_prefix: &str,
+) -> crate::Result<u64>
+where
+  D: Database,
+  S: AsRef<str>,
+{
+  let deleted_failed = _failed_migrations_len(buffer_cmd, db, mg, schema_prefix).await?;
+  buffer_cmd.write_fmt(format_args!(
+    "DELETE FROM {schema_prefix}_oapth_migration \
+    WHERE _oapth_migration_omg_version = {mg_version} AND success = 0;",
+    mg_version = mg.version(),
+  ))?;
+  for entry in entries {
+    buffer_cmd.write_fmt(format_args!(
+      "UPDATE {schema_prefix}_oapth_migration SET checksum = '{checksum}', name = '{name}' \
+      WHERE _oapth_migration_omg_version = {mg_version} AND version = {version};",
+      checksum = entry.new_checksum(),
+      mg_version = mg.version(),
+      name = entry.new_name(),
+      version = entry.version(),
+    ))?;
+  }
+  db.transaction(&*buffer_cmd).await?;
+  buffer_cmd.clear();
+  Ok(deleted_failed)
+}
+
+#[inline]
+pub(crate) async fn _update_migrations<'migration, D, DBS, I, S>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  mg: &MigrationGroup<S>,
+  migrations: I,
+  schema_prefix: &str,
+  transaction_mode: TransactionMode,
+) -> crate::Result<()>
+where
+  D: Database,
+  DBS: AsRef<[DatabaseTy]> + 'migration,
+  I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+  S: AsRef<str> + 'migration,
+{
+  _execute_migrations(buffer_cmd, db, mg, migrations, false, schema_prefix, transaction_mode).await
+}
+
+// SQL expression that identifies who is applying migrations.
+#[inline]
+fn _applied_by(db_ty: DatabaseTy) -> &'static str {
+  match db_ty {
+    DatabaseTy::Mssql => "CONCAT(SUSER_SNAME(), '@', HOST_NAME())",
+    DatabaseTy::MySql => "USER()",
+    DatabaseTy::Postgres => {
+      "CONCAT(current_user, '@', COALESCE(HOST(inet_client_addr()), 'localhost'))"
+    }
+    DatabaseTy::Sqlite | DatabaseTy::Unit => "NULL",
+  }
+}
+
+// Non transactional migrations are always executed alone because some databases, like
+// PostgreSQL, implicitly wrap multi-statement commands into a transaction.
+//
+// Durations are only known after the commit of each migration and are therefore stored in a
+// subsequent statement. Failures are only recorded for new migrations executed in
+// `TransactionMode::PerMigration`.
+#[inline]
+async fn _execute_migrations<'migration, D, DBS, I, S>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  mg: &MigrationGroup<S>,
+  migrations: I,
+  is_new: bool,
+  schema_prefix: &str,
+  transaction_mode: TransactionMode,
+) -> crate::Result<()>
+where
+  D: Database,
+  DBS: AsRef<[DatabaseTy]> + 'migration,
+  I: Clone + Iterator<Item = &'migration UserMigration<DBS, S>>,
+  S: AsRef<str> + 'migration,
+{
+  match transaction_mode {
+    TransactionMode::Batch => {
+      for migration in migrations.clone() {
+        if migration.transactional() {
+          _push_sql_up(buffer_cmd, migration);
+        } else {
+          if !buffer_cmd.is_empty() {
+            db.transaction(&*buffer_cmd).await?;
+            buffer_cmd.clear();
+          }
+          db.execute(migration.sql_up()).await?;
+        }
+      }
+      db.transaction(&*buffer_cmd).await?;
+      buffer_cmd.clear();
+
+      for migration in migrations {
+        _push_record(buffer_cmd, D::TY, mg, migration, is_new, schema_prefix, true)?;
+      }
+      db.transaction(&*buffer_cmd).await?;
+      buffer_cmd.clear();
+    }
+    TransactionMode::PerMigration => {
+      for migration in migrations {
+        #[cfg(feature = "std")]
+        let instant = std::time::Instant::now();
+        let rslt = if migration.transactional() {
+          _push_sql_up(buffer_cmd, migration);
+          _push_record(buffer_cmd, D::TY, mg, migration, is_new, schema_prefix, true)?;
+          db.transaction(&*buffer_cmd).await
+        } else {
+          match db.execute(migration.sql_up()).await {
+            Err(err) => Err(err),
+            Ok(()) => {
+              _push_record(buffer_cmd, D::TY, mg, migration, is_new, schema_prefix, true)?;
+              db.execute(&*buffer_cmd).await
+            }
+          }
+        };
+        buffer_cmd.clear();
+        if rslt.is_err() && is_new {
+          _push_record(buffer_cmd, D::TY, mg, migration, is_new, schema_prefix, false)?;
+          // The original error is more relevant than a possible bookkeeping error
+          let _rslt = db.execute(&*buffer_cmd).await;
+          buffer_cmd.clear();
+        }
+        rslt?;
+        #[cfg(feature = "std")]
+        {
+          _push_duration(buffer_cmd, instant.elapsed().as_millis(), mg, migration, schema_prefix)?;
+          db.execute(&*buffer_cmd).await?;
+          buffer_cmd.clear();
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn _push_duration<DBS, S>(
+  buffer_cmd: &mut String,
+  duration_ms: u128,
+  mg: &MigrationGroup<S>,
+  migration: &UserMigration<DBS, S>,
+  schema_prefix: &str,
+) -> crate::Result<()>
+where
+  DBS: AsRef<[DatabaseTy]>,
+  S: AsRef<str>,
+{
+  buffer_cmd.write_fmt(format_args!(
+    "UPDATE {schema_prefix}_oapth_migration SET duration_ms = {duration_ms} \
+    WHERE _oapth_migration_omg_version = {mg_version} AND version = {m_version};",
+    m_version = migration.version(),
+    mg_version = mg.version(),
+  ))?;
+  Ok(())
+}
+
+#[inline]
+fn _push_migration_group<S>(
+  buffer_cmd: &mut String,
+  mg: &MigrationGroup<S>,
+  schema_prefix: &str,
+) -> crate::Result<()>
+where
+  S: AsRef<str>,
+{
+  buffer_cmd.write_fmt(format_args!(
+    "INSERT INTO {schema_prefix}_oapth_migration_group (version, name)
+    SELECT * FROM (SELECT {mg_version} AS version, '{mg_name}' AS name) AS tmp
+    WHERE NOT EXISTS (
+      SELECT 1 FROM {schema_prefix}_oapth_migration_group WHERE version = {mg_version}
+    );",
+    mg_name = mg.name(),
+    mg_version = mg.version(),
+    schema_prefix = schema_prefix
+  ))?;
+  Ok(())
+}
+
+// Records of failed executions are replaced by new attempts.
+#[inline]
+fn _push_record<DBS, S>(
+  buffer_cmd: &mut String,
+  db_ty: DatabaseTy,
+  mg: &MigrationGroup<S>,
+  migration: &UserMigration<DBS, S>,
+  is_new: bool,
+  schema_prefix: &str,
+  success: bool,
+) -> crate::Result<()>
+where
+  DBS: AsRef<[DatabaseTy]>,
+  S: AsRef<str>,
+{
+  if is_new {
+    buffer_cmd.write_fmt(format_args!(
+      "DELETE FROM {schema_prefix}_oapth_migration \
+      WHERE _oapth_migration_omg_version = {mg_version} AND version = {m_version} AND success = 0;
+      INSERT INTO {schema_prefix}_oapth_migration (
+        version, _oapth_migration_omg_version, applied_by, checksum, name, oapth_version,
+        repeatability, success
+      ) VALUES (
+        {m_version}, {mg_version}, {applied_by}, '{m_checksum}', '{m_name}', '{oapth_version}',
+        {m_repeatability}, {success}
+      );",
+      applied_by = _applied_by(db_ty),
+      m_checksum = migration.checksum(),
+      m_name = migration.name(),
+      m_repeatability = _repeatability_column(migration.repeatability()),
+      m_version = migration.version(),
+      mg_version = mg.version(),
+      oapth_version = _OAPTH_VERSION,
+      success = u8::from(success),
+    ))?;
+  } else {
+    buffer_cmd.write_fmt(format_args!(
+      "UPDATE {schema_prefix}_oapth_migration SET \
+        applied_by = {applied_by}, \
+        checksum = '{m_checksum}', \
+        oapth_version = '{oapth_version}', \
+        repeatability = {m_repeatability}, \
+        success = {success} \
+      WHERE \
+        _oapth_migration_omg_version = {mg_version} AND version = {m_version};",
+      applied_by = _applied_by(db_ty),
+      m_checksum = migration.checksum(),
+      m_repeatability = _repeatability_column(migration.repeatability()),
+      m_version = migration.version(),
+      mg_version = mg.version(),
+      oapth_version = _OAPTH_VERSION,
+      success = u8::from(success),
+    ))?;
+  }
+  Ok(())
+}
+
+// Statements are separated to allow the concatenation of several migrations. Migrations can end
+// with a line comment, as such, the terminator and the following statements are pushed on new
+// lines.
+#[inline]
+pub(crate) fn _push_sql_up<DBS, S>(buffer_cmd: &mut String, migration: &UserMigration<DBS, S>)
+where
+  DBS: AsRef<[DatabaseTy]>,
+  S: AsRef<str>,
+{
+  let sql_up = migration.sql_up().trim_end();
+  buffer_cmd.push_str(sql_up);
+  // Trailing lines that only have comments don't terminate statements
+  let mut code = sql_up;
+  loop {
+    let (rest, last_line) = code.rsplit_once('\n').unwrap_or(("", code));
+    if !last_line.trim_start().starts_with("--") {
+      break;
+    }
+    code = rest.trim_end();
+  }
+  if !code.ends_with(';') {
+    buffer_cmd.push_str("\n;");
+  }
+  buffer_cmd.push('\n');
+}
+
+// Inverse of `db_migration::_from_opt_i32`
+#[inline]
+fn _repeatability_column(repeatability: Option<Repeatability>) -> &'static str {
+  match repeatability {
+    None => "NULL",
+    Some(Repeatability::Always) => "0",
+    Some(Repeatability::OnChecksumChange) => "1",
+  }
+}
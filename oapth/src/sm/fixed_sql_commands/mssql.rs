@@ -1,6 +1,10 @@
-use crate::{database::Database, sm::SchemaManagement, Identifier};
+use crate::{
+  database::Database,
+  sm::{SchemaManagement, _OAPTH},
+  Identifier, Row,
+};
 use alloc::{string::String, vec::Vec};
-use core::fmt::Write;
+use core::{fmt::Write, time::Duration};
 
 pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   "IF (NOT EXISTS (SELECT 1 FROM sys.schemas WHERE name = '_oapth'))
@@ -36,9 +40,46 @@ pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   created_on VARCHAR(32) NOT NULL DEFAULT CONVERT(VARCHAR(32), CURRENT_TIMESTAMP, 120),",
   oapth_migration_columns!(),
   ");
+  END
+
+  IF (NOT EXISTS (
+    SELECT
+      1
+    FROM
+      information_schema.columns
+    WHERE
+      table_name = '_oapth_migration' AND table_schema = '_oapth' AND column_name = 'success'
+  ))
+  BEGIN
+  ALTER TABLE _oapth._oapth_migration ADD
+    applied_by VARCHAR(256) NULL,
+    duration_ms BIGINT NULL,
+    oapth_version VARCHAR(32) NULL,
+    success INT NOT NULL DEFAULT 1;
   END"
 );
 
+#[inline]
+pub(crate) async fn _create_oapth_tables<D>(db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  db.execute(_CREATE_MIGRATION_TABLES).await
+}
+
+#[inline]
+pub(crate) async fn _has_oapth_tables<D>(db: &mut D) -> crate::Result<bool>
+where
+  D: Database,
+{
+  let cmd = "
+    SELECT CAST(COUNT(*) AS BIGINT)
+    FROM information_schema.tables
+    WHERE table_schema = '_oapth' AND table_name = '_oapth_migration'
+  ";
+  Ok(db.row(cmd).await?.i64_from_idx(0)? > 0)
+}
+
 #[inline]
 pub(crate) async fn _clear<D>(
   (buffer_cmd, buffer_idents): (&mut String, &mut Vec<Identifier>),
@@ -84,6 +125,36 @@ where
   Ok(())
 }
 
+#[inline]
+pub(crate) async fn _lock<D>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  timeout: Duration,
+) -> crate::Result<()>
+where
+  D: Database,
+{
+  let timeout_ms = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+  buffer_cmd.write_fmt(format_args!(
+    "
+    DECLARE @rslt INT;
+    EXEC @rslt = sp_getapplock
+      @Resource = '{_OAPTH}',
+      @LockMode = 'Exclusive',
+      @LockOwner = 'Session',
+      @LockTimeout = {timeout_ms};
+    SELECT CAST(@rslt AS BIGINT);
+    "
+  ))?;
+  let rslt = db.row(buffer_cmd).await?.i64_from_idx(0)?;
+  buffer_cmd.clear();
+  if rslt >= 0 {
+    Ok(())
+  } else {
+    Err(crate::Error::LockNotAcquired)
+  }
+}
+
 #[inline]
 pub(crate) async fn _schemas<D>(db: &mut D, results: &mut Vec<Identifier>) -> crate::Result<()>
 where
@@ -135,3 +206,16 @@ where
   buffer_cmd.clear();
   Ok(())
 }
+
+#[inline]
+pub(crate) async fn _unlock<D>(buffer_cmd: &mut String, db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  buffer_cmd.write_fmt(format_args!(
+    "EXEC sp_releaseapplock @Resource = '{_OAPTH}', @LockOwner = 'Session';"
+  ))?;
+  db.execute(buffer_cmd).await?;
+  buffer_cmd.clear();
+  Ok(())
+}
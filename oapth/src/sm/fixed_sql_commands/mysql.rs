@@ -1,6 +1,10 @@
-use crate::{sm::SchemaManagement, Identifier};
+use crate::{
+  database::Database,
+  sm::{SchemaManagement, _OAPTH},
+  Identifier, Row,
+};
 use alloc::{string::String, vec::Vec};
-use core::fmt::Write;
+use core::{fmt::Write, time::Duration};
 
 pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   "CREATE TABLE IF NOT EXISTS _oapth_migration_group (",
@@ -10,9 +14,49 @@ pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   serial_id!(),
   "created_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,",
   oapth_migration_columns!(),
-  ");"
+  ");
+  SET @oapth_upgrade = (
+    SELECT
+      IF(
+        COUNT(*) = 0,
+        'ALTER TABLE _oapth_migration
+          ADD COLUMN applied_by VARCHAR(256) NULL,
+          ADD COLUMN duration_ms BIGINT NULL,
+          ADD COLUMN oapth_version VARCHAR(32) NULL,
+          ADD COLUMN success INT NOT NULL DEFAULT 1',
+        'SELECT 1'
+      )
+    FROM
+      information_schema.columns
+    WHERE
+      table_schema = DATABASE() AND table_name = '_oapth_migration' AND column_name = 'success'
+  );
+  PREPARE oapth_upgrade FROM @oapth_upgrade;
+  EXECUTE oapth_upgrade;
+  DEALLOCATE PREPARE oapth_upgrade;"
 );
 
+#[inline]
+pub(crate) async fn _create_oapth_tables<D>(db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  db.execute(_CREATE_MIGRATION_TABLES).await
+}
+
+#[inline]
+pub(crate) async fn _has_oapth_tables<D>(db: &mut D) -> crate::Result<bool>
+where
+  D: Database,
+{
+  let cmd = "
+    SELECT COUNT(*)
+    FROM information_schema.tables
+    WHERE table_schema = DATABASE() AND table_name = '_oapth_migration'
+  ";
+  Ok(db.row(cmd).await?.i64_from_idx(0)? > 0)
+}
+
 #[inline]
 pub(crate) async fn _clear<D>(
   (buffer_cmd, buffer_idents): (&mut String, &mut Vec<Identifier>),
@@ -33,6 +77,30 @@ where
   Ok(())
 }
 
+// Names of `GET_LOCK` are server-wide so the current database is part of the lock name. Timeouts
+// are rounded up to whole seconds, otherwise sub-second timeouts wouldn't wait at all.
+#[inline]
+pub(crate) async fn _lock<D>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  timeout: Duration,
+) -> crate::Result<()>
+where
+  D: Database,
+{
+  buffer_cmd.write_fmt(format_args!(
+    "SELECT CAST(COALESCE(GET_LOCK(CONCAT('{_OAPTH}.', DATABASE()), {}), 0) AS SIGNED)",
+    timeout.as_secs().wrapping_add(u64::from(timeout.subsec_nanos() > 0))
+  ))?;
+  let is_locked = db.row(buffer_cmd).await?.i64_from_idx(0)? == 1;
+  buffer_cmd.clear();
+  if is_locked {
+    Ok(())
+  } else {
+    Err(crate::Error::LockNotAcquired)
+  }
+}
+
 #[inline]
 pub(crate) async fn _table_names<D>(
   buffer_cmd: &mut String,
@@ -56,3 +124,14 @@ where
   buffer_cmd.clear();
   Ok(())
 }
+
+#[inline]
+pub(crate) async fn _unlock<D>(buffer_cmd: &mut String, db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  buffer_cmd.write_fmt(format_args!("SELECT RELEASE_LOCK(CONCAT('{_OAPTH}.', DATABASE()))"))?;
+  db.execute(buffer_cmd).await?;
+  buffer_cmd.clear();
+  Ok(())
+}
@@ -1,6 +1,11 @@
-use crate::{database::Database, Identifier};
+use crate::{database::Database, Identifier, Row};
 use alloc::{string::String, vec::Vec};
-use core::fmt::Write;
+use core::{fmt::Write, time::Duration};
+
+// "oapth" in ASCII
+const _LOCK_ID: i64 = 0x6f_61_70_74_68;
+// Interval, in milliseconds, between two lock attempts
+const _LOCK_INTERVAL: u128 = 100;
 
 pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   "CREATE SCHEMA IF NOT EXISTS _oapth; \
@@ -11,9 +16,35 @@ pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   serial_id!(),
   "created_on TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,",
   oapth_migration_columns!(),
-  ");"
+  ");
+  ALTER TABLE _oapth._oapth_migration
+    ADD COLUMN IF NOT EXISTS applied_by VARCHAR(256) NULL,
+    ADD COLUMN IF NOT EXISTS duration_ms BIGINT NULL,
+    ADD COLUMN IF NOT EXISTS oapth_version VARCHAR(32) NULL,
+    ADD COLUMN IF NOT EXISTS success INT NOT NULL DEFAULT 1;"
 );
 
+#[inline]
+pub(crate) async fn _create_oapth_tables<D>(db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  db.execute(_CREATE_MIGRATION_TABLES).await
+}
+
+#[inline]
+pub(crate) async fn _has_oapth_tables<D>(db: &mut D) -> crate::Result<bool>
+where
+  D: Database,
+{
+  let cmd = "
+    SELECT COUNT(*)
+    FROM information_schema.tables
+    WHERE table_schema = '_oapth' AND table_name = '_oapth_migration'
+  ";
+  Ok(db.row(cmd).await?.i64_from_idx(0)? > 0)
+}
+
 #[inline]
 pub(crate) async fn _clear<D>(
   (buffer_cmd, buffer_idents): (&mut String, &mut Vec<Identifier>),
@@ -94,6 +125,31 @@ where
   .await
 }
 
+// `pg_advisory_lock` only waits indefinitely or up to `lock_timeout` with a generic error, as
+// such, `pg_try_advisory_lock` is polled with server-side sleeps between attempts.
+#[inline]
+pub(crate) async fn _lock<D>(
+  buffer_cmd: &mut String,
+  db: &mut D,
+  timeout: Duration,
+) -> crate::Result<()>
+where
+  D: Database,
+{
+  buffer_cmd.write_fmt(format_args!("SELECT pg_try_advisory_lock({_LOCK_ID})::INT::BIGINT"))?;
+  for idx in 0..=timeout.as_millis() / _LOCK_INTERVAL {
+    if idx > 0 {
+      db.execute("SELECT pg_sleep(0.1)").await?;
+    }
+    if db.row(buffer_cmd).await?.i64_from_idx(0)? == 1 {
+      buffer_cmd.clear();
+      return Ok(());
+    }
+  }
+  buffer_cmd.clear();
+  Err(crate::Error::LockNotAcquired)
+}
+
 #[inline]
 pub(crate) async fn _pg_proc<D>(
   (buffer_cmd, buffer_idents): (&mut String, &mut Vec<Identifier>),
@@ -227,6 +283,17 @@ where
   Ok(())
 }
 
+#[inline]
+pub(crate) async fn _unlock<D>(buffer_cmd: &mut String, db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  buffer_cmd.write_fmt(format_args!("SELECT pg_advisory_unlock({_LOCK_ID})"))?;
+  db.execute(buffer_cmd).await?;
+  buffer_cmd.clear();
+  Ok(())
+}
+
 #[inline]
 pub(crate) async fn _views<D>(db: &mut D, results: &mut Vec<Identifier>) -> crate::Result<()>
 where
@@ -1,6 +1,8 @@
-use crate::{sm::SchemaManagement, Identifier};
+use crate::{database::Database, sm::SchemaManagement, Identifier, Row};
 use alloc::{string::String, vec::Vec};
-use core::fmt::Write;
+use core::{fmt::Write, time::Duration};
+
+const _LOCK_INTERVAL: Duration = Duration::from_millis(100);
 
 pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   "CREATE TABLE IF NOT EXISTS _oapth_migration_group (",
@@ -12,6 +14,42 @@ pub(crate) const _CREATE_MIGRATION_TABLES: &str = concat!(
   oapth_migration_columns!(),
   ");"
 );
+const _UPGRADE_MIGRATION_TABLES: &str = "
+  ALTER TABLE _oapth_migration ADD COLUMN applied_by VARCHAR(256) NULL;
+  ALTER TABLE _oapth_migration ADD COLUMN duration_ms BIGINT NULL;
+  ALTER TABLE _oapth_migration ADD COLUMN oapth_version VARCHAR(32) NULL;
+  ALTER TABLE _oapth_migration ADD COLUMN success INT NOT NULL DEFAULT 1;
+";
+
+// SQLite doesn't support `ADD COLUMN IF NOT EXISTS` so tables created by older versions are
+// verified beforehand.
+#[inline]
+pub(crate) async fn _create_oapth_tables<D>(db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  db.execute(_CREATE_MIGRATION_TABLES).await?;
+  let row = db
+    .row("SELECT COUNT(*) FROM pragma_table_info('_oapth_migration') WHERE name = 'success'")
+    .await?;
+  if row.i64_from_idx(0)? == 0 {
+    db.transaction(_UPGRADE_MIGRATION_TABLES).await?;
+  }
+  Ok(())
+}
+
+#[inline]
+pub(crate) async fn _has_oapth_tables<D>(db: &mut D) -> crate::Result<bool>
+where
+  D: Database,
+{
+  let cmd = "
+    SELECT COUNT(*)
+    FROM sqlite_master
+    WHERE type = 'table' AND name = '_oapth_migration'
+  ";
+  Ok(db.row(cmd).await?.i64_from_idx(0)? > 0)
+}
 
 #[inline]
 pub(crate) async fn _clear<D>(
@@ -31,6 +69,38 @@ where
   Ok(())
 }
 
+// SQLite doesn't have named locks nor a way to wait on the server side, therefore, the lock is
+// represented by a table whose creation is atomic and attempts are repeated with client-side
+// sleeps between them.
+//
+// A runner that is killed while holding the lock leaves the table behind, which can be removed
+// with `DROP TABLE _oapth_lock` once no other runner is active.
+#[inline]
+pub(crate) async fn _lock<D>(_: &mut String, db: &mut D, timeout: Duration) -> crate::Result<()>
+where
+  D: Database,
+{
+  for idx in 0..=timeout.as_millis() / _LOCK_INTERVAL.as_millis() {
+    if idx > 0 {
+      #[cfg(feature = "sqlx-core")]
+      sqlx_core::rt::sleep(_LOCK_INTERVAL).await;
+    }
+    let Err(err) = db.execute("CREATE TABLE _oapth_lock (id INTEGER NOT NULL PRIMARY KEY)").await
+    else {
+      return Ok(());
+    };
+    let row = db
+      .row("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '_oapth_lock'")
+      .await?;
+    if row.i64_from_idx(0)? == 0 {
+      return Err(err);
+    }
+  }
+  Err(crate::Error::LockNotAcquired)
+}
+
+// The lock table is excluded, otherwise `clear` would drop it and release the lock while still
+// running.
 pub(crate) async fn _table_names<D>(
   _: &mut String,
   db: &mut D,
@@ -41,7 +111,15 @@ where
   D: SchemaManagement,
 {
   db.simple_entities(
-    "SELECT tbl_name generic_column FROM sqlite_master tables WHERE type='table' AND tbl_name NOT LIKE 'sqlite_%';",
+    "SELECT tbl_name generic_column FROM sqlite_master tables WHERE type='table' AND tbl_name NOT LIKE 'sqlite_%' AND tbl_name <> '_oapth_lock';",
     results
   ).await
 }
+
+#[inline]
+pub(crate) async fn _unlock<D>(_: &mut String, db: &mut D) -> crate::Result<()>
+where
+  D: Database,
+{
+  db.execute("DROP TABLE IF EXISTS _oapth_lock").await
+}
@@ -53,6 +53,36 @@ macro_rules! create_integration_tests {
         $($sqlx_mysql),*
       );
 
+      #[cfg(feature = "postgres")]
+      create_integration_test!(
+        {
+          use tokio_util::compat::TokioAsyncWriteCompatExt;
+          let c = crate::Config::with_url_from_default_var().unwrap();
+          let tcp = tokio::net::TcpStream::connect(c.full_host().unwrap()).await.unwrap();
+          crate::database::Postgres::new(&c, tcp.compat_write()).await.unwrap()
+        },
+        (&mut _buffer_cmd, &mut _buffer_db_migrations, &mut _buffer_idents),
+        _pg_schema(),
+        $($sqlx_pg),*
+      );
+
+      #[cfg(all(feature = "pool", feature = "postgres"))]
+      {
+        let c = crate::Config::with_url_from_default_var().unwrap();
+        let _pool = crate::database::Pool::new(
+          crate::database::ConfigManager::<
+            crate::database::Postgres<tokio_util::compat::Compat<tokio::net::TcpStream>>,
+          >::new(&c),
+          c.pool_params().unwrap(),
+        );
+        create_integration_test!(
+          _pool.get().await.unwrap(),
+          (&mut _buffer_cmd, &mut _buffer_db_migrations, &mut _buffer_idents),
+          _pg_schema(),
+          $($sqlx_pg),*
+        );
+      }
+
       #[cfg(feature = "sqlx-postgres")]
       create_integration_test!(
         _create_integration_test_backend!(SqlxPostgres),
@@ -69,6 +99,21 @@ macro_rules! create_integration_tests {
         $($sqlx_sqlite),*
       );
 
+      #[cfg(all(feature = "pool", feature = "sqlx-sqlite"))]
+      {
+        let c = crate::Config::with_url_from_default_var().unwrap();
+        let _pool = crate::database::Pool::new(
+          crate::database::ConfigManager::<crate::database::SqlxSqlite>::new(&c),
+          c.pool_params().unwrap(),
+        );
+        create_integration_test!(
+          _pool.get().await.unwrap(),
+          (&mut _buffer_cmd, &mut _buffer_db_migrations, &mut _buffer_idents),
+          _generic_schema(),
+          $($sqlx_sqlite),*
+        );
+      }
+
       #[cfg(feature = "tiberius")]
       create_integration_test!(
         {
@@ -159,19 +204,48 @@ create_all_integration_tests!(
   // Database
 
   mssql:
-    db::mssql::_clean_drops_all_objs;
+    db::mssql::_clean_drops_all_objs,
+    db::mssql::_failed_migration_batch_is_rolled_back,
+    db::mssql::_lock_is_exclusive,
+    db::mssql::_transaction_aborts_at_the_first_failing_statement,
+    db::mssql::_typed_values_round_trip;
   mysql:
-    db::mysql::_clean_drops_all_objs;
+    db::mysql::_clean_drops_all_objs,
+    db::mysql::_lock_is_exclusive,
+    db::mysql::_typed_values_round_trip;
   postgres:
-    db::postgres::_clean_drops_all_objs;
+    db::postgres::_clean_drops_all_objs,
+    db::postgres::_decimal_is_bound_as_numeric,
+    db::postgres::_lock_is_exclusive,
+    db::postgres::_null_is_bound_to_any_column_ty,
+    db::postgres::_typed_values_round_trip;
   sqlite:
-    db::sqlite::_clean_drops_all_objs;
+    db::sqlite::_clean_drops_all_objs,
+    db::sqlite::_legacy_migration_table_is_upgraded,
+    db::sqlite::_lock_is_exclusive,
+    db::sqlite::_typed_values_round_trip;
 
   // Generic
 
   generic:
     generic::all_tables_returns_the_number_of_tables_of_the_default_schema,
-    generic::rollback_works;
+    generic::baseline_records_migrations_without_executing_them,
+    generic::batch_transaction_mode_records_every_migration,
+    generic::crud_does_not_write_partial_entities,
+    generic::crud_handles_composite_text_keys,
+    generic::crud_only_orders_by_root_columns,
+    generic::crud_writes_entities_and_reports_affected_rows,
+    generic::lock_is_released_after_failed_operations,
+    generic::migrations_record_execution_metadata,
+    generic::parameterized_commands_bind_typed_values,
+    generic::parameterized_commands_bind_values,
+    generic::per_migration_transaction_mode_records_applied_migrations,
+    generic::plan_reports_pending_migrations,
+    generic::repair_realigns_divergent_migrations,
+    generic::repeatable_migrations_are_executed_again,
+    generic::rollback_works,
+    generic::status_reports_every_migration,
+    generic::transactions_are_committed_rolled_back_and_nested;
 
   // Schema
 
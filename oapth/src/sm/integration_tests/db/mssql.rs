@@ -22,3 +22,131 @@ pub(crate) async fn _clean_drops_all_objs<D>(
   assert_eq!(buffer_idents.len(), 0);
   buffer_idents.clear();
 }
+
+pub(crate) async fn _failed_migration_batch_is_rolled_back<D>(
+  (buffer_cmd, buffer_db_migrations, buffer_idents): (
+    &mut String,
+    &mut Vec<crate::sm::DbMigration>,
+    &mut Vec<crate::Identifier>,
+  ),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  let mg = crate::sm::MigrationGroup::new("initial", 1);
+  let migrations = [
+    crate::sm::UserMigrationRef::from_user_parts(
+      &[],
+      "create_foo",
+      None,
+      ["CREATE TABLE foo (id INT PRIMARY KEY)", "DROP TABLE foo"],
+      true,
+      1,
+    )
+    .unwrap(),
+    crate::sm::UserMigrationRef::from_user_parts(
+      &[],
+      "insert_foo",
+      None,
+      [
+        "INSERT INTO foo VALUES (1); INSERT INTO foo VALUES (1); INSERT INTO foo VALUES (2)",
+        "DELETE FROM foo",
+      ],
+      true,
+      2,
+    )
+    .unwrap(),
+  ];
+  c.set_transaction_mode(crate::sm::TransactionMode::Batch);
+  assert!(c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.is_err());
+  buffer_cmd.clear();
+  buffer_db_migrations.clear();
+
+  c.database.table_names(buffer_cmd, buffer_idents, "dbo").await.unwrap();
+  assert_eq!(buffer_idents.len(), 0);
+  buffer_idents.clear();
+}
+
+#[cfg(feature = "tiberius")]
+pub(crate) async fn _lock_is_exclusive<D>(
+  (buffer_cmd, buffer_db_migrations, _): (
+    &mut String,
+    &mut Vec<crate::sm::DbMigration>,
+    &mut Vec<crate::Identifier>,
+  ),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  use crate::sm::SchemaManagement;
+  use tokio_util::compat::TokioAsyncWriteCompatExt;
+  let config = crate::Config::with_url_from_default_var().unwrap();
+  let tcp = tokio::net::TcpStream::connect(config.full_host().unwrap()).await.unwrap();
+  let mut other = crate::database::Tiberius::new(&config, tcp.compat_write()).await.unwrap();
+  other.lock(buffer_cmd, core::time::Duration::ZERO).await.unwrap();
+  c.set_lock_timeout(core::time::Duration::from_millis(200));
+  let mg = crate::sm::doc_tests::migration_group();
+  let migrations = [crate::sm::doc_tests::migration()];
+  let rslt = c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await;
+  assert!(matches!(rslt, Err(crate::Error::LockNotAcquired)));
+  other.unlock(buffer_cmd).await.unwrap();
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+}
+
+pub(crate) async fn _transaction_aborts_at_the_first_failing_statement<D>(
+  _: (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  use crate::Row;
+
+  c.database.execute("CREATE TABLE foo (id INT PRIMARY KEY)").await.unwrap();
+  let cmd = "INSERT INTO foo VALUES (1); INSERT INTO foo VALUES (1); INSERT INTO foo VALUES (2)";
+  assert!(c.database.transaction(cmd).await.is_err());
+  // Nothing was inserted, no transaction was left open and `XACT_ABORT` was restored
+  let cmd = "SELECT COUNT_BIG(*), CAST(@@TRANCOUNT AS BIGINT), CAST(@@OPTIONS & 16384 AS BIGINT) \
+    FROM foo";
+  let row = c.database.row(cmd).await.unwrap();
+  assert_eq!(row.i64_from_idx(0).unwrap(), 0);
+  assert_eq!(row.i64_from_idx(1).unwrap(), 0);
+  assert_eq!(row.i64_from_idx(2).unwrap(), 0);
+
+  c.database.transaction("INSERT INTO foo VALUES (1); INSERT INTO foo VALUES (2)").await.unwrap();
+  let row = c.database.row("SELECT COUNT_BIG(*) FROM foo").await.unwrap();
+  assert_eq!(row.i64_from_idx(0).unwrap(), 2);
+}
+
+pub(crate) async fn _typed_values_round_trip<D>(
+  _: (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+  chrono::DateTime<chrono::Utc>: crate::FromSqlColumn<D::Row>,
+  chrono::NaiveDate: crate::FromSqlColumn<D::Row>,
+  rust_decimal::Decimal: crate::FromSqlColumn<D::Row>,
+{
+  use crate::{database::SqlParam, Row};
+  use chrono::{NaiveDate, TimeZone, Utc};
+  use rust_decimal::Decimal;
+
+  c.database.execute("CREATE TABLE foo (id INT PRIMARY KEY, created_at DATETIMEOFFSET, day DATE, value DECIMAL(20, 2))").await.unwrap();
+  let created_at = Utc.with_ymd_and_hms(2023, 10, 15, 12, 30, 45).unwrap();
+  let day = NaiveDate::from_ymd_opt(2023, 10, 15).unwrap();
+  let value = Decimal::new(-1_234_567, 2);
+  let params = [
+    SqlParam::I32(1),
+    SqlParam::DateTime(created_at),
+    SqlParam::NaiveDate(day),
+    SqlParam::Decimal(value),
+  ];
+  c.database.execute_with("INSERT INTO foo VALUES (@P1, @P2, @P3, @P4)", &params).await.unwrap();
+  let row = c.database.row("SELECT created_at, day, value FROM foo").await.unwrap();
+  assert_eq!(row.value_from_idx::<chrono::DateTime<Utc>>(0).unwrap(), created_at);
+  assert_eq!(row.value_from_idx::<NaiveDate>(1).unwrap(), day);
+  assert_eq!(row.value_from_idx::<Decimal>(2).unwrap(), value);
+}
@@ -22,3 +22,64 @@ pub(crate) async fn _clean_drops_all_objs<D>(
   assert_eq!(buffer_idents.len(), 0);
   buffer_idents.clear();
 }
+
+#[cfg(feature = "sqlx-mysql")]
+pub(crate) async fn _lock_is_exclusive<D>(
+  (buffer_cmd, buffer_db_migrations, _): (
+    &mut String,
+    &mut Vec<crate::sm::DbMigration>,
+    &mut Vec<crate::Identifier>,
+  ),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  use crate::sm::SchemaManagement;
+  let config = crate::Config::with_url_from_default_var().unwrap();
+  let mut other = crate::database::SqlxMysql::new(&config).await.unwrap();
+  other.lock(buffer_cmd, core::time::Duration::ZERO).await.unwrap();
+  c.set_lock_timeout(core::time::Duration::from_millis(200));
+  let mg = crate::sm::doc_tests::migration_group();
+  let migrations = [crate::sm::doc_tests::migration()];
+  let rslt = c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await;
+  assert!(matches!(rslt, Err(crate::Error::LockNotAcquired)));
+  other.unlock(buffer_cmd).await.unwrap();
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+}
+
+pub(crate) async fn _typed_values_round_trip<D>(
+  _: (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+  chrono::DateTime<chrono::Utc>: crate::FromSqlColumn<D::Row>,
+  chrono::NaiveDate: crate::FromSqlColumn<D::Row>,
+  rust_decimal::Decimal: crate::FromSqlColumn<D::Row>,
+{
+  use crate::{database::SqlParam, Row};
+  use chrono::{NaiveDate, TimeZone, Utc};
+  use rust_decimal::Decimal;
+
+  c.database
+    .execute(
+      "CREATE TABLE foo (id INT PRIMARY KEY, created_at DATETIME, day DATE, value DECIMAL(20, 2))",
+    )
+    .await
+    .unwrap();
+  let created_at = Utc.with_ymd_and_hms(2023, 10, 15, 12, 30, 45).unwrap();
+  let day = NaiveDate::from_ymd_opt(2023, 10, 15).unwrap();
+  let value = Decimal::new(-1_234_567, 2);
+  let params = [
+    SqlParam::I32(1),
+    SqlParam::DateTime(created_at),
+    SqlParam::NaiveDate(day),
+    SqlParam::Decimal(value),
+  ];
+  c.database.execute_with("INSERT INTO foo VALUES (?, ?, ?, ?)", &params).await.unwrap();
+  let row = c.database.row("SELECT created_at, day, value FROM foo").await.unwrap();
+  assert_eq!(row.value_from_idx::<chrono::DateTime<Utc>>(0).unwrap(), created_at);
+  assert_eq!(row.value_from_idx::<NaiveDate>(1).unwrap(), day);
+  assert_eq!(row.value_from_idx::<Decimal>(2).unwrap(), value);
+}
@@ -106,3 +106,169 @@ pub(crate) async fn _clean_drops_all_objs<D>(
   assert_eq!(buffer_idents.len(), 0);
   buffer_idents.clear();
 }
+
+pub(crate) async fn _decimal_is_bound_as_numeric<D>(
+  _: (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  _c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  #[cfg(feature = "rust_decimal")]
+  {
+    use crate::{database::SqlParam, Row};
+    use core::str::FromStr;
+    use rust_decimal::Decimal;
+
+    _c.database.execute("CREATE TABLE foo (id INT PRIMARY KEY, value NUMERIC)").await.unwrap();
+    let values = [
+      "0",
+      "0.00",
+      "0.0001",
+      "0.5",
+      "1.000",
+      "-12345.67",
+      "100000000",
+      "79228162514264337593543950335",
+      "-0.0000000000000000000000000001",
+    ];
+    for (id, value) in (0..).zip(values) {
+      let decimal = Decimal::from_str(value).unwrap();
+      let params = [SqlParam::I32(id), SqlParam::Decimal(decimal)];
+      _c.database.execute_with("INSERT INTO foo VALUES ($1, $2)", &params).await.unwrap();
+      let select = "SELECT CAST(value AS TEXT) FROM foo WHERE id = $1";
+      let row = _c.database.row_with(select, &[SqlParam::I32(id)]).await.unwrap();
+      // The scale of zero isn't kept by every driver
+      assert_eq!(Decimal::from_str(row.str_from_idx(0).unwrap()).unwrap(), decimal);
+    }
+  }
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlx-postgres"))]
+pub(crate) async fn _lock_is_exclusive<D>(
+  (buffer_cmd, buffer_db_migrations, _): (
+    &mut String,
+    &mut Vec<crate::sm::DbMigration>,
+    &mut Vec<crate::Identifier>,
+  ),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  use crate::sm::SchemaManagement;
+  let config = crate::Config::with_url_from_default_var().unwrap();
+  #[cfg(feature = "postgres")]
+  let mut other = {
+    use tokio_util::compat::TokioAsyncWriteCompatExt;
+    let tcp = tokio::net::TcpStream::connect(config.full_host().unwrap()).await.unwrap();
+    crate::database::Postgres::new(&config, tcp.compat_write()).await.unwrap()
+  };
+  #[cfg(not(feature = "postgres"))]
+  let mut other = crate::database::SqlxPostgres::new(&config).await.unwrap();
+  other.lock(buffer_cmd, core::time::Duration::ZERO).await.unwrap();
+  c.set_lock_timeout(core::time::Duration::from_millis(200));
+  let mg = crate::sm::doc_tests::migration_group();
+  let migrations = [crate::sm::doc_tests::migration()];
+  let rslt = c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await;
+  assert!(matches!(rslt, Err(crate::Error::LockNotAcquired)));
+  other.unlock(buffer_cmd).await.unwrap();
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+}
+
+pub(crate) async fn _null_is_bound_to_any_column_ty<D>(
+  (buffer_cmd, _, _): (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  use crate::{database::SqlParam, Row};
+
+  let cmd = "CREATE TABLE foo (id INT PRIMARY KEY, active BOOLEAN, name TEXT, nick VARCHAR(64))";
+  c.database.execute(cmd).await.unwrap();
+
+  #[cfg(feature = "orm")]
+  {
+    use crate::{
+      orm::{Crud, FromSuffixRslt, NoTableAssociation, Table, TableField, TableParams},
+      TableSuffix,
+    };
+
+    struct Foo {
+      id: i32,
+      name: Option<String>,
+    }
+
+    impl<'entity> Table<'entity> for Foo {
+      const PRIMARY_KEY_NAME: &'static str = "id";
+      const TABLE_NAME: &'static str = "foo";
+
+      type Associations = NoTableAssociation<crate::Error>;
+      type Error = crate::Error;
+      type Fields = (TableField<Option<&'entity str>>,);
+      type PrimaryKeyValue = &'entity i32;
+
+      fn type_instances(_: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (NoTableAssociation::new(), (TableField::new("name"),))
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some(&entity.id);
+        *table.fields_mut().0.value_mut() = Some(entity.name.as_deref());
+      }
+    }
+
+    let foo = Foo { id: 1, name: None };
+    c.database.create(buffer_cmd, &foo, &mut TableParams::default()).await.unwrap();
+    buffer_cmd.clear();
+    let row =
+      c.database.row("SELECT COUNT(*) FROM foo WHERE id = 1 AND name IS NULL").await.unwrap();
+    assert_eq!(row.i64_from_idx(0).unwrap(), 1);
+  }
+
+  let insert = "INSERT INTO foo VALUES ($1, $2, $3, $4)";
+  let params = [SqlParam::I32(2), SqlParam::Null, SqlParam::Null, SqlParam::Null];
+  c.database.execute_with(insert, &params).await.unwrap();
+  let select = "SELECT COUNT(*) FROM foo WHERE id = $1 AND active IS NOT DISTINCT FROM $2 \
+    AND name IS NOT DISTINCT FROM $3";
+  let params = [SqlParam::I32(2), SqlParam::Null, SqlParam::Null];
+  let row = c.database.row_with(select, &params).await.unwrap();
+  assert_eq!(row.i64_from_idx(0).unwrap(), 1);
+}
+
+pub(crate) async fn _typed_values_round_trip<D>(
+  _: (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+  chrono::DateTime<chrono::Utc>: crate::FromSqlColumn<D::Row>,
+  chrono::NaiveDate: crate::FromSqlColumn<D::Row>,
+  rust_decimal::Decimal: crate::FromSqlColumn<D::Row>,
+{
+  use crate::{database::SqlParam, Row};
+  use chrono::{NaiveDate, TimeZone, Utc};
+  use rust_decimal::Decimal;
+
+  c.database
+    .execute(
+      "CREATE TABLE foo (id INT PRIMARY KEY, created_at TIMESTAMPTZ, day DATE, value NUMERIC)",
+    )
+    .await
+    .unwrap();
+  let created_at = Utc.with_ymd_and_hms(2023, 10, 15, 12, 30, 45).unwrap();
+  let day = NaiveDate::from_ymd_opt(2023, 10, 15).unwrap();
+  let value = Decimal::new(-1_234_567, 2);
+  let params = [
+    SqlParam::I32(1),
+    SqlParam::DateTime(created_at),
+    SqlParam::NaiveDate(day),
+    SqlParam::Decimal(value),
+  ];
+  c.database.execute_with("INSERT INTO foo VALUES ($1, $2, $3, $4)", &params).await.unwrap();
+  let row = c.database.row("SELECT created_at, day, value FROM foo").await.unwrap();
+  assert_eq!(row.value_from_idx::<chrono::DateTime<Utc>>(0).unwrap(), created_at);
+  assert_eq!(row.value_from_idx::<NaiveDate>(1).unwrap(), day);
+  assert_eq!(row.value_from_idx::<Decimal>(2).unwrap(), value);
+}
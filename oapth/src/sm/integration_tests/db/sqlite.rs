@@ -12,9 +12,11 @@ pub(crate) async fn _clean_drops_all_objs<D>(
 {
   crate::sm::integration_tests::create_foo_table(buffer_cmd, c, "").await;
 
+  c.database.lock(buffer_cmd, core::time::Duration::ZERO).await.unwrap();
   c.database.table_names(buffer_cmd, buffer_idents, "").await.unwrap();
   assert_eq!(buffer_idents.len(), 1);
   buffer_idents.clear();
+  c.database.unlock(buffer_cmd).await.unwrap();
 
   c.clear((buffer_cmd, buffer_idents)).await.unwrap();
 
@@ -22,3 +24,92 @@ pub(crate) async fn _clean_drops_all_objs<D>(
   assert_eq!(buffer_idents.len(), 0);
   buffer_idents.clear();
 }
+
+pub(crate) async fn _lock_is_exclusive<D>(
+  (buffer_cmd, buffer_db_migrations, _): (
+    &mut String,
+    &mut Vec<crate::sm::DbMigration>,
+    &mut Vec<crate::Identifier>,
+  ),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  c.database.lock(buffer_cmd, core::time::Duration::ZERO).await.unwrap();
+  c.set_lock_timeout(core::time::Duration::from_millis(200));
+  let mg = crate::sm::doc_tests::migration_group();
+  let migrations = [crate::sm::doc_tests::migration()];
+  let now = std::time::Instant::now();
+  let rslt = c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await;
+  assert!(matches!(rslt, Err(crate::Error::LockNotAcquired)));
+  assert!(now.elapsed() >= core::time::Duration::from_millis(200));
+  c.database.unlock(buffer_cmd).await.unwrap();
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+}
+
+pub(crate) async fn _legacy_migration_table_is_upgraded<D>(
+  (buffer_cmd, buffer_db_migrations, _): (
+    &mut String,
+    &mut Vec<crate::sm::DbMigration>,
+    &mut Vec<crate::Identifier>,
+  ),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+{
+  c.database
+    .execute(
+      "CREATE TABLE _oapth_migration_group (version INT NOT NULL PRIMARY KEY, name VARCHAR(128) NOT NULL);
+      CREATE TABLE _oapth_migration (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        created_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        _oapth_migration_omg_version INT NOT NULL,
+        checksum VARCHAR(20) NOT NULL,
+        name VARCHAR(128) NOT NULL,
+        repeatability INTEGER NULL,
+        version INT NOT NULL,
+        CONSTRAINT _oapth_migration_unq UNIQUE (version, _oapth_migration_omg_version)
+      );
+      INSERT INTO _oapth_migration_group (version, name) VALUES (1, 'legacy');
+      INSERT INTO _oapth_migration (_oapth_migration_omg_version, checksum, name, version)
+      VALUES (1, '1', 'create_foo', 1);",
+    )
+    .await
+    .unwrap();
+  c.database.create_oapth_tables().await.unwrap();
+  c.database.create_oapth_tables().await.unwrap();
+  let mg = crate::sm::MigrationGroup::new("legacy", 1);
+  c.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 1);
+  assert_eq!(buffer_db_migrations[0].applied_by(), None);
+  assert_eq!(buffer_db_migrations[0].duration_ms(), None);
+  assert!(buffer_db_migrations[0].success());
+  buffer_db_migrations.clear();
+}
+
+pub(crate) async fn _typed_values_round_trip<D>(
+  _: (&mut String, &mut Vec<crate::sm::DbMigration>, &mut Vec<crate::Identifier>),
+  c: &mut crate::sm::Commands<D>,
+  _: crate::sm::integration_tests::AuxTestParams,
+) where
+  D: crate::sm::SchemaManagement,
+  chrono::DateTime<chrono::Utc>: crate::FromSqlColumn<D::Row>,
+  chrono::NaiveDate: crate::FromSqlColumn<D::Row>,
+{
+  use crate::{database::SqlParam, Row};
+  use chrono::{NaiveDate, TimeZone, Utc};
+
+  c.database
+    .execute("CREATE TABLE foo (id INT PRIMARY KEY, created_at TEXT, day TEXT)")
+    .await
+    .unwrap();
+  let created_at = Utc.with_ymd_and_hms(2023, 10, 15, 12, 30, 45).unwrap();
+  let day = NaiveDate::from_ymd_opt(2023, 10, 15).unwrap();
+  let params = [SqlParam::I32(1), SqlParam::DateTime(created_at), SqlParam::NaiveDate(day)];
+  c.database.execute_with("INSERT INTO foo VALUES (?, ?, ?)", &params).await.unwrap();
+  let row = c.database.row("SELECT created_at, day FROM foo").await.unwrap();
+  assert_eq!(row.value_from_idx::<chrono::DateTime<Utc>>(0).unwrap(), created_at);
+  assert_eq!(row.value_from_idx::<NaiveDate>(1).unwrap(), day);
+}
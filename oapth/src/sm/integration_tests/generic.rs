@@ -1,7 +1,12 @@
 use crate::{
-  sm::{integration_tests::AuxTestParams, Commands, DbMigration, MigrationGroup, SchemaManagement},
-  Identifier,
+  database::{Database, SqlParam},
+  sm::{
+    integration_tests::AuxTestParams, Commands, DbMigration, MigrationGroup, MigrationStatus,
+    Repeatability, SchemaManagement, TransactionMode, UserMigrationRef,
+  },
+  DatabaseTy, FromSqlColumn, Identifier, Row,
 };
+use core::time::Duration;
 use std::path::Path;
 
 pub(crate) async fn all_tables_returns_the_number_of_tables_of_the_default_schema<D>(
@@ -17,6 +22,950 @@ pub(crate) async fn all_tables_returns_the_number_of_tables_of_the_default_schem
   buffer_idents.clear();
 }
 
+pub(crate) async fn baseline_records_migrations_without_executing_them<D>(
+  (buffer_cmd, buffer_db_migrations, buffer_idents): (
+    &mut String,
+    &mut Vec<DbMigration>,
+    &mut Vec<Identifier>,
+  ),
+  c: &mut Commands<D>,
+  aux: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let mg = MigrationGroup::new("baseline", 1);
+  let migration = |name, sql_up, version| {
+    UserMigrationRef::from_user_parts(&[], name, None, [sql_up, ""], true, version).unwrap()
+  };
+  let migrations = [
+    migration("create_foo", "CREATE TABLE foo (id INT)", 1),
+    migration("create_bar", "CREATE TABLE bar (id INT)", 2),
+    migration("create_baz", "CREATE TABLE baz (id INT)", 3),
+  ];
+  c.baseline((buffer_cmd, buffer_db_migrations), &mg, migrations.iter(), 2).await.unwrap();
+  c.database.table_names(buffer_cmd, buffer_idents, aux.default_schema).await.unwrap();
+  assert_eq!(buffer_idents.len(), aux.schema_regulator);
+  buffer_idents.clear();
+  c.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 2);
+  assert_eq!(buffer_db_migrations[1].checksum(), migrations[1].checksum());
+  assert_eq!(buffer_db_migrations[1].name(), "create_bar");
+  buffer_db_migrations.clear();
+
+  let rslt = c.baseline((buffer_cmd, buffer_db_migrations), &mg, migrations.iter(), 3).await;
+  assert!(matches!(rslt, Err(crate::Error::BaselineWithAppliedMigrations)));
+
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+  c.database.table_names(buffer_cmd, buffer_idents, aux.default_schema).await.unwrap();
+  assert_eq!(buffer_idents.len(), 1 + aux.schema_regulator);
+  buffer_idents.clear();
+}
+
+pub(crate) async fn batch_transaction_mode_records_every_migration<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let path = Path::new("../.test-utils/migrations.toml");
+  c.set_transaction_mode(TransactionMode::Batch);
+  c.migrate_from_toml_path((buffer_cmd, buffer_db_migrations), path).await.unwrap();
+  let initial = MigrationGroup::new("initial", 1);
+  c.database.migrations(buffer_cmd, &initial, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 4);
+  buffer_db_migrations.clear();
+}
+
+pub(crate) async fn crud_does_not_write_partial_entities<D>(
+  (_buffer_cmd, _, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  _c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+  i32: FromSqlColumn<D::Row>,
+{
+  #[cfg(feature = "orm")]
+  {
+    use crate::{
+      orm::{
+        Crud, FromSuffixRslt, NoTableAssociation, Table, TableAssociation, TableAssociationWrapper,
+        TableField, TableParams,
+      },
+      TableSuffix,
+    };
+
+    struct Bar {
+      id: i32,
+      name: &'static str,
+    }
+
+    impl<'entity> Table<'entity> for Bar {
+      const PRIMARY_KEY_NAME: &'static str = "id";
+      const TABLE_NAME: &'static str = "bar";
+
+      type Associations = NoTableAssociation<crate::Error>;
+      type Error = crate::Error;
+      type Fields = (TableField<&'static str>,);
+      type PrimaryKeyValue = &'entity i32;
+
+      fn type_instances(_: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (NoTableAssociation::new(), (TableField::new("name"),))
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some(&entity.id);
+        *table.fields_mut().0.value_mut() = Some(entity.name);
+      }
+    }
+
+    struct Foo {
+      bars: Vec<Bar>,
+      id: i32,
+      name: &'static str,
+    }
+
+    impl<'entity> Table<'entity> for Foo {
+      const PRIMARY_KEY_NAME: &'static str = "id";
+      const TABLE_NAME: &'static str = "foo";
+
+      type Associations = (TableAssociationWrapper<'entity, Bar, Vec<TableParams<'entity, Bar>>>,);
+      type Error = crate::Error;
+      type Fields = (TableField<&'static str>,);
+      type PrimaryKeyValue = &'entity i32;
+
+      fn type_instances(ts: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (
+          (TableAssociationWrapper {
+            association: TableAssociation::new("id", "foo_id"),
+            guide: TableParams::new(ts + 1),
+            tables: Vec::new(),
+          },),
+          (TableField::new("name"),),
+        )
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some(&entity.id);
+        *table.fields_mut().0.value_mut() = Some(entity.name);
+        table.associations_mut().0.tables.clear();
+        for bar in &entity.bars {
+          let mut elem = TableParams::new(table.table_suffix() + 1);
+          elem.update_all_table_fields(bar);
+          table.associations_mut().0.tables.push(elem);
+        }
+      }
+    }
+
+    let db = &mut _c.database;
+    db.execute("CREATE TABLE foo (id INT PRIMARY KEY, name VARCHAR(64))").await.unwrap();
+    db.execute("CREATE TABLE bar (id INT PRIMARY KEY, name VARCHAR(64), foo_id INT)")
+      .await
+      .unwrap();
+    db.execute("INSERT INTO bar VALUES (2, 'bar', NULL)").await.unwrap();
+
+    // The second child collides with the existing row
+    let bars = vec![Bar { id: 1, name: "bar1" }, Bar { id: 2, name: "bar2" }];
+    let foo = Foo { bars, id: 1, name: "foo" };
+    assert!(db.create(_buffer_cmd, &foo, &mut TableParams::default()).await.is_err());
+    _buffer_cmd.clear();
+    let mut ids = Vec::new();
+    let cmd = "SELECT id FROM foo UNION ALL SELECT id FROM bar";
+    db.rows(cmd, |row| {
+      ids.push(row.value_from_idx::<i32>(0)?);
+      crate::Result::Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(ids, [2]);
+
+    let bars = vec![Bar { id: 1, name: "bar1" }];
+    let foo = Foo { bars, id: 1, name: "foo" };
+    db.create(_buffer_cmd, &foo, &mut TableParams::default()).await.unwrap();
+    _buffer_cmd.clear();
+    let row = db.row("SELECT foo_id FROM bar WHERE id = 1").await.unwrap();
+    assert_eq!(row.value_from_idx::<i32>(0).unwrap(), 1);
+  }
+}
+
+pub(crate) async fn crud_handles_composite_text_keys<D>(
+  (_buffer_cmd, _, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  _c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+  i64: FromSqlColumn<D::Row>,
+  String: FromSqlColumn<D::Row>,
+{
+  #[cfg(feature = "orm")]
+  {
+    use crate::{
+      orm::{
+        decode_column_alias, Crud, FromSuffixRslt, NoTableAssociation, SelectLimit, SelectOrderBy,
+        Table, TableField, TableParams,
+      },
+      FromRows, TableSuffix,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Bar {
+      code: String,
+      id: i64,
+      name: String,
+    }
+
+    impl<R> FromRows<R> for Bar
+    where
+      R: Row,
+      i64: FromSqlColumn<R>,
+      String: FromSqlColumn<R>,
+    {
+      type Error = crate::Error;
+      type PrimaryKey = (String, i64);
+
+      fn from_rows(
+        buffer_cmd: &mut String,
+        curr_row: &R,
+        _: &[R],
+        ts: TableSuffix,
+      ) -> Result<(usize, Self), Self::Error> {
+        let (code, id) = Self::primary_key(buffer_cmd, curr_row, ts)?;
+        let name = decode_column_alias(buffer_cmd, curr_row, "bar", ts, "name")?;
+        Ok((1, Self { code, id, name }))
+      }
+
+      fn primary_key(
+        buffer_cmd: &mut String,
+        row: &R,
+        ts: TableSuffix,
+      ) -> crate::Result<(String, i64)> {
+        Ok((
+          decode_column_alias(buffer_cmd, row, "bar", ts, "code")?,
+          decode_column_alias(buffer_cmd, row, "bar", ts, "id")?,
+        ))
+      }
+    }
+
+    impl<'entity> Table<'entity> for Bar {
+      const PRIMARY_KEY_NAME: &'static str = "code";
+      const PRIMARY_KEY_NAMES: &'static [&'static str] = &["code", "id"];
+      const TABLE_NAME: &'static str = "bar";
+
+      type Associations = NoTableAssociation<crate::Error>;
+      type Error = crate::Error;
+      type Fields = (TableField<&'entity str>,);
+      type PrimaryKeyValue = (&'entity String, &'entity i64);
+
+      fn type_instances(_: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (NoTableAssociation::new(), (TableField::new("name"),))
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some((&entity.code, &entity.id));
+        *table.fields_mut().0.value_mut() = Some(entity.name.as_str());
+      }
+    }
+
+    let bar = |code: &str, id, name: &str| Bar { code: code.into(), id, name: name.into() };
+    let db = &mut _c.database;
+    db.execute(
+      "CREATE TABLE bar (code VARCHAR(8), id BIGINT, name VARCHAR(64), PRIMARY KEY (code, id))",
+    )
+    .await
+    .unwrap();
+    let bars = [bar("a", 1, "foo"), bar("a", 2, "bar"), bar("b", 1, "baz")];
+    for elem in &bars {
+      db.create(_buffer_cmd, elem, &mut TableParams::default()).await.unwrap();
+      _buffer_cmd.clear();
+    }
+    let (bar_a2, bar_b1) = (bar("a", 2, "qux"), bar("b", 1, "quux"));
+    assert_eq!(db.update(_buffer_cmd, &bar_a2, &mut TableParams::default()).await.unwrap(), 1);
+    _buffer_cmd.clear();
+    let updated = db.upsert(_buffer_cmd, &bar_b1, &mut TableParams::default()).await.unwrap();
+    assert_eq!(updated, if D::TY == DatabaseTy::MySql { 2 } else { 1 });
+    _buffer_cmd.clear();
+
+    let (mut buffer_rows, mut results) = (Vec::new(), Vec::new());
+    let read_tp = TableParams::<Bar>::default();
+    db.read_all((_buffer_cmd, &mut buffer_rows), &mut results, &read_tp).await.unwrap();
+    assert_eq!(results, [bar("a", 1, "foo"), bar("a", 2, "qux"), bar("b", 1, "quux")]);
+    buffer_rows.clear();
+    results.clear();
+    db.read_all_with_params(
+      (_buffer_cmd, &mut buffer_rows),
+      (SelectOrderBy::Descending, &[]),
+      &mut results,
+      SelectLimit::Page { count: 1, offset: 1 },
+      None,
+      &read_tp,
+    )
+    .await
+    .unwrap();
+    assert_eq!(results, [bar("a", 2, "qux")]);
+    _buffer_cmd.clear();
+    let (code, id) = (String::from("b"), 1);
+    let entity = db.read_by_id(_buffer_cmd, &(&code, &id), &read_tp).await.unwrap();
+    assert_eq!(entity, bar("b", 1, "quux"));
+    _buffer_cmd.clear();
+
+    assert_eq!(db.delete(_buffer_cmd, &bars[0], &mut TableParams::default()).await.unwrap(), 1);
+    _buffer_cmd.clear();
+    buffer_rows.clear();
+    results.clear();
+    db.read_all((_buffer_cmd, &mut buffer_rows), &mut results, &read_tp).await.unwrap();
+    assert_eq!(results, [bar("a", 2, "qux"), bar("b", 1, "quux")]);
+  }
+}
+
+pub(crate) async fn crud_only_orders_by_root_columns<D>(
+  (_buffer_cmd, _, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  _c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+  i32: FromSqlColumn<D::Row>,
+{
+  #[cfg(feature = "orm")]
+  {
+    use crate::{
+      orm::{
+        decode_column_alias, Crud, FromSuffixRslt, NoTableAssociation, SelectLimit, SelectOrderBy,
+        SqlColumn, Table, TableAssociation, TableAssociationWrapper, TableField, TableParams,
+      },
+      FromRows, TableSuffix,
+    };
+
+    #[derive(Debug)]
+    struct Bar {
+      id: i32,
+      name: &'static str,
+    }
+
+    impl<'entity> Table<'entity> for Bar {
+      const PRIMARY_KEY_NAME: &'static str = "id";
+      const TABLE_NAME: &'static str = "bar";
+
+      type Associations = NoTableAssociation<crate::Error>;
+      type Error = crate::Error;
+      type Fields = (TableField<&'static str>,);
+      type PrimaryKeyValue = &'entity i32;
+
+      fn type_instances(_: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (NoTableAssociation::new(), (TableField::new("name"),))
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some(&entity.id);
+        *table.fields_mut().0.value_mut() = Some(entity.name);
+      }
+    }
+
+    #[derive(Debug)]
+    struct Foo {
+      bars: Vec<Bar>,
+      id: i32,
+      name: &'static str,
+    }
+
+    impl<R> FromRows<R> for Foo
+    where
+      R: Row,
+      i32: FromSqlColumn<R>,
+    {
+      type Error = crate::Error;
+      type PrimaryKey = i32;
+
+      fn from_rows(
+        buffer_cmd: &mut String,
+        curr_row: &R,
+        rows: &[R],
+        ts: TableSuffix,
+      ) -> Result<(usize, Self), Self::Error> {
+        let id = Self::primary_key(buffer_cmd, curr_row, ts)?;
+        let mut len = 0;
+        for row in rows {
+          if Self::primary_key(buffer_cmd, row, ts)? != id {
+            break;
+          }
+          len += 1;
+        }
+        Ok((len, Self { bars: Vec::new(), id, name: "" }))
+      }
+
+      fn primary_key(buffer_cmd: &mut String, row: &R, ts: TableSuffix) -> crate::Result<i32> {
+        decode_column_alias(buffer_cmd, row, "foo", ts, "id")
+      }
+    }
+
+    impl<'entity> Table<'entity> for Foo {
+      const PRIMARY_KEY_NAME: &'static str = "id";
+      const TABLE_NAME: &'static str = "foo";
+
+      type Associations = (TableAssociationWrapper<'entity, Bar, Vec<TableParams<'entity, Bar>>>,);
+      type Error = crate::Error;
+      type Fields = (TableField<&'static str>,);
+      type PrimaryKeyValue = &'entity i32;
+
+      fn type_instances(ts: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (
+          (TableAssociationWrapper {
+            association: TableAssociation::new("id", "foo_id"),
+            guide: TableParams::new(ts + 1),
+            tables: Vec::new(),
+          },),
+          (TableField::new("name"),),
+        )
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some(&entity.id);
+        *table.fields_mut().0.value_mut() = Some(entity.name);
+        table.associations_mut().0.tables.clear();
+        for bar in &entity.bars {
+          let mut elem = TableParams::new(table.table_suffix() + 1);
+          elem.update_all_table_fields(bar);
+          table.associations_mut().0.tables.push(elem);
+        }
+      }
+    }
+
+    let db = &mut _c.database;
+    db.execute("CREATE TABLE foo (id INT PRIMARY KEY, name VARCHAR(64))").await.unwrap();
+    db.execute("CREATE TABLE bar (id INT PRIMARY KEY, name VARCHAR(64), foo_id INT)")
+      .await
+      .unwrap();
+    let foo =
+      Foo { bars: vec![Bar { id: 1, name: "b" }, Bar { id: 2, name: "a" }], id: 1, name: "foo" };
+    db.create(_buffer_cmd, &foo, &mut TableParams::default()).await.unwrap();
+    _buffer_cmd.clear();
+
+    let (mut buffer_rows, mut results) = (Vec::new(), Vec::new());
+    let tp = TableParams::<Foo>::default();
+    let bar_name =
+      SqlColumn::new(&tp.associations().0.guide, &tp.associations().0.guide.fields().0);
+    let foo_name = SqlColumn::new(&tp, &tp.fields().0);
+    for limit in [SelectLimit::All, SelectLimit::Page { count: 1, offset: 0 }] {
+      let rslt = db
+        .read_all_with_params(
+          (_buffer_cmd, &mut buffer_rows),
+          (SelectOrderBy::Ascending, &[(bar_name, SelectOrderBy::Descending)]),
+          &mut results,
+          limit,
+          None,
+          &tp,
+        )
+        .await;
+      assert!(matches!(rslt, Err(crate::Error::NonRootOrderByColumn)));
+      _buffer_cmd.clear();
+      db.read_all_with_params(
+        (_buffer_cmd, &mut buffer_rows),
+        (SelectOrderBy::Ascending, &[(foo_name, SelectOrderBy::Descending)]),
+        &mut results,
+        limit,
+        None,
+        &tp,
+      )
+      .await
+      .unwrap();
+      _buffer_cmd.clear();
+      buffer_rows.clear();
+    }
+    assert_eq!(results.iter().map(|el| el.id).collect::<Vec<_>>(), [1, 1]);
+  }
+}
+
+pub(crate) async fn crud_writes_entities_and_reports_affected_rows<D>(
+  (_buffer_cmd, _, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  _c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+  i32: FromSqlColumn<D::Row>,
+  Option<String>: FromSqlColumn<D::Row>,
+  String: FromSqlColumn<D::Row>,
+{
+  #[cfg(feature = "orm")]
+  {
+    use crate::{
+      orm::{
+        decode_column_alias, Crud, FromSuffixRslt, NoTableAssociation, SelectLimit, SelectOrderBy,
+        SqlColumn, Table, TableField, TableParams,
+      },
+      FromRows, TableSuffix,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Foo {
+      id: i32,
+      name: String,
+    }
+
+    impl<R> FromRows<R> for Foo
+    where
+      R: Row,
+      i32: FromSqlColumn<R>,
+      String: FromSqlColumn<R>,
+    {
+      type Error = crate::Error;
+      type PrimaryKey = i32;
+
+      fn from_rows(
+        buffer_cmd: &mut String,
+        curr_row: &R,
+        _: &[R],
+        ts: TableSuffix,
+      ) -> Result<(usize, Self), Self::Error> {
+        let id = Self::primary_key(buffer_cmd, curr_row, ts)?;
+        let name = decode_column_alias(buffer_cmd, curr_row, "foo", ts, "name")?;
+        Ok((1, Self { id, name }))
+      }
+
+      fn primary_key(buffer_cmd: &mut String, row: &R, ts: TableSuffix) -> crate::Result<i32> {
+        decode_column_alias(buffer_cmd, row, "foo", ts, "id")
+      }
+    }
+
+    impl<'entity> Table<'entity> for Foo {
+      const PRIMARY_KEY_NAME: &'static str = "id";
+      const TABLE_NAME: &'static str = "foo";
+
+      type Associations = NoTableAssociation<crate::Error>;
+      type Error = crate::Error;
+      type Fields = (TableField<&'entity str>,);
+      type PrimaryKeyValue = &'entity i32;
+
+      fn type_instances(_: TableSuffix) -> FromSuffixRslt<'entity, Self> {
+        (NoTableAssociation::new(), (TableField::new("name"),))
+      }
+
+      fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
+        *table.id_field_mut().value_mut() = Some(&entity.id);
+        *table.fields_mut().0.value_mut() = Some(entity.name.as_str());
+      }
+    }
+
+    let db = &mut _c.database;
+    db.execute("CREATE TABLE foo (id INT PRIMARY KEY, name VARCHAR(64))").await.unwrap();
+    let mut tp = TableParams::default();
+    let foo1 = Foo { id: 1, name: "foo".into() };
+    let foo2 = Foo { id: 2, name: "bar".into() };
+    db.create(_buffer_cmd, &foo1, &mut tp).await.unwrap();
+    _buffer_cmd.clear();
+
+    let foo1 = Foo { id: 1, name: "baz".into() };
+    assert_eq!(db.update(_buffer_cmd, &foo1, &mut tp).await.unwrap(), 1);
+    _buffer_cmd.clear();
+    let foo1 = Foo { id: 1, name: "qux".into() };
+    let updated = db.upsert(_buffer_cmd, &foo1, &mut tp).await.unwrap();
+    assert_eq!(updated, if D::TY == DatabaseTy::MySql { 2 } else { 1 });
+    _buffer_cmd.clear();
+    assert_eq!(db.upsert(_buffer_cmd, &foo2, &mut tp).await.unwrap(), 1);
+    _buffer_cmd.clear();
+    let row = db.row("SELECT name FROM foo WHERE id = 1").await.unwrap();
+    assert_eq!(row.str_from_idx(0).unwrap(), "qux");
+    let row = db.row("SELECT name FROM foo WHERE id = 2").await.unwrap();
+    assert_eq!(row.str_from_idx(0).unwrap(), "bar");
+    let row = db.row("SELECT id, NULL AS missing FROM foo WHERE id = 2").await.unwrap();
+    assert_eq!(row.columns_len(), 2);
+    assert_eq!((row.column_name(0), row.column_name(1)), (Some("id"), Some("missing")));
+    assert!(row.column_ty(0).is_some());
+    assert_eq!(row.value_from_idx::<i32>(0).unwrap(), 2);
+    assert_eq!(row.value_from_name::<Option<String>>("missing").unwrap(), None);
+
+    let (mut buffer_rows, mut results) = (Vec::new(), Vec::new());
+    let read_tp = TableParams::<Foo>::default();
+    let (names, pattern) = (["bar", "quux"], "q%");
+    let sql_where = SqlColumn::new(&read_tp, &read_tp.fields().0)
+      .in_list(&names)
+      .or(SqlColumn::new(&read_tp, &read_tp.fields().0).like(&pattern));
+    db.read_all_with_params(
+      (_buffer_cmd, &mut buffer_rows),
+      (SelectOrderBy::Descending, &[]),
+      &mut results,
+      SelectLimit::All,
+      Some(&sql_where),
+      &read_tp,
+    )
+    .await
+    .unwrap();
+    assert_eq!(results, [Foo { id: 2, name: "bar".into() }, Foo { id: 1, name: "qux".into() }]);
+    _buffer_cmd.clear();
+
+    let foo3 = Foo { id: 3, name: "baz".into() };
+    db.create(_buffer_cmd, &foo3, &mut TableParams::default()).await.unwrap();
+    _buffer_cmd.clear();
+    let name_column = SqlColumn::new(&read_tp, &read_tp.fields().0);
+    buffer_rows.clear();
+    results.clear();
+    db.read_all_with_params(
+      (_buffer_cmd, &mut buffer_rows),
+      (SelectOrderBy::Ascending, &[(name_column, SelectOrderBy::Ascending)]),
+      &mut results,
+      SelectLimit::Page { count: 1, offset: 1 },
+      None,
+      &read_tp,
+    )
+    .await
+    .unwrap();
+    assert_eq!(results, [Foo { id: 3, name: "baz".into() }]);
+    _buffer_cmd.clear();
+    let last_id = 1;
+    buffer_rows.clear();
+    results.clear();
+    db.read_all_with_params(
+      (_buffer_cmd, &mut buffer_rows),
+      (SelectOrderBy::Ascending, &[]),
+      &mut results,
+      SelectLimit::Page { count: 1, offset: 0 },
+      Some(&SqlColumn::new(&read_tp, read_tp.id_field()).gt(&last_id)),
+      &read_tp,
+    )
+    .await
+    .unwrap();
+    assert_eq!(results, [Foo { id: 2, name: "bar".into() }]);
+    _buffer_cmd.clear();
+    let mut ids = Vec::new();
+    db.read_all_with_cb((_buffer_cmd, &mut buffer_rows), &read_tp, |foo| {
+      ids.push(foo.id);
+      Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(ids, [1, 2, 3]);
+    assert!(_buffer_cmd.is_empty() && buffer_rows.is_empty());
+
+    assert_eq!(db.delete(_buffer_cmd, &foo1, &mut tp).await.unwrap(), 1);
+    _buffer_cmd.clear();
+    assert_eq!(db.delete(_buffer_cmd, &foo1, &mut tp).await.unwrap(), 0);
+    _buffer_cmd.clear();
+  }
+}
+
+pub(crate) async fn lock_is_released_after_failed_operations<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let mg = MigrationGroup::new("lock", 1);
+  let migrations =
+    [UserMigrationRef::from_user_parts(&[], "fail", None, ["SELECT * FROM baz", ""], true, 1)
+      .unwrap()];
+  assert!(c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.is_err());
+  c.database.lock(buffer_cmd, Duration::ZERO).await.unwrap();
+  c.database.unlock(buffer_cmd).await.unwrap();
+}
+
+pub(crate) async fn migrations_record_execution_metadata<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  aux: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  async fn failed_records<D>(
+    buffer_cmd: &mut String,
+    c: &mut Commands<D>,
+    aux: AuxTestParams,
+  ) -> i64
+  where
+    D: SchemaManagement,
+  {
+    buffer_cmd.clear();
+    if !aux.oapth_schema.is_empty() {
+      buffer_cmd.push_str(aux.oapth_schema);
+      buffer_cmd.push('.');
+    }
+    let table = format!("{buffer_cmd}_oapth_migration");
+    buffer_cmd.clear();
+    let sql = format!("SELECT COUNT(*) FROM {table} WHERE success = 0");
+    c.database.row(&sql).await.unwrap().i64_from_idx(0).unwrap()
+  }
+
+  let mg = MigrationGroup::new("metadata", 1);
+  let migration = |name, sql_up, version| {
+    UserMigrationRef::from_user_parts(&[], name, None, [sql_up, ""], true, version).unwrap()
+  };
+  let mut migrations = [
+    migration("create_foo", "CREATE TABLE foo (id INT)", 1),
+    migration("create_bar", "CREATE TABLE bar (id INT REFERENCES)", 2),
+  ];
+  assert!(c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.is_err());
+  c.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 1);
+  assert_eq!(buffer_db_migrations[0].oapth_version(), Some(env!("CARGO_PKG_VERSION")));
+  assert!(buffer_db_migrations[0].duration_ms().is_some());
+  assert!(buffer_db_migrations[0].success());
+  buffer_db_migrations.clear();
+  assert_eq!(failed_records(buffer_cmd, c, aux).await, 1);
+
+  // A trailing line comment must not hide the removal of the failed record
+  migrations[1] = migration("create_bar", "CREATE TABLE bar (id INT); -- Bar", 2);
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+  c.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 2);
+  buffer_db_migrations.clear();
+  assert_eq!(failed_records(buffer_cmd, c, aux).await, 0);
+}
+
+pub(crate) async fn parameterized_commands_bind_values<D>(
+  _: (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let [insert, select_name, select_ids] = match D::TY {
+    DatabaseTy::Mssql => [
+      "INSERT INTO foo (id, name) VALUES (@P1, @P2)",
+      "SELECT name FROM foo WHERE id = @P1",
+      "SELECT id FROM foo WHERE id >= @P1 ORDER BY id",
+    ],
+    DatabaseTy::Postgres => [
+      "INSERT INTO foo (id, name) VALUES ($1, $2)",
+      "SELECT name FROM foo WHERE id = $1",
+      "SELECT id FROM foo WHERE id >= $1 ORDER BY id",
+    ],
+    _ => [
+      "INSERT INTO foo (id, name) VALUES (?, ?)",
+      "SELECT name FROM foo WHERE id = ?",
+      "SELECT id FROM foo WHERE id >= ? ORDER BY id",
+    ],
+  };
+  let name = "O'Brien'); DROP TABLE foo; --";
+  c.database.execute("CREATE TABLE foo (id BIGINT, name VARCHAR(64))").await.unwrap();
+  c.database.execute_with(insert, &[SqlParam::I64(1), SqlParam::Str(name)]).await.unwrap();
+  c.database.execute_with(insert, &[SqlParam::I64(2), SqlParam::Null]).await.unwrap();
+  let row = c.database.row_with(select_name, &[SqlParam::I32(1)]).await.unwrap();
+  assert_eq!(row.str_from_idx(0).unwrap(), name);
+  let mut ids = Vec::new();
+  c.database
+    .rows_with(select_ids, &[SqlParam::I64(1)], |row| {
+      ids.push(row.i64_from_idx(0)?);
+      Ok::<_, crate::Error>(())
+    })
+    .await
+    .unwrap();
+  assert_eq!(ids, [1, 2]);
+}
+
+pub(crate) async fn parameterized_commands_bind_typed_values<D>(
+  (_buffer_cmd, _, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+  f64: FromSqlColumn<D::Row>,
+  Vec<u8>: FromSqlColumn<D::Row>,
+{
+  let (create, insert) = match D::TY {
+    DatabaseTy::Mssql => {
+      ("CREATE TABLE foo (id INT, score FLOAT, data VARBINARY(8))", "VALUES (@P1, @P2, @P3)")
+    }
+    DatabaseTy::MySql => ("CREATE TABLE foo (id INT, score DOUBLE, data BLOB)", "VALUES (?, ?, ?)"),
+    DatabaseTy::Postgres => {
+      ("CREATE TABLE foo (id INT, score DOUBLE PRECISION, data BYTEA)", "VALUES ($1, $2, $3)")
+    }
+    _ => ("CREATE TABLE foo (id INT, score REAL, data BLOB)", "VALUES (?, ?, ?)"),
+  };
+  c.database.execute(create).await.unwrap();
+  let params = [SqlParam::I32(1), SqlParam::F64(1.5), SqlParam::Bytes(&[10, 255])];
+  let cmd = alloc::format!("INSERT INTO foo (id, score, data) {insert}");
+  c.database.execute_with(&cmd, &params).await.unwrap();
+  #[cfg(feature = "orm")]
+  {
+    use crate::orm::SqlValue;
+    _buffer_cmd.push_str("INSERT INTO foo (id, score, data) VALUES (2, ");
+    SqlValue::<crate::Error>::write(&-0.25f64, _buffer_cmd, D::TY).unwrap();
+    _buffer_cmd.push_str(", ");
+    SqlValue::<crate::Error>::write(&&[0u8, 1][..], _buffer_cmd, D::TY).unwrap();
+    _buffer_cmd.push(')');
+    c.database.execute(_buffer_cmd).await.unwrap();
+    _buffer_cmd.clear();
+    let row = c.database.row("SELECT score, data FROM foo WHERE id = 2").await.unwrap();
+    assert_eq!(row.value_from_idx::<f64>(0).unwrap(), -0.25);
+    assert_eq!(row.value_from_name::<Vec<u8>>("data").unwrap(), [0, 1]);
+  }
+  let row = c.database.row("SELECT score, data FROM foo WHERE id = 1").await.unwrap();
+  assert_eq!(row.value_from_name::<f64>("score").unwrap(), 1.5);
+  assert_eq!(row.value_from_idx::<Vec<u8>>(1).unwrap(), [10, 255]);
+}
+
+pub(crate) async fn per_migration_transaction_mode_records_applied_migrations<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let mg = MigrationGroup::new("transactions", 1);
+  let migrations = [
+    UserMigrationRef::from_user_parts(
+      &[],
+      "create_foo",
+      None,
+      ["CREATE TABLE foo (id INT)", "DROP TABLE foo"],
+      true,
+      1,
+    )
+    .unwrap(),
+    UserMigrationRef::from_user_parts(
+      &[],
+      "create_bar",
+      None,
+      ["CREATE TABLE bar (id INT)", "DROP TABLE bar"],
+      false,
+      2,
+    )
+    .unwrap(),
+    UserMigrationRef::from_user_parts(&[], "fail", None, ["SELECT * FROM baz", ""], true, 3)
+      .unwrap(),
+  ];
+  c.set_transaction_mode(TransactionMode::PerMigration);
+  assert!(c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.is_err());
+  c.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 2);
+  assert_eq!(buffer_db_migrations[1].name(), "create_bar");
+  buffer_db_migrations.clear();
+}
+
+pub(crate) async fn plan_reports_pending_migrations<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let path = Path::new("../.test-utils/migrations.toml");
+  let mut plans = Vec::new();
+  c.plan_from_toml_path((buffer_cmd, buffer_db_migrations), path, &mut plans).await.unwrap();
+  assert_eq!(plans.len(), 2);
+  assert_eq!(plans[0].group().name(), "initial");
+  assert_eq!(plans[0].migrations().len(), 4);
+  assert_eq!(plans[0].migrations()[0].name(), "create_author");
+  assert_eq!(plans[1].group().name(), "more_stuff");
+  assert_eq!(plans[1].migrations().len(), if D::TY == DatabaseTy::Postgres { 3 } else { 2 });
+  assert!(!c.database.has_oapth_tables().await.unwrap());
+  plans[0].write_sql(buffer_cmd);
+  let first_sql_up = plans[0].migrations()[0].sql_up().trim_end();
+  assert!(buffer_cmd.starts_with(first_sql_up));
+  assert!(buffer_cmd[first_sql_up.len()..].starts_with('\n'));
+  buffer_cmd.clear();
+
+  c.migrate_from_toml_path((buffer_cmd, buffer_db_migrations), path).await.unwrap();
+  plans.clear();
+  c.plan_from_toml_path((buffer_cmd, buffer_db_migrations), path, &mut plans).await.unwrap();
+  assert_eq!(plans.len(), 2);
+  assert!(plans[0].is_empty());
+  assert_eq!(plans[1].migrations().len(), if D::TY == DatabaseTy::Postgres { 1 } else { 0 });
+}
+
+pub(crate) async fn repair_realigns_divergent_migrations<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let mg = MigrationGroup::new("repair", 1);
+  let migration = |name, sql_up, version| {
+    UserMigrationRef::from_user_parts(&[], name, None, [sql_up, ""], true, version).unwrap()
+  };
+  let mut migrations = [
+    migration("create_foo", "CREATE TABLE foo (id INT)", 1),
+    migration("create_bar", "CREATE TABLE bar (id INT)", 2),
+    migration("create_baz", "CREATE TABLE baz (id INT REFERENCES)", 3),
+  ];
+  assert!(c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.is_err());
+
+  migrations[0] = migration("create_foo_table", "CREATE TABLE foo (id INT)", 1);
+  migrations[1] = migration("create_bar", "CREATE TABLE bar ( id INT )", 2);
+  let applied = &migrations[..2];
+  let rslt = c.validate((buffer_cmd, buffer_db_migrations), &mg, applied.iter()).await;
+  assert!(matches!(rslt, Err(crate::Error::ValidationDivergentMigrations(1))));
+  buffer_db_migrations.clear();
+
+  let preview =
+    c.repair_preview((buffer_cmd, buffer_db_migrations), &mg, applied.iter()).await.unwrap();
+  assert_eq!(preview.deleted_failed(), 1);
+  assert_eq!(preview.entries().len(), 2);
+  let rslt = c.validate((buffer_cmd, buffer_db_migrations), &mg, applied.iter()).await;
+  assert!(matches!(rslt, Err(crate::Error::ValidationDivergentMigrations(1))));
+  buffer_db_migrations.clear();
+
+  let repair = c.repair((buffer_cmd, buffer_db_migrations), &mg, applied.iter()).await.unwrap();
+  assert_eq!(repair, preview);
+  assert_eq!(repair.entries().len(), 2);
+  assert_eq!(repair.entries()[0].old_name(), "create_foo");
+  assert_eq!(repair.entries()[0].new_name(), "create_foo_table");
+  assert_eq!(repair.entries()[1].new_checksum(), migrations[1].checksum());
+  c.validate((buffer_cmd, buffer_db_migrations), &mg, applied.iter()).await.unwrap();
+
+  let repair = c.repair((buffer_cmd, buffer_db_migrations), &mg, applied.iter()).await.unwrap();
+  assert!(repair.is_empty());
+}
+
+pub(crate) async fn repeatable_migrations_are_executed_again<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  async fn counter_rows<D>(c: &mut Commands<D>) -> usize
+  where
+    D: SchemaManagement,
+  {
+    let mut rows = 0;
+    c.database
+      .rows("SELECT id FROM counter", |_| {
+        rows += 1;
+        Ok::<_, crate::Error>(())
+      })
+      .await
+      .unwrap();
+    rows
+  }
+
+  let mg = MigrationGroup::new("repeatable", 1);
+  let migration = |name, repeatability, sql_up, version| {
+    UserMigrationRef::from_user_parts(&[], name, repeatability, [sql_up, ""], true, version)
+      .unwrap()
+  };
+  let mut migrations = [
+    migration("create_counter", None, "CREATE TABLE counter (id INT)", 1),
+    migration("always", Some(Repeatability::Always), "INSERT INTO counter (id) VALUES (1)", 2),
+    migration(
+      "on_checksum_change",
+      Some(Repeatability::OnChecksumChange),
+      "INSERT INTO counter (id) VALUES (2)",
+      3,
+    ),
+  ];
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+  assert_eq!(counter_rows(c).await, 2);
+
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+  assert_eq!(counter_rows(c).await, 3);
+
+  migrations[2] = migration(
+    "on_checksum_change",
+    Some(Repeatability::OnChecksumChange),
+    "INSERT INTO counter (id) VALUES (3)",
+    3,
+  );
+  c.migrate((buffer_cmd, buffer_db_migrations), &mg, migrations.iter()).await.unwrap();
+  assert_eq!(counter_rows(c).await, 5);
+
+  c.database.migrations(buffer_cmd, &mg, buffer_db_migrations).await.unwrap();
+  assert_eq!(buffer_db_migrations.len(), 3);
+  assert_eq!(buffer_db_migrations[1].repeatability(), Some(Repeatability::Always));
+  assert_eq!(buffer_db_migrations[2].checksum(), migrations[2].checksum());
+  assert_eq!(buffer_db_migrations[2].repeatability(), Some(Repeatability::OnChecksumChange));
+  buffer_db_migrations.clear();
+}
+
 pub(crate) async fn rollback_works<D>(
   (buffer_cmd, buffer_db_migrations, buffer_idents): (
     &mut String,
@@ -48,3 +997,94 @@ pub(crate) async fn rollback_works<D>(
   assert_eq!(buffer_idents.len(), 2);
   buffer_idents.clear();
 }
+
+pub(crate) async fn status_reports_every_migration<D>(
+  (buffer_cmd, buffer_db_migrations, _): (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  let path = Path::new("../.test-utils/migrations.toml");
+  let mut reports = Vec::new();
+  c.status_from_toml_path((buffer_cmd, buffer_db_migrations), path, &mut reports).await.unwrap();
+  assert!(reports[0].entries().iter().all(|entry| entry.status() == MigrationStatus::Pending));
+  assert!(reports[0].entries().iter().all(|entry| entry.created_on().is_none()));
+
+  c.migrate_from_toml_path((buffer_cmd, buffer_db_migrations), path).await.unwrap();
+  reports.clear();
+  c.status_from_toml_path((buffer_cmd, buffer_db_migrations), path, &mut reports).await.unwrap();
+  assert_eq!(reports.len(), 2);
+  assert_eq!(reports[0].entries().len(), 4);
+  assert!(reports[0].entries().iter().all(|entry| entry.status() == MigrationStatus::Applied));
+  assert!(reports[0].entries().iter().all(|entry| entry.created_on().is_some()));
+  if D::TY == DatabaseTy::Postgres {
+    assert_eq!(reports[1].entries().len(), 3);
+    assert_eq!(reports[1].entries()[2].status(), MigrationStatus::RepeatableRerunNeeded);
+  } else {
+    assert_eq!(reports[1].entries().len(), 2);
+  }
+  assert!(!reports.iter().any(|report| report.has_divergences()));
+
+  let migrations = [
+    UserMigrationRef::from_user_parts(&[], "create_author", None, ["", ""], true, 1).unwrap(),
+    UserMigrationRef::from_user_parts(&[], "create_comment", None, ["", ""], true, 2).unwrap(),
+    UserMigrationRef::from_user_parts(&[], "create_tag", None, ["", ""], true, 5).unwrap(),
+  ];
+  let initial = MigrationGroup::new("initial", 1);
+  let report =
+    c.status((buffer_cmd, buffer_db_migrations), &initial, migrations.iter()).await.unwrap();
+  let statuses = report.entries().iter().map(|entry| entry.status()).collect::<Vec<_>>();
+  assert_eq!(
+    statuses,
+    [
+      MigrationStatus::ChecksumMismatch,
+      MigrationStatus::NameMismatch,
+      MigrationStatus::MissingFromDisk,
+      MigrationStatus::MissingFromDisk,
+      MigrationStatus::Pending
+    ]
+  );
+  assert_eq!(report.entries()[2].name(), "insert_author");
+  assert!(report.has_divergences());
+}
+
+pub(crate) async fn transactions_are_committed_rolled_back_and_nested<D>(
+  _: (&mut String, &mut Vec<DbMigration>, &mut Vec<Identifier>),
+  c: &mut Commands<D>,
+  _: AuxTestParams,
+) where
+  D: SchemaManagement,
+{
+  async fn ids(database: &mut impl Database) -> Vec<i64> {
+    let mut ids = Vec::new();
+    database
+      .rows("SELECT id FROM foo ORDER BY id", |row| {
+        ids.push(row.i64_from_idx(0)?);
+        Ok::<_, crate::Error>(())
+      })
+      .await
+      .unwrap();
+    ids
+  }
+
+  c.database.execute("CREATE TABLE foo (id BIGINT)").await.unwrap();
+  let mut transaction = c.database.begin().await.unwrap();
+  transaction.execute("INSERT INTO foo (id) VALUES (1)").await.unwrap();
+  let mut savepoint = transaction.begin().await.unwrap();
+  savepoint.execute("INSERT INTO foo (id) VALUES (2)").await.unwrap();
+  savepoint.rollback().await.unwrap();
+  let mut savepoint = transaction.begin().await.unwrap();
+  savepoint.execute("INSERT INTO foo (id) VALUES (3)").await.unwrap();
+  savepoint.commit().await.unwrap();
+  let mut savepoint = transaction.begin().await.unwrap();
+  savepoint.execute("INSERT INTO foo (id) VALUES (4)").await.unwrap();
+  drop(savepoint);
+  assert_eq!(ids(&mut transaction).await, [1, 3]);
+  transaction.commit().await.unwrap();
+
+  let mut transaction = c.database.begin().await.unwrap();
+  transaction.execute("INSERT INTO foo (id) VALUES (5)").await.unwrap();
+  drop(transaction);
+  assert_eq!(ids(&mut c.database).await, [1, 3]);
+}
@@ -1,9 +1,15 @@
 mod db_migration;
 mod migration_common;
 mod migration_group;
+mod migration_plan;
+mod migration_repair;
+mod migration_status;
 mod user_migration;
 
 pub use db_migration::*;
 pub(crate) use migration_common::*;
 pub use migration_group::*;
+pub use migration_plan::*;
+pub use migration_repair::*;
+pub use migration_status::*;
 pub use user_migration::*;
@@ -4,19 +4,30 @@ use crate::{
   sm::{MigrationCommon, MigrationGroup, Repeatability},
   DatabaseTy, Identifier,
 };
+use alloc::string::String;
 use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
 use core::fmt;
 
 /// Migration retrieved from a database.
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct DbMigration {
+  applied_by: Option<String>,
   common: MigrationCommon<Identifier>,
   created_on: DateTime<FixedOffset>,
   db_ty: DatabaseTy,
+  duration_ms: Option<u64>,
   group: MigrationGroup<Identifier>,
+  oapth_version: Option<String>,
+  success: bool,
 }
 
 impl DbMigration {
+  /// User and host that applied the migration, if known.
+  #[inline]
+  pub fn applied_by(&self) -> Option<&str> {
+    self.applied_by.as_deref()
+  }
+
   /// Data integrity
   #[inline]
   pub fn checksum(&self) -> u64 {
@@ -35,6 +46,12 @@ impl DbMigration {
     self.db_ty
   }
 
+  /// How long the migration took to execute, if measured.
+  #[inline]
+  pub fn duration_ms(&self) -> Option<u64> {
+    self.duration_ms
+  }
+
   /// Group
   #[inline]
   pub fn group(&self) -> &MigrationGroup<Identifier> {
@@ -47,6 +64,24 @@ impl DbMigration {
     &self.common.name
   }
 
+  /// Version of oapth that applied the migration, if known.
+  #[inline]
+  pub fn oapth_version(&self) -> Option<&str> {
+    self.oapth_version.as_deref()
+  }
+
+  /// If this is a repeatable migration, returns its type.
+  #[inline]
+  pub fn repeatability(&self) -> Option<Repeatability> {
+    self.common.repeatability
+  }
+
+  /// If the last execution of the migration succeeded.
+  #[inline]
+  pub fn success(&self) -> bool {
+    self.success
+  }
+
   /// Version
   #[inline]
   pub fn version(&self) -> i32 {
@@ -73,6 +108,7 @@ impl crate::FromRow<tiberius::Row> for DbMigration {
       };
     }
     Ok(Self {
+      applied_by: from.try_get::<&str, _>("applied_by")?.map(Into::into),
       common: MigrationCommon {
         checksum: {
           let checksum_str = translate!(from.try_get::<&str, _>("checksum"));
@@ -87,10 +123,44 @@ impl crate::FromRow<tiberius::Row> for DbMigration {
         _mssql_date_hack(s)?
       },
       db_ty: DatabaseTy::Mssql,
+      duration_ms: _duration_from_opt_i64(from.try_get("duration_ms")?),
       group: MigrationGroup::new(
         translate!(from.try_get::<&str, _>("omg_name")).try_into()?,
         translate!(from.try_get("omg_version")),
       ),
+      oapth_version: from.try_get::<&str, _>("oapth_version")?.map(Into::into),
+      success: translate!(from.try_get::<i32, _>("success")) != 0,
+    })
+  }
+}
+
+#[cfg(feature = "postgres")]
+impl crate::FromRow<crate::database::PostgresRow> for DbMigration {
+  type Error = crate::Error;
+
+  #[inline]
+  fn from_row(from: &crate::database::PostgresRow) -> Result<Self, Self::Error> {
+    let opt_i64 = |name: &str| -> crate::Result<Option<i64>> {
+      from.opt_str(name)?.map(str::parse).transpose().map_err(|_err| crate::Error::InvalidSqlQuery)
+    };
+    let req_str = |name: &str| from.opt_str(name)?.ok_or(crate::Error::InvalidSqlQuery);
+    let req_i32 = |name: &str| -> crate::Result<i32> {
+      req_str(name)?.parse().map_err(|_err| crate::Error::InvalidSqlQuery)
+    };
+    Ok(Self {
+      applied_by: from.opt_str("applied_by")?.map(Into::into),
+      common: MigrationCommon {
+        checksum: _checksum_from_str(req_str("checksum")?)?,
+        name: req_str("name")?.try_into()?,
+        repeatability: _from_opt_i32(opt_i64("repeatability")?.and_then(|el| el.try_into().ok())),
+        version: req_i32("version")?,
+      },
+      created_on: DateTime::parse_from_str(req_str("created_on")?, "%Y-%m-%d %H:%M:%S%.f%#z")?,
+      db_ty: DatabaseTy::Postgres,
+      duration_ms: _duration_from_opt_i64(opt_i64("duration_ms")?),
+      group: MigrationGroup::new(req_str("omg_name")?.try_into()?, req_i32("omg_version")?),
+      oapth_version: from.opt_str("oapth_version")?.map(Into::into),
+      success: req_i32("success")? != 0,
     })
   }
 }
@@ -99,6 +169,10 @@ fn _checksum_from_str(s: &str) -> crate::Result<u64> {
   s.parse().map_err(|_e| crate::Error::ChecksumMustBeANumber)
 }
 
+fn _duration_from_opt_i64(n: Option<i64>) -> Option<u64> {
+  n.and_then(|el| el.try_into().ok())
+}
+
 fn _fixed_from_naive_utc(naive: NaiveDateTime) -> DateTime<FixedOffset> {
   chrono::DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc).into()
 }
@@ -6,6 +6,7 @@ impl crate::FromRow<sqlx_mysql::MySqlRow> for crate::sm::DbMigration {
   fn from_row(from: &sqlx_mysql::MySqlRow) -> Result<Self, Self::Error> {
     use sqlx_core::row::Row;
     Ok(Self {
+      applied_by: from.try_get("applied_by")?,
       common: crate::sm::MigrationCommon {
         checksum: crate::sm::migration::db_migration::_checksum_from_str(
           from.try_get("checksum")?,
@@ -18,10 +19,15 @@ impl crate::FromRow<sqlx_mysql::MySqlRow> for crate::sm::DbMigration {
       },
       created_on: from.try_get::<chrono::DateTime<chrono::Utc>, _>("created_on")?.into(),
       db_ty: crate::DatabaseTy::MySql,
+      duration_ms: crate::sm::migration::db_migration::_duration_from_opt_i64(
+        from.try_get("duration_ms")?,
+      ),
       group: crate::sm::MigrationGroup::new(
         from.try_get::<&str, _>("omg_name")?.try_into()?,
         from.try_get("omg_version")?,
       ),
+      oapth_version: from.try_get("oapth_version")?,
+      success: from.try_get::<i32, _>("success")? != 0,
     })
   }
 }
@@ -34,6 +40,7 @@ impl crate::FromRow<sqlx_postgres::PgRow> for crate::sm::DbMigration {
   fn from_row(from: &sqlx_postgres::PgRow) -> Result<Self, Self::Error> {
     use sqlx_core::row::Row;
     Ok(Self {
+      applied_by: from.try_get("applied_by")?,
       common: crate::sm::MigrationCommon {
         checksum: crate::sm::migration::db_migration::_checksum_from_str(
           from.try_get("checksum")?,
@@ -46,10 +53,15 @@ impl crate::FromRow<sqlx_postgres::PgRow> for crate::sm::DbMigration {
       },
       created_on: from.try_get("created_on")?,
       db_ty: crate::DatabaseTy::Postgres,
+      duration_ms: crate::sm::migration::db_migration::_duration_from_opt_i64(
+        from.try_get("duration_ms")?,
+      ),
       group: crate::sm::MigrationGroup::new(
         from.try_get::<&str, _>("omg_name")?.try_into()?,
         from.try_get("omg_version")?,
       ),
+      oapth_version: from.try_get("oapth_version")?,
+      success: from.try_get::<i32, _>("success")? != 0,
     })
   }
 }
@@ -62,6 +74,7 @@ impl crate::FromRow<sqlx_sqlite::SqliteRow> for crate::sm::DbMigration {
   fn from_row(from: &sqlx_sqlite::SqliteRow) -> Result<Self, Self::Error> {
     use sqlx_core::row::Row;
     Ok(Self {
+      applied_by: from.try_get("applied_by")?,
       common: crate::sm::MigrationCommon {
         checksum: crate::sm::migration::db_migration::_checksum_from_str(
           from.try_get("checksum")?,
@@ -74,10 +87,15 @@ impl crate::FromRow<sqlx_sqlite::SqliteRow> for crate::sm::DbMigration {
       },
       created_on: from.try_get("created_on")?,
       db_ty: crate::DatabaseTy::Sqlite,
+      duration_ms: crate::sm::migration::db_migration::_duration_from_opt_i64(
+        from.try_get("duration_ms")?,
+      ),
       group: crate::sm::MigrationGroup::new(
         from.try_get::<&str, _>("omg_name")?.try_into()?,
         from.try_get("omg_version")?,
       ),
+      oapth_version: from.try_get("oapth_version")?,
+      success: from.try_get::<i32, _>("success")? != 0,
     })
   }
 }
@@ -0,0 +1,45 @@
+use crate::sm::{MigrationGroup, UserMigrationOwned};
+use alloc::{string::String, vec::Vec};
+
+/// Migrations of a group that are going to be applied by the next `Commands::migrate` call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationPlan {
+  group: MigrationGroup<String>,
+  migrations: Vec<UserMigrationOwned>,
+}
+
+impl MigrationPlan {
+  /// Creates a new instance from all necessary parameters.
+  #[inline]
+  pub fn new(group: MigrationGroup<String>, migrations: Vec<UserMigrationOwned>) -> Self {
+    Self { group, migrations }
+  }
+
+  /// Group
+  #[inline]
+  pub fn group(&self) -> &MigrationGroup<String> {
+    &self.group
+  }
+
+  /// If there is nothing to be applied.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.migrations.is_empty()
+  }
+
+  /// Pending migrations sorted by version followed by repeatable migrations that are going to be
+  /// executed again.
+  #[inline]
+  pub fn migrations(&self) -> &[UserMigrationOwned] {
+    &self.migrations
+  }
+
+  /// Writes the SQL of all planned migrations in the same order they would be sent to the
+  /// database.
+  #[inline]
+  pub fn write_sql(&self, buffer_cmd: &mut String) {
+    for migration in &self.migrations {
+      crate::sm::fixed_sql_commands::_push_sql_up(buffer_cmd, migration);
+    }
+  }
+}
@@ -0,0 +1,98 @@
+use crate::sm::MigrationGroup;
+use alloc::{string::String, vec::Vec};
+
+/// Stored migration whose name or checksum was realigned with its local file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationRepairEntry {
+  new_checksum: u64,
+  new_name: String,
+  old_checksum: u64,
+  old_name: String,
+  version: i32,
+}
+
+impl MigrationRepairEntry {
+  /// Creates a new instance from all necessary parameters.
+  #[inline]
+  pub fn new(
+    (old_checksum, new_checksum): (u64, u64),
+    (old_name, new_name): (String, String),
+    version: i32,
+  ) -> Self {
+    Self { new_checksum, new_name, old_checksum, old_name, version }
+  }
+
+  /// Checksum after the repair
+  #[inline]
+  pub fn new_checksum(&self) -> u64 {
+    self.new_checksum
+  }
+
+  /// Name after the repair
+  #[inline]
+  pub fn new_name(&self) -> &str {
+    &self.new_name
+  }
+
+  /// Checksum before the repair
+  #[inline]
+  pub fn old_checksum(&self) -> u64 {
+    self.old_checksum
+  }
+
+  /// Name before the repair
+  #[inline]
+  pub fn old_name(&self) -> &str {
+    &self.old_name
+  }
+
+  /// Version
+  #[inline]
+  pub fn version(&self) -> i32 {
+    self.version
+  }
+}
+
+/// Everything that `Commands::repair` modified in a group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationRepair {
+  deleted_failed: u64,
+  entries: Vec<MigrationRepairEntry>,
+  group: MigrationGroup<String>,
+}
+
+impl MigrationRepair {
+  /// Creates a new instance from all necessary parameters.
+  #[inline]
+  pub fn new(
+    deleted_failed: u64,
+    entries: Vec<MigrationRepairEntry>,
+    group: MigrationGroup<String>,
+  ) -> Self {
+    Self { deleted_failed, entries, group }
+  }
+
+  /// Number of removed records that were left behind by failed executions.
+  #[inline]
+  pub fn deleted_failed(&self) -> u64 {
+    self.deleted_failed
+  }
+
+  /// Realigned migrations, sorted by version.
+  #[inline]
+  pub fn entries(&self) -> &[MigrationRepairEntry] {
+    &self.entries
+  }
+
+  /// Group
+  #[inline]
+  pub fn group(&self) -> &MigrationGroup<String> {
+    &self.group
+  }
+
+  /// If nothing was modified.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.deleted_failed == 0 && self.entries.is_empty()
+  }
+}
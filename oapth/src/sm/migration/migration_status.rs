@@ -0,0 +1,127 @@
+use crate::sm::MigrationGroup;
+use alloc::{string::String, vec::Vec};
+use chrono::{DateTime, FixedOffset};
+
+/// State of a migration when local files are compared with the database.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MigrationStatus {
+  /// Stored in the database and equal to its local file
+  Applied,
+  /// Stored in the database but its local file has a different content
+  ChecksumMismatch,
+  /// Stored in the database but there is no local file with the same version
+  MissingFromDisk,
+  /// Stored in the database but its local file has a different name
+  NameMismatch,
+  /// Local file that wasn't applied yet
+  Pending,
+  /// Applied repeatable migration that is going to be executed again
+  RepeatableRerunNeeded,
+}
+
+impl MigrationStatus {
+  /// Canonical string representation
+  #[inline]
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::Applied => "applied",
+      Self::ChecksumMismatch => "checksum mismatch",
+      Self::MissingFromDisk => "missing from disk",
+      Self::NameMismatch => "name mismatch",
+      Self::Pending => "pending",
+      Self::RepeatableRerunNeeded => "repeatable rerun needed",
+    }
+  }
+
+  /// If this status prevents `Commands::migrate` from running.
+  #[inline]
+  pub const fn is_divergent(&self) -> bool {
+    matches!(self, Self::ChecksumMismatch | Self::MissingFromDisk | Self::NameMismatch)
+  }
+}
+
+impl core::fmt::Display for MigrationStatus {
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/// Status of a single migration.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationStatusEntry {
+  created_on: Option<DateTime<FixedOffset>>,
+  name: String,
+  status: MigrationStatus,
+  version: i32,
+}
+
+impl MigrationStatusEntry {
+  /// Creates a new instance from all necessary parameters.
+  #[inline]
+  pub fn new(
+    created_on: Option<DateTime<FixedOffset>>,
+    name: String,
+    status: MigrationStatus,
+    version: i32,
+  ) -> Self {
+    Self { created_on, name, status, version }
+  }
+
+  /// When the migration was applied. `None` if the migration isn't stored in the database.
+  #[inline]
+  pub fn created_on(&self) -> Option<&DateTime<FixedOffset>> {
+    self.created_on.as_ref()
+  }
+
+  /// Name of the local file or, if missing, the name stored in the database.
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// See [MigrationStatus].
+  #[inline]
+  pub fn status(&self) -> MigrationStatus {
+    self.status
+  }
+
+  /// Version
+  #[inline]
+  pub fn version(&self) -> i32 {
+    self.version
+  }
+}
+
+/// Status of all migrations of a group, sorted by version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationStatusReport {
+  entries: Vec<MigrationStatusEntry>,
+  group: MigrationGroup<String>,
+}
+
+impl MigrationStatusReport {
+  /// Creates a new instance from all necessary parameters.
+  #[inline]
+  pub fn new(entries: Vec<MigrationStatusEntry>, group: MigrationGroup<String>) -> Self {
+    Self { entries, group }
+  }
+
+  /// Migration entries
+  #[inline]
+  pub fn entries(&self) -> &[MigrationStatusEntry] {
+    &self.entries
+  }
+
+  /// Group
+  #[inline]
+  pub fn group(&self) -> &MigrationGroup<String> {
+    &self.group
+  }
+
+  /// If at least one entry has a status that prevents `Commands::migrate` from running.
+  #[inline]
+  pub fn has_divergences(&self) -> bool {
+    self.entries.iter().any(|entry| entry.status.is_divergent())
+  }
+}
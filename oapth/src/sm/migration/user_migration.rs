@@ -26,6 +26,7 @@ pub struct UserMigration<DBS, S> {
   dbs: DBS,
   sql_down: S,
   sql_up: S,
+  transactional: bool,
 }
 
 impl<DBS, S> UserMigration<DBS, S>
@@ -34,6 +35,7 @@ where
   S: AsRef<str>,
 {
   /// Creates a new instance from all necessary parameters, including internal ones.
+  #[allow(clippy::too_many_arguments)]
   #[inline]
   pub const fn from_all_parts(
     checksum: u64,
@@ -42,6 +44,7 @@ where
     repeatability: Option<Repeatability>,
     sql_down: S,
     sql_up: S,
+    transactional: bool,
     version: i32,
   ) -> Self {
     Self {
@@ -49,6 +52,7 @@ where
       common: MigrationCommon { checksum, name, repeatability, version },
       sql_down,
       sql_up,
+      transactional,
     }
   }
 
@@ -59,6 +63,7 @@ where
     name: S,
     repeatability: Option<Repeatability>,
     [sql_up, sql_down]: [S; 2],
+    transactional: bool,
     version: i32,
   ) -> crate::Result<Self> {
     is_sorted_and_unique(dbs.as_ref())?;
@@ -68,6 +73,7 @@ where
       common: MigrationCommon { checksum, name, repeatability, version },
       sql_down,
       sql_up,
+      transactional,
     })
   }
 
@@ -153,6 +159,20 @@ where
     self.sql_up.as_ref()
   }
 
+  /// If the migration can be executed inside a transaction. Statements like
+  /// `CREATE INDEX CONCURRENTLY` must opt-out.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use oapth::sm::doc_tests::migration;
+  /// assert!(migration().transactional())
+  /// ```
+  #[inline]
+  pub fn transactional(&self) -> bool {
+    self.transactional
+  }
+
   /// UserMigration version
   ///
   /// # Example
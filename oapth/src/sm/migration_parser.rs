@@ -2,7 +2,7 @@
 
 use crate::{
   sm::{
-    toml_parser::{toml, Expr},
+    toml_parser::{toml, Value},
     Repeatability,
   },
   DatabaseTy,
@@ -11,12 +11,21 @@ use arrayvec::ArrayVec;
 use std::io::{BufRead, BufReader, Read};
 
 /// Auxiliary parameters of a migration file
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct MigrationCfg {
   /// All unique declared databases
   pub dbs: ArrayVec<DatabaseTy, { DatabaseTy::len() }>,
   /// Declared repeatability
   pub repeatability: Option<Repeatability>,
+  /// If the migration can be executed inside a transaction. Defaults to `true`.
+  pub transactional: bool,
+}
+
+impl Default for MigrationCfg {
+  #[inline]
+  fn default() -> Self {
+    Self { dbs: ArrayVec::new(), repeatability: None, transactional: true }
+  }
 }
 
 /// In-memory representation of a parsed migration file
@@ -61,6 +70,13 @@ where
     iterations(&mut overall_buffer, &mut br, |_| false)?;
   }
 
+  if let Some(rslt) = overall_buffer.split("-- oapth transactional").nth(1) {
+    if let Ok(transactional) = rslt.trim().parse() {
+      parsed_migration.cfg.transactional = transactional;
+    }
+    iterations(&mut overall_buffer, &mut br, |_| false)?;
+  }
+
   if !overall_buffer.contains("-- oapth UP") {
     return Err(crate::Error::IncompleteSqlFile);
   }
@@ -91,25 +107,36 @@ pub(crate) fn parse_migration_toml<R>(read: R) -> crate::Result<MigrationCfg>
 where
   R: Read,
 {
-  let mut migration_toml = MigrationCfg { dbs: ArrayVec::new(), repeatability: None };
-
-  for (ident, toml_expr) in toml(read)? {
-    match (ident.as_ref(), toml_expr) {
-      ("dbs", Expr::Array(array)) => {
-        for s in array {
-          let Ok(elem) = s.parse() else {
+  let mut migration_toml = MigrationCfg::default();
+
+  for (key, value) in toml(read)?.entries {
+    match (key.name.as_str(), value) {
+      ("dbs", Value::Array(array)) => {
+        for elem in array {
+          let Value::String(s) = elem else {
+            return key.invalid("`dbs` must be an array of strings");
+          };
+          let Ok(db) = s.parse() else {
             continue;
           };
-          migration_toml.dbs.try_push(elem)?;
+          if !migration_toml.dbs.contains(&db) {
+            migration_toml.dbs.try_push(db)?;
+          }
         }
       }
-      ("repeatability", Expr::String(s)) => {
+      ("dbs", _) => return key.invalid("`dbs` must be an array of strings"),
+      ("repeatability", Value::String(s)) => {
         let Ok(elem) = s.parse() else {
           continue;
         };
         migration_toml.repeatability = Some(elem);
       }
-      _ => {}
+      ("repeatability", _) => return key.invalid("`repeatability` must be a string"),
+      ("transactional", Value::Boolean(elem)) => {
+        migration_toml.transactional = elem;
+      }
+      ("transactional", _) => return key.invalid("`transactional` must be a boolean"),
+      _ => return key.unknown(),
     }
   }
 
@@ -156,7 +183,10 @@ where
 #[cfg(test)]
 mod tests {
   use crate::{
-    sm::{migration_parser::parse_unified_migration, Repeatability},
+    sm::{
+      migration_parser::{parse_migration_toml, parse_unified_migration},
+      Repeatability,
+    },
     DatabaseTy,
   };
 
@@ -223,6 +253,44 @@ mod tests {
     assert_eq!(on_checksum_change.cfg.repeatability, Some(Repeatability::OnChecksumChange));
   }
 
+  #[test]
+  fn parses_transactional() {
+    let s = "-- oapth UP\nSOMETHING";
+    let no_declaration = parse_unified_migration(s.as_bytes()).unwrap();
+    assert!(no_declaration.cfg.transactional);
+
+    let s = "-- oapth transactional FOO\n-- oapth UP\nSOMETHING";
+    let with_incorrect_declaration = parse_unified_migration(s.as_bytes()).unwrap();
+    assert!(with_incorrect_declaration.cfg.transactional);
+
+    let s = "-- oapth repeatability always\n-- oapth transactional false\n-- oapth UP\nSOMETHING";
+    let not_transactional = parse_unified_migration(s.as_bytes()).unwrap();
+    assert_eq!(not_transactional.cfg.repeatability, Some(Repeatability::Always));
+    assert!(!not_transactional.cfg.transactional);
+  }
+
+  #[test]
+  fn parses_migration_toml() {
+    let s = "dbs = [\"mssql\", \"mssql\", \"postgres\"] # Comment\nrepeatability = \"always\"\n";
+    let cfg = parse_migration_toml(format!("{s}transactional = false").as_bytes()).unwrap();
+    assert_eq!(cfg.dbs.as_slice(), [DatabaseTy::Mssql, DatabaseTy::Postgres]);
+    assert_eq!(cfg.repeatability, Some(Repeatability::Always));
+    assert!(!cfg.transactional);
+
+    let rslt = parse_migration_toml("transactional = \"false\"".as_bytes());
+    assert!(matches!(rslt, Err(crate::Error::InvalidToml(_, 1, 1))));
+
+    let Err(crate::Error::UnknownTomlKey(key, 2, 3)) =
+      parse_migration_toml("dbs = []\n  foo = 1".as_bytes())
+    else {
+      panic!();
+    };
+    assert_eq!(key, "foo");
+
+    let rslt = parse_migration_toml("\ndbs = \"postgres\"".as_bytes());
+    assert!(matches!(rslt, Err(crate::Error::InvalidToml(_, 2, 1))));
+  }
+
   #[test]
   fn parses_mandatory_params() {
     let s = "-- oapth UP\n\nSOMETHING\nFOO";
@@ -1,215 +1,854 @@
 //! Migration TOML parser
+//!
+//! Implements the TOML v1.0.0 specification. Dates and times are validated but kept as strings
+//! because no configuration parameter needs them. Every key stores the position where it was
+//! declared so that callers can report unexpected entries.
 
-use arrayvec::{ArrayString, ArrayVec};
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 
-pub(crate) const EXPR_ARRAY_MAX_LEN: usize = 8;
+// Arrays and inline tables are parsed recursively, which means that deeply nested values could
+// otherwise exhaust the stack.
+const MAX_DEPTH: usize = 64;
 
-pub(crate) type ExprArrayTy = ArrayVec<ExprStringTy, EXPR_ARRAY_MAX_LEN>;
-pub(crate) type ExprStringTy = ArrayString<128>;
-pub(crate) type IdentTy = ArrayString<64>;
-pub(crate) type RootParamsTy = ArrayVec<(IdentTy, Expr), 2>;
+/// Key of a table with its 1-based position in the document
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Key {
+  pub(crate) column: usize,
+  pub(crate) line: usize,
+  pub(crate) name: String,
+}
+
+impl Key {
+  #[inline]
+  pub(crate) fn invalid<T>(&self, msg: &'static str) -> crate::Result<T> {
+    Err(crate::Error::InvalidToml(msg, self.line, self.column))
+  }
+
+  #[inline]
+  pub(crate) fn unknown<T>(&self) -> crate::Result<T> {
+    Err(crate::Error::UnknownTomlKey(self.name.clone(), self.line, self.column))
+  }
+}
+
+/// Entries of a table in declaration order
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Table {
+  pub(crate) entries: Vec<(Key, Value)>,
+  origin: TableOrigin,
+}
+
+impl Table {
+  #[inline]
+  fn new(origin: TableOrigin) -> Self {
+    Self { entries: Vec::new(), origin }
+  }
+}
 
-#[allow(clippy::large_enum_variant, variant_size_differences)]
 #[derive(Debug, PartialEq)]
-pub(crate) enum Expr {
-  Array(ExprArrayTy),
-  String(ExprStringTy),
+pub(crate) enum Value {
+  Array(Vec<Value>),
+  ArrayOfTables(Vec<Table>),
+  Boolean(bool),
+  Datetime(String),
+  Float(f64),
+  Integer(i64),
+  String(String),
+  Table(Table),
+}
+
+// How a table was created dictates how it can be extended afterwards.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum TableOrigin {
+  // Dotted keys
+  Dotted,
+  // `[table]` or `[[table]]` headers
+  Header,
+  // Parents of headers or the root table
+  #[default]
+  Implicit,
+  // `{ .. }` values
+  Inline,
 }
 
 #[inline]
-pub(crate) fn toml<R>(read: R) -> crate::Result<RootParamsTy>
+pub(crate) fn toml<R>(mut read: R) -> crate::Result<Table>
 where
   R: Read,
 {
-  let mut br = BufReader::new(read);
-  let mut is_in_array_context = None;
-  let mut buffer = String::new();
-  let mut root_params = ArrayVec::new();
-
-  macro_rules! clear_and_continue {
-    () => {
-      buffer.clear();
-      continue;
-    };
+  let mut input = String::new();
+  let _ = read.read_to_string(&mut input)?;
+  Parser { depth: 0, idx: 0, input: &input, line: 1, line_start: 0 }.document()
+}
+
+struct Parser<'input> {
+  depth: usize,
+  idx: usize,
+  input: &'input str,
+  line: usize,
+  line_start: usize,
+}
+
+impl<'input> Parser<'input> {
+  #[inline]
+  fn array(&mut self) -> crate::Result<Value> {
+    let _ = self.bump();
+    let mut values = Vec::new();
+    loop {
+      self.blank_lines()?;
+      if self.eat(']') {
+        break;
+      }
+      values.push(self.value()?);
+      self.blank_lines()?;
+      if self.eat(',') {
+        continue;
+      }
+      if self.eat(']') {
+        break;
+      }
+      return self.err("Expected `,` or `]` after an array element");
+    }
+    Ok(Value::Array(values))
   }
 
-  loop {
-    if br.read_line(&mut buffer)? == 0 {
-      break;
+  #[inline]
+  fn basic_string(&mut self, is_multiline: bool) -> crate::Result<String> {
+    let mut string = String::new();
+    if is_multiline {
+      let _ = self.newline();
     }
+    loop {
+      if is_multiline && self.rest().starts_with("\"\"\"") {
+        self.closing_quotes('"', &mut string)?;
+        return Ok(string);
+      }
+      if !is_multiline && self.is_line_end() {
+        return self.err("Unterminated string");
+      }
+      let Some(c) = self.bump() else {
+        return self.err("Unterminated string");
+      };
+      match c {
+        '"' if !is_multiline => return Ok(string),
+        '\\' if is_multiline && self.is_line_ending_backslash() => {
+          while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            if self.peek() == Some('\r') && !self.rest().starts_with("\r\n") {
+              return self.err("Carriage returns must be followed by a new line");
+            }
+            let _ = self.bump();
+          }
+        }
+        '\\' => string.push(self.escape()?),
+        '\n' if is_multiline => string.push(c),
+        '\r' if is_multiline && self.peek() == Some('\n') => string.push(c),
+        _ if is_control(c) => return self.err("Control characters must be escaped"),
+        _ => string.push(c),
+      }
+    }
+  }
 
-    let buffer_ref = buffer.trim();
+  // Whitespaces, comments and new lines.
+  #[inline]
+  fn blank_lines(&mut self) -> crate::Result<()> {
+    loop {
+      self.whitespaces();
+      self.comment()?;
+      if !self.newline() {
+        return Ok(());
+      }
+    }
+  }
 
-    if buffer_ref.starts_with('#') {
-      clear_and_continue!();
+  #[inline]
+  fn bump(&mut self) -> Option<char> {
+    let c = self.peek()?;
+    self.idx = self.idx.wrapping_add(c.len_utf8());
+    if c == '\n' {
+      self.line = self.line.wrapping_add(1);
+      self.line_start = self.idx;
+    }
+    Some(c)
+  }
+
+  // Up to two quotes can be placed right before the closing delimiter of multi-line strings.
+  #[inline]
+  fn closing_quotes(&mut self, quote: char, string: &mut String) -> crate::Result<()> {
+    let len = self.rest().chars().take_while(|el| *el == quote).count();
+    if len > 5 {
+      return self.err("Too many quotes at the end of a multi-line string");
+    }
+    for _ in 3..len {
+      string.push(quote);
     }
+    for _ in 0..len {
+      let _ = self.bump();
+    }
+    Ok(())
+  }
+
+  #[inline]
+  fn column(&self) -> usize {
+    let line = self.input.get(self.line_start..self.idx).unwrap_or_default();
+    line.chars().count().wrapping_add(1)
+  }
 
-    if let Some(ident) = is_in_array_context.as_ref() {
-      if buffer_ref.ends_with(']') {
-        let inner = buffer_ref.get(0..buffer_ref.len().saturating_sub(1)).unwrap_or_default();
-        try_parse_and_push_toml_expr_array(inner, *ident, &mut root_params)?;
-        is_in_array_context = None;
-        buffer.clear();
+  #[inline]
+  fn comment(&mut self) -> crate::Result<()> {
+    if !self.eat('#') {
+      return Ok(());
+    }
+    while let Some(c) = self.peek() {
+      if self.is_line_end() {
+        break;
+      }
+      if is_control(c) {
+        return self.err("Control characters aren't allowed in comments");
       }
-      continue;
+      let _ = self.bump();
     }
+    Ok(())
+  }
 
-    let mut root_param_iter = buffer_ref.split('=');
+  #[inline]
+  fn document(mut self) -> crate::Result<Table> {
+    let mut root = Table::default();
+    let mut path = Vec::new();
+    let _ = self.eat('\u{feff}');
+    loop {
+      self.blank_lines()?;
+      if self.peek().is_none() {
+        break;
+      }
+      if self.peek() == Some('[') {
+        path = self.table_header(&mut root)?;
+      } else {
+        let Some(table) = table_at(&mut root, &path) else {
+          return self.err("Table couldn't be found");
+        };
+        self.key_value(table)?;
+      }
+      self.whitespaces();
+      self.comment()?;
+      if self.peek().is_some() && !self.newline() {
+        return self.err("Expected a new line after an expression");
+      }
+    }
+    Ok(root)
+  }
 
-    let ident = if let Some(el) = root_param_iter.next() {
-      el.trim().try_into().map_err(|_err| crate::Error::TomlValueIsTooLarge)?
+  #[inline]
+  fn eat(&mut self, c: char) -> bool {
+    if self.peek() == Some(c) {
+      let _ = self.bump();
+      true
     } else {
-      clear_and_continue!();
+      false
+    }
+  }
+
+  #[inline]
+  fn err<T>(&self, msg: &'static str) -> crate::Result<T> {
+    Err(crate::Error::InvalidToml(msg, self.line, self.column()))
+  }
+
+  #[inline]
+  fn escape(&mut self) -> crate::Result<char> {
+    let len = match self.bump() {
+      Some('b') => return Ok('\u{8}'),
+      Some('f') => return Ok('\u{c}'),
+      Some('n') => return Ok('\n'),
+      Some('r') => return Ok('\r'),
+      Some('t') => return Ok('\t'),
+      Some('"') => return Ok('"'),
+      Some('\\') => return Ok('\\'),
+      Some('u') => 4,
+      Some('U') => 8,
+      _ => return self.err("Invalid escape sequence"),
+    };
+    let hex = self.rest().get(..len).unwrap_or_default();
+    let opt = hex
+      .bytes()
+      .all(|el| el.is_ascii_hexdigit())
+      .then(|| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32))
+      .flatten();
+    let Some(c) = opt.filter(|_| hex.len() == len) else {
+      return self.err("Invalid unicode escape sequence");
+    };
+    for _ in 0..len {
+      let _ = self.bump();
+    }
+    Ok(c)
+  }
+
+  #[inline]
+  fn inline_table(&mut self) -> crate::Result<Value> {
+    let _ = self.bump();
+    let mut table = Table::new(TableOrigin::Inline);
+    self.whitespaces();
+    if self.eat('}') {
+      return Ok(Value::Table(table));
+    }
+    loop {
+      self.whitespaces();
+      self.key_value(&mut table)?;
+      self.whitespaces();
+      if self.eat(',') {
+        continue;
+      }
+      if self.eat('}') {
+        break;
+      }
+      return self.err("Expected `,` or `}` after an inline table entry");
+    }
+    Ok(Value::Table(table))
+  }
+
+  #[inline]
+  fn is_line_end(&self) -> bool {
+    let rest = self.rest();
+    rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n")
+  }
+
+  #[inline]
+  fn is_line_ending_backslash(&self) -> bool {
+    let rest = self.rest().trim_start_matches([' ', '\t']);
+    rest.starts_with('\n') || rest.starts_with("\r\n")
+  }
+
+  #[inline]
+  fn key(&mut self) -> crate::Result<Key> {
+    let (column, line) = (self.column(), self.line);
+    let name = match self.peek() {
+      Some('"') => {
+        let _ = self.bump();
+        self.basic_string(false)?
+      }
+      Some('\'') => {
+        let _ = self.bump();
+        self.literal_string(false)?
+      }
+      _ => {
+        let rest = self.rest();
+        let len = rest
+          .bytes()
+          .take_while(|el| el.is_ascii_alphanumeric() || *el == b'-' || *el == b'_')
+          .count();
+        if len == 0 {
+          return self.err("Expected a key");
+        }
+        let name = rest.get(..len).unwrap_or_default().into();
+        self.idx = self.idx.wrapping_add(len);
+        name
+      }
+    };
+    Ok(Key { column, line, name })
+  }
+
+  #[inline]
+  fn keys(&mut self) -> crate::Result<Vec<Key>> {
+    let mut keys = Vec::new();
+    loop {
+      keys.push(self.key()?);
+      self.whitespaces();
+      if !self.eat('.') {
+        return Ok(keys);
+      }
+      self.whitespaces();
+    }
+  }
+
+  #[inline]
+  fn key_value(&mut self, table: &mut Table) -> crate::Result<()> {
+    let keys = self.keys()?;
+    if !self.eat('=') {
+      return self.err("Expected `=` after a key");
+    }
+    self.whitespaces();
+    let value = self.value()?;
+    let Some((last, parents)) = keys.split_last() else {
+      return self.err("Expected a key");
     };
+    let mut current = table;
+    for key in parents {
+      current = match entry(current, key, || Value::Table(Table::new(TableOrigin::Dotted))) {
+        Some(Value::Table(elem)) if elem.origin == TableOrigin::Dotted => elem,
+        _ => return key.invalid("Key is already defined"),
+      };
+    }
+    if current.entries.iter().any(|el| el.0.name == last.name) {
+      return last.invalid("Key is already defined");
+    }
+    current.entries.push((last.clone(), value));
+    Ok(())
+  }
 
-    let expr_raw = if let Some(el) = root_param_iter.next() {
-      el.trim()
+  #[inline]
+  fn literal_string(&mut self, is_multiline: bool) -> crate::Result<String> {
+    let mut string = String::new();
+    if is_multiline {
+      let _ = self.newline();
+    }
+    loop {
+      if is_multiline && self.rest().starts_with("'''") {
+        self.closing_quotes('\'', &mut string)?;
+        return Ok(string);
+      }
+      if !is_multiline && self.is_line_end() {
+        return self.err("Unterminated string");
+      }
+      let Some(c) = self.bump() else {
+        return self.err("Unterminated string");
+      };
+      match c {
+        '\'' if !is_multiline => return Ok(string),
+        '\n' if is_multiline => string.push(c),
+        '\r' if is_multiline && self.peek() == Some('\n') => string.push(c),
+        _ if is_control(c) => {
+          return self.err("Control characters aren't allowed in literal strings")
+        }
+        _ => string.push(c),
+      }
+    }
+  }
+
+  #[inline]
+  fn nested(&mut self, cb: fn(&mut Self) -> crate::Result<Value>) -> crate::Result<Value> {
+    if self.depth >= MAX_DEPTH {
+      return self.err("Arrays and inline tables are nested too deeply");
+    }
+    self.depth = self.depth.wrapping_add(1);
+    let rslt = cb(self);
+    self.depth = self.depth.wrapping_sub(1);
+    rslt
+  }
+
+  #[inline]
+  fn newline(&mut self) -> bool {
+    if self.rest().starts_with("\r\n") {
+      let _ = self.bump();
+    }
+    self.eat('\n')
+  }
+
+  // Numbers, dates and times.
+  #[inline]
+  fn number_or_datetime(&mut self) -> crate::Result<Value> {
+    let is_token =
+      |el: &u8| el.is_ascii_alphanumeric() || matches!(*el, b'+' | b'-' | b'.' | b':' | b'_');
+    let rest = self.rest();
+    let mut len = rest.bytes().take_while(is_token).count();
+    // Dates and times can also be separated by a space
+    let after_date = rest.get(len..).unwrap_or_default();
+    if len == 10 && date(rest).is_some() && after_date.starts_with(' ') {
+      let time_len = after_date.bytes().skip(1).take_while(is_token).count();
+      if time_len > 0 {
+        len = len.wrapping_add(time_len).wrapping_add(1);
+      }
+    }
+    let token = rest.get(..len).unwrap_or_default();
+    let value = if is_datetime(token) {
+      Value::Datetime(token.into())
+    } else if let Some(elem) = integer(token) {
+      Value::Integer(elem.map_err(|_err| {
+        crate::Error::InvalidToml("Integer is out of range", self.line, self.column())
+      })?)
+    } else if let Some(elem) = float(token) {
+      Value::Float(elem)
     } else {
-      clear_and_continue!();
+      return self.err("Invalid value");
     };
+    self.idx = self.idx.wrapping_add(len);
+    Ok(value)
+  }
+
+  #[inline]
+  fn peek(&self) -> Option<char> {
+    self.rest().chars().next()
+  }
+
+  #[inline]
+  fn rest(&self) -> &'input str {
+    self.input.get(self.idx..).unwrap_or_default()
+  }
 
-    if expr_raw.starts_with('[') {
-      if expr_raw.ends_with(']') {
-        let inner = expr_raw.get(1..expr_raw.len().saturating_sub(1)).unwrap_or_default();
-        try_parse_and_push_toml_expr_array(inner, ident, &mut root_params)?;
+  #[inline]
+  fn table_header(&mut self, root: &mut Table) -> crate::Result<Vec<String>> {
+    let _ = self.bump();
+    let is_array = self.eat('[');
+    self.whitespaces();
+    let keys = self.keys()?;
+    if !self.eat(']') || (is_array && !self.eat(']')) {
+      return self.err("Expected `]` after a table header");
+    }
+    let Some((last, parents)) = keys.split_last() else {
+      return self.err("Expected a key");
+    };
+    let mut current = root;
+    for key in parents {
+      current = match entry(current, key, || Value::Table(Table::new(TableOrigin::Implicit))) {
+        Some(Value::ArrayOfTables(elem)) => match elem.last_mut() {
+          Some(table) => table,
+          None => return key.invalid("Array of tables is empty"),
+        },
+        Some(Value::Table(elem)) if elem.origin != TableOrigin::Inline => elem,
+        Some(Value::Table(_)) => return key.invalid("Inline tables can't be extended"),
+        _ => return key.invalid("Key is already defined with a value that isn't a table"),
+      };
+    }
+    let mut was_created = false;
+    let value = entry(current, last, || {
+      was_created = true;
+      if is_array {
+        Value::ArrayOfTables(Vec::new())
       } else {
-        is_in_array_context = Some(ident);
+        Value::Table(Table::new(TableOrigin::Header))
       }
-    } else {
-      try_parse_and_push_toml_expr_string(expr_raw, ident, &mut root_params)?;
+    });
+    match (is_array, value) {
+      (true, Some(Value::ArrayOfTables(elem))) => elem.push(Table::new(TableOrigin::Header)),
+      (false, Some(Value::Table(elem))) if was_created || elem.origin == TableOrigin::Implicit => {
+        elem.origin = TableOrigin::Header;
+      }
+      _ => return last.invalid("Table is already defined"),
+    }
+    Ok(keys.into_iter().map(|el| el.name).collect())
+  }
+
+  #[inline]
+  fn value(&mut self) -> crate::Result<Value> {
+    let rest = self.rest();
+    match self.peek() {
+      Some('"') if rest.starts_with("\"\"\"") => {
+        let _ = (self.bump(), self.bump(), self.bump());
+        Ok(Value::String(self.basic_string(true)?))
+      }
+      Some('"') => {
+        let _ = self.bump();
+        Ok(Value::String(self.basic_string(false)?))
+      }
+      Some('\'') if rest.starts_with("'''") => {
+        let _ = (self.bump(), self.bump(), self.bump());
+        Ok(Value::String(self.literal_string(true)?))
+      }
+      Some('\'') => {
+        let _ = self.bump();
+        Ok(Value::String(self.literal_string(false)?))
+      }
+      Some('[') => self.nested(Self::array),
+      Some('{') => self.nested(Self::inline_table),
+      Some(_) if starts_with_word(rest, "false") => {
+        self.idx = self.idx.wrapping_add(5);
+        Ok(Value::Boolean(false))
+      }
+      Some(_) if starts_with_word(rest, "true") => {
+        self.idx = self.idx.wrapping_add(4);
+        Ok(Value::Boolean(true))
+      }
+      Some('+' | '-' | '0'..='9' | 'i' | 'n') => self.number_or_datetime(),
+      _ => self.err("Invalid value"),
     }
+  }
+
+  #[inline]
+  fn whitespaces(&mut self) {
+    while matches!(self.peek(), Some(' ' | '\t')) {
+      let _ = self.bump();
+    }
+  }
+}
+
+#[inline]
+fn date(s: &str) -> Option<&str> {
+  let year: u32 = fixed_digits(s, 4)?.parse().ok()?;
+  let month: u32 = fixed_digits(s.get(4..)?.strip_prefix('-')?, 2)?.parse().ok()?;
+  let day: u32 = fixed_digits(s.get(7..)?.strip_prefix('-')?, 2)?.parse().ok()?;
+  let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+  let max_day = match month {
+    2 if is_leap_year => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+    _ => return None,
+  };
+  (1..=max_day).contains(&day).then(|| s.get(10..)).flatten()
+}
 
-    buffer.clear();
+// Returns the existing value of `key` or inserts a new one.
+#[inline]
+fn entry<'table>(
+  table: &'table mut Table,
+  key: &Key,
+  cb: impl FnOnce() -> Value,
+) -> Option<&'table mut Value> {
+  if !table.entries.iter().any(|el| el.0.name == key.name) {
+    table.entries.push((key.clone(), cb()));
   }
+  table.entries.iter_mut().find(|el| el.0.name == key.name).map(|el| &mut el.1)
+}
+
+#[inline]
+fn fixed_digits(s: &str, len: usize) -> Option<&str> {
+  let digits = s.get(..len)?;
+  digits.bytes().all(|el| el.is_ascii_digit()).then_some(digits)
+}
+
+#[inline]
+fn float(s: &str) -> Option<f64> {
+  let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+  if unsigned == "inf" || unsigned == "nan" {
+    let value = if unsigned == "inf" { f64::INFINITY } else { f64::NAN };
+    return Some(if s.starts_with('-') { -value } else { value });
+  }
+  let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+    Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+    None => (unsigned, None),
+  };
+  let (int, frac) = match mantissa.split_once('.') {
+    Some((int, frac)) => (int, Some(frac)),
+    None => (mantissa, None),
+  };
+  let is_valid = is_decimal_int(int)
+    && (frac.is_some() || exponent.is_some())
+    && frac.map_or(true, is_digits)
+    && exponent.map_or(true, |el| is_digits(el.strip_prefix(['+', '-']).unwrap_or(el)));
+  if !is_valid {
+    return None;
+  }
+  s.replace('_', "").parse().ok()
+}
 
-  Ok(root_params)
+#[inline]
+fn integer(s: &str) -> Option<Result<i64, core::num::ParseIntError>> {
+  let (radix, digits) = if let Some(elem) = s.strip_prefix("0x") {
+    (16, elem)
+  } else if let Some(elem) = s.strip_prefix("0o") {
+    (8, elem)
+  } else if let Some(elem) = s.strip_prefix("0b") {
+    (2, elem)
+  } else {
+    let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+    return is_decimal_int(unsigned).then(|| s.replace('_', "").parse());
+  };
+  let is_valid = !digits.is_empty()
+    && digits.split('_').all(|el| !el.is_empty() && el.chars().all(|c| c.is_digit(radix)));
+  is_valid.then(|| i64::from_str_radix(&digits.replace('_', ""), radix))
+}
+
+#[inline]
+fn is_control(c: char) -> bool {
+  c != '\t' && (c <= '\u{1f}' || c == '\u{7f}')
 }
 
 #[inline]
-fn try_parse_expr_array(s: &str) -> crate::Result<ExprArrayTy> {
-  let mut array = ArrayVec::new();
-  if s.is_empty() {
-    return Ok(array);
+fn is_datetime(s: &str) -> bool {
+  let Some(rest) = date(s) else {
+    return time(s) == Some("");
+  };
+  if rest.is_empty() {
+    return true;
   }
-  for elem in s.split(',') {
-    let expr_string = try_parse_expr_string(elem.trim())?;
-    array.try_push(expr_string).map_err(|_err| crate::Error::TomlValueIsTooLarge)?;
+  let Some(rest) = rest.strip_prefix(['T', 't', ' ']).and_then(time) else {
+    return false;
+  };
+  if rest.is_empty() || rest == "Z" || rest == "z" {
+    return true;
   }
-  Ok(array)
+  let Some(offset) = rest.strip_prefix(['+', '-']) else {
+    return false;
+  };
+  offset.len() == 5
+    && time_component(offset, 23).and_then(|el| time_component(el.strip_prefix(':')?, 59))
+      == Some("")
+}
+
+// Integers without leading zeros where underscores must be surrounded by digits.
+#[inline]
+fn is_decimal_int(s: &str) -> bool {
+  is_digits(s) && (s == "0" || !s.starts_with('0'))
+}
+
+#[inline]
+fn is_digits(s: &str) -> bool {
+  !s.is_empty() && s.split('_').all(|el| !el.is_empty() && el.bytes().all(|c| c.is_ascii_digit()))
+}
+
+#[inline]
+fn starts_with_word(s: &str, word: &str) -> bool {
+  s.strip_prefix(word).map_or(false, |el| {
+    !el.starts_with(|c: char| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | ':' | '_'))
+  })
 }
 
 #[inline]
-fn try_parse_expr_string(s: &str) -> crate::Result<ExprStringTy> {
-  let mut iter = s.split('"');
-  let _ = iter.next().ok_or(crate::Error::TomlParserOnlySupportsStringsAndArraysOfStrings)?;
-  let value = iter.next().ok_or(crate::Error::TomlParserOnlySupportsStringsAndArraysOfStrings)?;
-  let _ = iter.next().ok_or(crate::Error::TomlParserOnlySupportsStringsAndArraysOfStrings)?;
-  if iter.next().is_some() {
-    return Err(crate::Error::TomlParserOnlySupportsStringsAndArraysOfStrings);
+fn table_at<'table>(root: &'table mut Table, path: &[String]) -> Option<&'table mut Table> {
+  let mut current = root;
+  for name in path {
+    current = match &mut current.entries.iter_mut().find(|el| el.0.name == *name)?.1 {
+      Value::ArrayOfTables(elem) => elem.last_mut()?,
+      Value::Table(elem) => elem,
+      _ => return None,
+    };
   }
-  value.trim().try_into().map_err(|_err| crate::Error::TomlValueIsTooLarge)
+  Some(current)
 }
 
 #[inline]
-fn try_parse_and_push_toml_expr_array(
-  s: &str,
-  ident: IdentTy,
-  root_params: &mut RootParamsTy,
-) -> crate::Result<()> {
-  let expr_array = try_parse_expr_array(s)?;
-  root_params
-    .try_push((ident, Expr::Array(expr_array)))
-    .map_err(|_err| crate::Error::TomlValueIsTooLarge)?;
-  Ok(())
+fn time(s: &str) -> Option<&str> {
+  let rest = time_component(s, 23)?;
+  let rest = time_component(rest.strip_prefix(':')?, 59)?;
+  let rest = time_component(rest.strip_prefix(':')?, 60)?;
+  let Some(frac) = rest.strip_prefix('.') else {
+    return Some(rest);
+  };
+  let len = frac.bytes().take_while(u8::is_ascii_digit).count();
+  (len > 0).then(|| frac.get(len..)).flatten()
 }
 
 #[inline]
-fn try_parse_and_push_toml_expr_string(
-  s: &str,
-  ident: IdentTy,
-  root_params: &mut RootParamsTy,
-) -> crate::Result<()> {
-  let expr_string = try_parse_expr_string(s)?;
-  root_params
-    .try_push((ident, Expr::String(expr_string)))
-    .map_err(|_err| crate::Error::TomlValueIsTooLarge)?;
-  Ok(())
+fn time_component(s: &str, max: u8) -> Option<&str> {
+  let value: u8 = fixed_digits(s, 2)?.parse().ok()?;
+  (value <= max).then(|| s.get(2..)).flatten()
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::sm::toml_parser::{toml, Expr};
-  use arrayvec::ArrayVec;
+  use crate::sm::toml_parser::{toml, Table, Value};
+
+  fn entry<'table>(table: &'table Table, name: &str) -> &'table Value {
+    &table.entries.iter().find(|el| el.0.name == name).unwrap().1
+  }
+
+  fn error(s: &str) -> (&'static str, usize, usize) {
+    let Err(crate::Error::InvalidToml(msg, line, column)) = toml(s.as_bytes()) else {
+      panic!("{s}");
+    };
+    (msg, line, column)
+  }
 
   #[test]
-  fn toml_parses_root_parameter_array_in_a_single_line() {
-    let array = toml(
-      &br#"
-    foo = ["1", "2"]
+  fn toml_ignores_comments() {
+    let table = toml(&b"# Foo\n\nfoo = \"bar\" # Bar\n"[..]).unwrap();
+    assert_eq!(entry(&table, "foo"), &Value::String("bar".into()));
+  }
 
-    bar=[]
-    "#[..],
-    )
-    .unwrap();
+  #[test]
+  fn toml_limits_the_nesting_of_values() {
+    let nested = |depth| format!("a = {}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(toml(nested(64).as_bytes()).is_ok());
+    assert_eq!(error(&nested(65)), ("Arrays and inline tables are nested too deeply", 1, 69));
+    let s = format!("a = {}1{}", "{ b = ".repeat(65), " }".repeat(65));
+    assert_eq!(error(&s).0, "Arrays and inline tables are nested too deeply");
+  }
+
+  #[test]
+  fn toml_parses_arrays_in_multiple_lines() {
+    let table = toml(&b"foo = [\n  \"1\", # One\n  2,\n  [3.5],\n]\nbar=[]"[..]).unwrap();
     assert_eq!(
-      array[0],
-      (
-        "foo".try_into().unwrap(),
-        Expr::Array({
-          let mut elems = ArrayVec::new();
-          elems.push("1".try_into().unwrap());
-          elems.push("2".try_into().unwrap());
-          elems
-        })
-      )
+      entry(&table, "foo"),
+      &Value::Array(vec![
+        Value::String("1".into()),
+        Value::Integer(2),
+        Value::Array(vec![Value::Float(3.5)])
+      ])
     );
-    assert_eq!(array[1], ("bar".try_into().unwrap(), Expr::Array(Default::default())));
+    assert_eq!(entry(&table, "bar"), &Value::Array(vec![]));
   }
 
   #[test]
-  fn toml_parses_root_parameter_array_in_multiple_lines() {
-    let array = toml(
-      &br#"
-    foo=[
-      "1",
-      "2",
-      "3"
-    ]
-    "#[..],
+  fn toml_parses_dates_and_times() {
+    let table =
+      toml(
+        &b"a = 1979-05-27T07:32:00-08:00\nb = 1979-05-27 07:32:00.99\nc = 1979-05-27\nd = 07:32:00"
+          [..],
+      )
+      .unwrap();
+    assert_eq!(entry(&table, "a"), &Value::Datetime("1979-05-27T07:32:00-08:00".into()));
+    assert_eq!(entry(&table, "b"), &Value::Datetime("1979-05-27 07:32:00.99".into()));
+    assert_eq!(entry(&table, "c"), &Value::Datetime("1979-05-27".into()));
+    assert_eq!(entry(&table, "d"), &Value::Datetime("07:32:00".into()));
+    assert_eq!(error("a = 1979-02-30").0, "Invalid value");
+  }
+
+  #[test]
+  fn toml_parses_numbers_and_booleans() {
+    let table = toml(
+      &b"a = +1_000\nb = -0\nc = 0xDEAD_beef\nd = 0o755\ne = 0b101\nf = 6.626e-34\ng = -inf\nh = true\ni = false"[..],
     )
     .unwrap();
-    assert_eq!(
-      array[0],
-      (
-        "foo".try_into().unwrap(),
-        Expr::Array({
-          let mut elems = ArrayVec::new();
-          elems.push("1".try_into().unwrap());
-          elems.push("2".try_into().unwrap());
-          elems.push("3".try_into().unwrap());
-          elems
-        })
-      )
-    );
+    assert_eq!(entry(&table, "a"), &Value::Integer(1000));
+    assert_eq!(entry(&table, "b"), &Value::Integer(0));
+    assert_eq!(entry(&table, "c"), &Value::Integer(0xdead_beef));
+    assert_eq!(entry(&table, "d"), &Value::Integer(0o755));
+    assert_eq!(entry(&table, "e"), &Value::Integer(5));
+    assert_eq!(entry(&table, "f"), &Value::Float(6.626e-34));
+    assert_eq!(entry(&table, "g"), &Value::Float(f64::NEG_INFINITY));
+    assert_eq!(entry(&table, "h"), &Value::Boolean(true));
+    assert_eq!(entry(&table, "i"), &Value::Boolean(false));
+    assert_eq!(error("a = 01").0, "Invalid value");
+    assert_eq!(error("a = 1__0").0, "Invalid value");
+    assert_eq!(error("a = 9223372036854775808").0, "Integer is out of range");
   }
 
   #[test]
-  fn toml_parses_root_parameter_string() {
-    let array = toml(&br#"foo="bar""#[..]).unwrap();
-    assert_eq!(array[0], ("foo".try_into().unwrap(), Expr::String("bar".try_into().unwrap())));
+  fn toml_parses_strings() {
+    let table = toml(
+      &br#"a = "tab\there \"quoted\" \u00e9"
+b = 'C:\Users\nodejs'
+c = """
+one \
+  two"""""
+d = '''
+line
+'''"#[..],
+    )
+    .unwrap();
+    assert_eq!(entry(&table, "a"), &Value::String("tab\there \"quoted\" é".into()));
+    assert_eq!(entry(&table, "b"), &Value::String("C:\\Users\\nodejs".into()));
+    assert_eq!(entry(&table, "c"), &Value::String("one two\"\"".into()));
+    assert_eq!(entry(&table, "d"), &Value::String("line\n".into()));
+    assert_eq!(error("a = \"\\x\""), ("Invalid escape sequence", 1, 8));
+    assert_eq!(error("a = \"foo\nb = 1"), ("Unterminated string", 1, 9));
   }
 
   #[test]
-  fn toml_ignores_comments() {
-    let array = toml(
+  fn toml_parses_tables() {
+    let table = toml(
       &br#"
-    # Foo
-
-    foo="bar"
-    "#[..],
+name = "root"
+[a.b]
+c = 1
+[a]
+d.e = { f = [1, 2], "g h" = 'i' }
+[[items]]
+id = 1
+[[items]]
+id = 2
+[items.sub]
+j = true
+"#[..],
     )
     .unwrap();
-    assert_eq!(array[0], ("foo".try_into().unwrap(), Expr::String("bar".try_into().unwrap())));
+    let Value::Table(a) = entry(&table, "a") else { panic!() };
+    let Value::Table(b) = entry(a, "b") else { panic!() };
+    assert_eq!(entry(b, "c"), &Value::Integer(1));
+    let Value::Table(d) = entry(a, "d") else { panic!() };
+    let Value::Table(e) = entry(d, "e") else { panic!() };
+    assert_eq!(entry(e, "g h"), &Value::String("i".into()));
+    let Value::ArrayOfTables(items) = entry(&table, "items") else { panic!() };
+    assert_eq!(items.len(), 2);
+    assert_eq!(entry(&items[1], "id"), &Value::Integer(2));
+    assert!(matches!(entry(&items[1], "sub"), Value::Table(_)));
+  }
+
+  #[test]
+  fn toml_reports_the_position_of_invalid_definitions() {
+    assert_eq!(error("a = 1\na = 2"), ("Key is already defined", 2, 1));
+    assert_eq!(error("[a]\n[a]"), ("Table is already defined", 2, 2));
+    assert_eq!(error("a = { b = 1 }\n[a.c]"), ("Inline tables can't be extended", 2, 2));
+    assert_eq!(error("a.b = 1\n[a]"), ("Table is already defined", 2, 2));
+    assert_eq!(error("a = [1]\n[[a]]"), ("Table is already defined", 2, 3));
+    assert_eq!(error("a = 1 b = 2"), ("Expected a new line after an expression", 1, 7));
+    assert_eq!(error("a = { b = 1, }"), ("Expected a key", 1, 14));
+    assert_eq!(error("  = 1"), ("Expected a key", 1, 3));
   }
 }
@@ -0,0 +1,11 @@
+create_enum! {
+  /// How migrations and their bookkeeping records are grouped into transactions
+  #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+  pub enum TransactionMode {
+    /// All migrations of a batch are executed in a single transaction and their records are
+    /// inserted afterwards in another one. Durations and failures are not recorded.
+    Batch, "batch";
+    /// Each migration is executed in its own transaction along with its record
+    PerMigration, "per-migration";
+  }
+}
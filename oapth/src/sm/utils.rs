@@ -8,15 +8,18 @@ macro_rules! opt_to_inv_mig {
 }
 
 use crate::{
-  sm::migration::{DbMigration, UserMigration},
+  sm::{
+    migration::{DbMigration, UserMigration},
+    Repeatability,
+  },
   DatabaseTy,
 };
 use core::hash::{Hash, Hasher};
 #[cfg(feature = "std")]
 use {
   crate::sm::{
-    toml_parser::{toml, Expr, EXPR_ARRAY_MAX_LEN},
-    MigrationGroup, Repeatability, UserMigrationOwned,
+    toml_parser::{toml, Value},
+    MigrationGroup, UserMigrationOwned,
   },
   arrayvec::ArrayString,
   arrayvec::ArrayVec,
@@ -32,8 +35,15 @@ use {
 #[cfg(feature = "std")]
 type MigrationGroupParts = (String, i32);
 #[cfg(feature = "std")]
-type MigrationParts =
-  (ArrayVec<DatabaseTy, { DatabaseTy::len() }>, String, Option<Repeatability>, String, String, i32);
+type MigrationParts = (
+  ArrayVec<DatabaseTy, { DatabaseTy::len() }>,
+  String,
+  Option<Repeatability>,
+  String,
+  String,
+  bool,
+  i32,
+);
 
 /// All files of a given `path`.
 #[cfg(feature = "std")]
@@ -73,6 +83,7 @@ where
       let mut repeatability = None;
       let mut sql_down = String::default();
       let mut sql_up = String::default();
+      let mut transactional = true;
       let version;
 
       if local_path.is_dir() {
@@ -98,6 +109,7 @@ where
             let mc = parse_migration_toml(File::open(file_path)?)?;
             dbs = mc.dbs;
             repeatability = mc.repeatability;
+            transactional = mc.transactional;
           } else if file_name == &down_file_name {
             sql_down = read_to_string(file_path)?;
           } else if file_name == &up_file_name {
@@ -113,12 +125,13 @@ where
         let pm = parse_unified_migration(File::open(local_path)?)?;
         dbs = pm.cfg.dbs;
         repeatability = pm.cfg.repeatability;
+        transactional = pm.cfg.transactional;
         sql_up = pm.sql_up;
         sql_down = pm.sql_down;
       } else {
         return Err(crate::Error::InvalidMigration);
       }
-      Ok((dbs, name, repeatability, sql_down, sql_up, version))
+      Ok((dbs, name, repeatability, sql_down, sql_up, transactional, version))
     });
 
     Ok((mg, migrations))
@@ -127,8 +140,15 @@ where
   let ((mg_name, mg_version), ms) = group_and_migrations_from_path(path, cb)?;
   let mg = MigrationGroup::new(mg_name, mg_version);
   let mapped = ms.map(|rslt| {
-    let (dbs, name, repeatability, sql_down, sql_up, version) = rslt?;
-    UserMigrationOwned::from_user_parts(dbs, name, repeatability, [sql_up, sql_down], version)
+    let (dbs, name, repeatability, sql_down, sql_up, transactional, version) = rslt?;
+    UserMigrationOwned::from_user_parts(
+      dbs,
+      name,
+      repeatability,
+      [sql_up, sql_down],
+      transactional,
+      version,
+    )
   });
   Ok((mg, mapped))
 }
@@ -136,9 +156,7 @@ where
 /// All paths to directories that contain migrations and optional seeds
 #[cfg(feature = "std")]
 #[inline]
-pub fn parse_root_toml(
-  cfg_path: &Path,
-) -> crate::Result<(ArrayVec<PathBuf, EXPR_ARRAY_MAX_LEN>, Option<PathBuf>)> {
+pub fn parse_root_toml(cfg_path: &Path) -> crate::Result<(Vec<PathBuf>, Option<PathBuf>)> {
   let cfg_dir = cfg_path.parent().unwrap_or_else(|| Path::new("."));
   parse_root_toml_raw(File::open(cfg_path)?, cfg_dir)
 }
@@ -149,36 +167,43 @@ pub fn parse_root_toml(
 pub fn parse_root_toml_raw<R>(
   read: R,
   root: &Path,
-) -> crate::Result<(ArrayVec<PathBuf, EXPR_ARRAY_MAX_LEN>, Option<PathBuf>)>
+) -> crate::Result<(Vec<PathBuf>, Option<PathBuf>)>
 where
   R: Read,
 {
-  let mut migration_groups = ArrayVec::new();
+  let mut migration_groups = Vec::new();
   let mut seeds = None;
 
-  for (ident, toml_expr) in toml(read)? {
-    match (ident.as_ref(), toml_expr) {
-      ("migration_groups", Expr::Array(array)) => {
+  for (key, value) in toml(read)?.entries {
+    match (key.name.as_str(), value) {
+      ("migration_groups", Value::Array(array)) => {
         for elem in array {
-          let path = root.join(elem.as_str());
+          let Value::String(s) = elem else {
+            return key.invalid("`migration_groups` must be an array of strings");
+          };
+          let path = root.join(s.as_str());
           let name_opt = || path.file_name()?.to_str();
           let Some(name) = name_opt() else {
             continue;
           };
-          if elem.is_empty() || !path.is_dir() || dir_name_parts(name).is_err() {
+          if s.is_empty() || !path.is_dir() || dir_name_parts(name).is_err() {
             continue;
           }
-          migration_groups.try_push(path)?;
+          migration_groups.push(path);
         }
       }
-      ("seeds", Expr::String(elem)) => {
+      ("migration_groups", _) => {
+        return key.invalid("`migration_groups` must be an array of strings");
+      }
+      ("seeds", Value::String(elem)) => {
         let path = root.join(elem.as_str());
         if !path.is_dir() {
           continue;
         }
         seeds = Some(path);
       }
-      _ => {}
+      ("seeds", _) => return key.invalid("`seeds` must be a string"),
+      _ => return key.unknown(),
     }
   }
 
@@ -196,6 +221,7 @@ pub(crate) fn calc_checksum(name: &str, sql_up: &str, sql_down: &str, version: i
   hasher.finish()
 }
 
+// Repeatable migrations are allowed to change their contents.
 #[inline]
 pub(crate) fn is_migration_divergent<DBS, S>(
   db_migrations: &[DbMigration],
@@ -210,11 +236,42 @@ where
   let Some(db_migration) = opt else {
     return false;
   };
-  migration.checksum() != db_migration.checksum()
+  (migration.repeatability().is_none() && migration.checksum() != db_migration.checksum())
     || migration.name() != db_migration.name()
     || migration.version() != db_migration.version()
 }
 
+/// If an already applied `migration` must be executed again according to its repeatability.
+#[inline]
+pub(crate) fn is_rerun_needed<DBS, S>(
+  db_migration: &DbMigration,
+  migration: &UserMigration<DBS, S>,
+) -> bool
+where
+  DBS: AsRef<[DatabaseTy]>,
+  S: AsRef<str>,
+{
+  match migration.repeatability() {
+    None => false,
+    Some(Repeatability::Always) => true,
+    Some(Repeatability::OnChecksumChange) => migration.checksum() != db_migration.checksum(),
+  }
+}
+
+/// If `migration` was already applied and must be executed again, see [is_rerun_needed].
+#[inline]
+pub(crate) fn needs_rerun<DBS, S>(
+  db_migrations: &[DbMigration],
+  migration: &UserMigration<DBS, S>,
+) -> bool
+where
+  DBS: AsRef<[DatabaseTy]>,
+  S: AsRef<str>,
+{
+  binary_search_migration_by_version(migration.version(), db_migrations)
+    .map_or(false, |db_migration| is_rerun_needed(db_migration, migration))
+}
+
 pub(crate) fn is_sorted_and_unique<T>(slice: &[T]) -> crate::Result<()>
 where
   T: PartialOrd,
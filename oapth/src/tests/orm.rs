@@ -1,2 +1,3 @@
 mod collection;
 mod diamond;
+mod escaping;
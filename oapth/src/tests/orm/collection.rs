@@ -1,11 +1,13 @@
 // C --> A/B
 
 use crate::{
+  database::SqlParam,
   orm::{
-    FromSuffixRslt, InitialInsertValue, NoTableAssociation, SelectLimit, SelectOrderBy, SqlWriter,
-    Table, TableAssociation, TableAssociationWrapper, TableField, TableParams,
+    FromSuffixRslt, InitialInsertValue, NoTableAssociation, SelectLimit, SelectOrderBy,
+    SqlBindings, SqlColumn, SqlWriter, Table, TableAssociation, TableAssociationWrapper,
+    TableField, TableParams,
   },
-  TableSuffix,
+  DatabaseTy, TableSuffix,
 };
 use alloc::{string::String, vec, vec::Vec};
 use core::mem;
@@ -30,9 +32,9 @@ impl<'entity> Table<'entity> for A {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
   }
 }
 
@@ -55,9 +57,9 @@ impl<'entity> Table<'entity> for B {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
   }
 }
 
@@ -99,9 +101,9 @@ impl<'entity> Table<'entity> for C {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
 
     table.associations_mut().0.tables.clear();
     for a in entity.r#as.iter() {
@@ -136,14 +138,22 @@ fn update_some_values_has_correct_behavior() {
   let mut buffer = String::new();
   let mut c_table_defs = TableParams::<C>::default();
 
-  *c_table_defs.id_field_mut().value_mut() = Some((&c3.id).into());
+  *c_table_defs.id_field_mut().value_mut() = Some(&c3.id);
 
   let mut elem = TableParams::new(0);
-  *elem.id_field_mut().value_mut() = Some((&c3.r#as[0].id).into());
+  *elem.id_field_mut().value_mut() = Some(&c3.r#as[0].id);
   c_table_defs.associations_mut().0.tables.push(elem);
 
-  c_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"UPDATE c SET id='3' WHERE id='3';UPDATE a SET id='1' WHERE id='1';"#);
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  c_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "c" SET "id"=$1 WHERE "id"=$2;UPDATE "a" SET "id"=$1 WHERE "id"=$2;"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[SqlParam::I32(3), SqlParam::I32(3), SqlParam::I32(1), SqlParam::I32(1)]
+  );
 }
 
 #[test]
@@ -155,58 +165,303 @@ fn write_collection_has_correct_params() {
   let mut buffer = String::new();
   let mut c_table_defs = TableParams::<C>::default();
 
-  c_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  c_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(&buffer, r#""#);
 
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
   c_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(&buffer, r#""#);
 
   buffer.clear();
   c_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Ascending, &[]),
+      SelectLimit::All,
+      DatabaseTy::Postgres,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "c0".id AS c0__id,"c0".name AS c0__name,"a1".id AS a1__id,"a1".name AS a1__name,"b2".id AS b2__id,"b2".name AS b2__name FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0".id = "a1".id_a LEFT JOIN "b" AS "b2" ON "c0".id = "b2".id_b  ORDER BY "c0".id,"a1".id,"b2".id ASC LIMIT ALL"#
+    r#"SELECT "c0"."id" AS "c0__id","c0"."name" AS "c0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name","b2"."id" AS "b2__id","b2"."name" AS "b2__name" FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  ORDER BY "c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC LIMIT ALL"#
   );
 
   buffer.clear();
-  c_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  c_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(&buffer, r#""#);
 
   c_table_defs.update_all_table_fields(&c3);
 
   buffer.clear();
-  c_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  c_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(
     &buffer,
-    r#"DELETE FROM a WHERE id='1';DELETE FROM a WHERE id='2';DELETE FROM c WHERE id='3';"#
+    r#"DELETE FROM "a" WHERE "id"=$1;DELETE FROM "a" WHERE "id"=$1;DELETE FROM "c" WHERE "id"=$1;"#
   );
+  assert_eq!(bindings.params(), &[SqlParam::I32(1), SqlParam::I32(2), SqlParam::I32(3)]);
 
   buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
   c_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"INSERT INTO "c" (id,name) VALUES ('3','foo3');INSERT INTO "a" (id,name,id_a) VALUES ('1','foo1','3');INSERT INTO "a" (id,name,id_a) VALUES ('2','foo2','3');"#
+    r#"INSERT INTO "c" ("id","name") VALUES ($1,$2);INSERT INTO "a" ("id","name","id_a") VALUES ($1,$2,$3);INSERT INTO "a" ("id","name","id_a") VALUES ($1,$2,$3);"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(3),
+      SqlParam::Str("foo3"),
+      SqlParam::I32(1),
+      SqlParam::Str("foo1"),
+      SqlParam::I32(3),
+      SqlParam::I32(2),
+      SqlParam::Str("foo2"),
+      SqlParam::I32(3)
+    ]
   );
 
   buffer.clear();
   c_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Ascending, &[]),
+      SelectLimit::All,
+      DatabaseTy::Postgres,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "c0".id AS c0__id,"c0".name AS c0__name,"a1".id AS a1__id,"a1".name AS a1__name,"b2".id AS b2__id,"b2".name AS b2__name FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0".id = "a1".id_a LEFT JOIN "b" AS "b2" ON "c0".id = "b2".id_b  ORDER BY "c0".id,"a1".id,"b2".id ASC LIMIT ALL"#
+    r#"SELECT "c0"."id" AS "c0__id","c0"."name" AS "c0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name","b2"."id" AS "b2__id","b2"."name" AS "b2__name" FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  ORDER BY "c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC LIMIT ALL"#
   );
 
   buffer.clear();
-  c_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  c_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(
     &buffer,
-    r#"UPDATE c SET id='3',name='foo3' WHERE id='3';UPDATE a SET id='1',name='foo1' WHERE id='1';UPDATE a SET id='2',name='foo2' WHERE id='2';"#
+    r#"UPDATE "c" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "a" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "a" SET "id"=$1,"name"=$2 WHERE "id"=$3;"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(3),
+      SqlParam::Str("foo3"),
+      SqlParam::I32(3),
+      SqlParam::I32(1),
+      SqlParam::Str("foo1"),
+      SqlParam::I32(1),
+      SqlParam::I32(2),
+      SqlParam::Str("foo2"),
+      SqlParam::I32(2)
+    ]
+  );
+}
+
+#[test]
+fn placeholders_follow_the_database_syntax() {
+  let a1 = A { id: 1, name: "foo1" };
+  let c3 = C { r#as: vec![a1], bs: vec![], id: 3, name: "foo3" };
+
+  let mut buffer = String::new();
+  let mut c_table_defs = TableParams::<C>::default();
+  c_table_defs.update_all_table_fields(&c3);
+
+  let mut bindings = SqlBindings::new(DatabaseTy::Mssql);
+  c_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(&buffer, r#"DELETE FROM [a] WHERE [id]=@P1;DELETE FROM [c] WHERE [id]=@P1;"#);
+  assert_eq!(
+    bindings.statements(&buffer).collect::<Vec<_>>(),
+    [
+      ("DELETE FROM [a] WHERE [id]=@P1;", &[SqlParam::I32(1)][..]),
+      ("DELETE FROM [c] WHERE [id]=@P1;", &[SqlParam::I32(3)][..])
+    ]
+  );
+
+  buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Sqlite);
+  c_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(
+    &buffer,
+    r#"UPDATE "c" SET "id"=?,"name"=? WHERE "id"=?;UPDATE "a" SET "id"=?,"name"=? WHERE "id"=?;"#
+  );
+  assert_eq!(bindings.statements(&buffer).map(|el| el.1.len()).collect::<Vec<_>>(), [3, 3]);
+}
+
+#[test]
+fn select_limit_follows_the_database_syntax() {
+  let mut buffer = String::new();
+  let c_table_defs = TableParams::<C>::default();
+  for (ty, limit, suffix) in [
+    (DatabaseTy::Mssql, SelectLimit::All, r#"ORDER BY [c0].[id] ASC,[a1].[id] ASC,[b2].[id] ASC"#),
+    (
+      DatabaseTy::Mssql,
+      SelectLimit::Count(5),
+      r#"ORDER BY [c0].[id] ASC,[a1].[id] ASC,[b2].[id] ASC OFFSET 0 ROWS FETCH NEXT 5 ROWS ONLY"#,
+    ),
+    (DatabaseTy::MySql, SelectLimit::All, r#"ORDER BY `c0`.`id` ASC,`a1`.`id` ASC,`b2`.`id` ASC"#),
+    (
+      DatabaseTy::MySql,
+      SelectLimit::Count(5),
+      r#"ORDER BY `c0`.`id` ASC,`a1`.`id` ASC,`b2`.`id` ASC LIMIT 5"#,
+    ),
+    (
+      DatabaseTy::Postgres,
+      SelectLimit::All,
+      r#"ORDER BY "c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC LIMIT ALL"#,
+    ),
+    (
+      DatabaseTy::Postgres,
+      SelectLimit::Count(5),
+      r#"ORDER BY "c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC LIMIT 5"#,
+    ),
+    (DatabaseTy::Sqlite, SelectLimit::All, r#"ORDER BY "c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC"#),
+    (
+      DatabaseTy::Sqlite,
+      SelectLimit::Count(5),
+      r#"ORDER BY "c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC LIMIT 5"#,
+    ),
+  ] {
+    buffer.clear();
+    c_table_defs
+      .write_select(&mut buffer, (SelectOrderBy::Ascending, &[]), limit, ty, &mut |_| Ok(()))
+      .unwrap();
+    assert!(buffer.ends_with(suffix), "{buffer}");
+  }
+}
+
+#[test]
+fn select_where_binds_values_of_associations() {
+  let mut buffer = String::new();
+  let c_table_defs = TableParams::<C>::default();
+  let a_table_defs = &c_table_defs.associations().0.guide;
+  let b_table_defs = &c_table_defs.associations().1.guide;
+  let (ids, name, pattern) = ([1, 2], "foo", "bar%");
+  let sql_where = SqlColumn::new(&c_table_defs, &c_table_defs.fields().0)
+    .eq(&name)
+    .and(SqlColumn::new(a_table_defs, a_table_defs.id_field()).in_list(&ids))
+    .or(!SqlColumn::new(b_table_defs, &b_table_defs.fields().0).like(&pattern))
+    .and(SqlColumn::new(b_table_defs, b_table_defs.id_field()).is_null());
+
+  for (ty, expected) in [
+    (
+      DatabaseTy::Mssql,
+      r#" WHERE ((([c0].[name] = @P1 AND [a1].[id] IN (@P2,@P3)) OR NOT ([b2].[name] LIKE @P4)) AND [b2].[id] IS NULL) ORDER BY"#,
+    ),
+    (
+      DatabaseTy::Postgres,
+      r#" WHERE ((("c0"."name" = $1 AND "a1"."id" IN ($2,$3)) OR NOT ("b2"."name" LIKE $4)) AND "b2"."id" IS NULL) ORDER BY"#,
+    ),
+  ] {
+    buffer.clear();
+    let mut bindings = SqlBindings::new(ty);
+    c_table_defs
+      .write_select(&mut buffer, (SelectOrderBy::Ascending, &[]), SelectLimit::All, ty, &mut |b| {
+        sql_where.write(b, &mut bindings)
+      })
+      .unwrap();
+    assert!(buffer.contains(expected), "{buffer}");
+    assert_eq!(
+      bindings.params(),
+      &[SqlParam::Str("foo"), SqlParam::I32(1), SqlParam::I32(2), SqlParam::Str("bar%")]
+    );
+  }
+
+  buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Sqlite);
+  let empty: [i32; 0] = [];
+  SqlColumn::new(&c_table_defs, c_table_defs.id_field())
+    .in_list::<(), _>(&empty)
+    .write(&mut buffer, &mut bindings)
+    .unwrap();
+  assert_eq!(&buffer, "1 = 0");
+  assert!(bindings.params().is_empty());
+
+  buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  let (none, some) = (None::<&str>, Some("foo"));
+  let name_column = SqlColumn::new(&c_table_defs, &c_table_defs.fields().0);
+  name_column
+    .eq::<(), _>(&none)
+    .and(name_column.ne(&none))
+    .or(name_column.ne(&some))
+    .write(&mut buffer, &mut bindings)
+    .unwrap();
+  assert_eq!(
+    &buffer,
+    r#"(("c0"."name" IS NULL AND "c0"."name" IS NOT NULL) OR "c0"."name" <> $1)"#
+  );
+  assert_eq!(bindings.params(), &[SqlParam::Str("foo")]);
+}
+
+#[test]
+fn select_page_limits_root_entities() {
+  let mut buffer = String::new();
+  let c_table_defs = TableParams::<C>::default();
+  let name_column = SqlColumn::new(&c_table_defs, &c_table_defs.fields().0);
+  let order_by = [(name_column, SelectOrderBy::Descending)];
+  let name = "foo";
+  let sql_where = name_column.ne(&name);
+  for (ty, expected) in [
+    (
+      DatabaseTy::Mssql,
+      r#"FROM [c] AS [c0] LEFT JOIN [a] AS [a1] ON [c0].[id] = [a1].[id_a] LEFT JOIN [b] AS [b2] ON [c0].[id] = [b2].[id_b]  WHERE ([c0].[name] <> @P1) AND EXISTS (SELECT 1 FROM (SELECT DISTINCT [c0].[id] AS [c0__id],[c0].[name] AS [c0__name] FROM [c] AS [c0] LEFT JOIN [a] AS [a1] ON [c0].[id] = [a1].[id_a] LEFT JOIN [b] AS [b2] ON [c0].[id] = [b2].[id_b]  WHERE [c0].[name] <> @P2 ORDER BY [c0].[name] DESC,[c0].[id] ASC OFFSET 4 ROWS FETCH NEXT 2 ROWS ONLY) AS [page] WHERE [page].[c0__id] = [c0].[id]) ORDER BY [c0].[name] DESC,[c0].[id] ASC,[a1].[id] ASC,[b2].[id] ASC"#,
+    ),
+    (
+      DatabaseTy::MySql,
+      r#"FROM `c` AS `c0` LEFT JOIN `a` AS `a1` ON `c0`.`id` = `a1`.`id_a` LEFT JOIN `b` AS `b2` ON `c0`.`id` = `b2`.`id_b`  WHERE (`c0`.`name` <> ?) AND EXISTS (SELECT 1 FROM (SELECT DISTINCT `c0`.`id` AS `c0__id`,`c0`.`name` AS `c0__name` FROM `c` AS `c0` LEFT JOIN `a` AS `a1` ON `c0`.`id` = `a1`.`id_a` LEFT JOIN `b` AS `b2` ON `c0`.`id` = `b2`.`id_b`  WHERE `c0`.`name` <> ? ORDER BY `c0`.`name` DESC,`c0`.`id` ASC LIMIT 2 OFFSET 4) AS `page` WHERE `page`.`c0__id` = `c0`.`id`) ORDER BY `c0`.`name` DESC,`c0`.`id` ASC,`a1`.`id` ASC,`b2`.`id` ASC"#,
+    ),
+    (
+      DatabaseTy::Postgres,
+      r#"FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  WHERE ("c0"."name" <> $1) AND EXISTS (SELECT 1 FROM (SELECT DISTINCT "c0"."id" AS "c0__id","c0"."name" AS "c0__name" FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  WHERE "c0"."name" <> $2 ORDER BY "c0"."name" DESC,"c0"."id" ASC LIMIT 2 OFFSET 4) AS "page" WHERE "page"."c0__id" = "c0"."id") ORDER BY "c0"."name" DESC,"c0"."id" ASC,"a1"."id" ASC,"b2"."id" ASC LIMIT ALL"#,
+    ),
+  ] {
+    buffer.clear();
+    let mut bindings = SqlBindings::new(ty);
+    c_table_defs
+      .write_select(
+        &mut buffer,
+        (SelectOrderBy::Ascending, &order_by),
+        SelectLimit::Page { count: 2, offset: 4 },
+        ty,
+        &mut |b| sql_where.write(b, &mut bindings),
+      )
+      .unwrap();
+    assert!(buffer.ends_with(expected), "{buffer}");
+    assert_eq!(bindings.params(), &[SqlParam::Str("foo"), SqlParam::Str("foo")]);
+  }
+
+  buffer.clear();
+  c_table_defs
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Descending, &[]),
+      SelectLimit::Page { count: 2, offset: 0 },
+      DatabaseTy::Sqlite,
+      &mut |_| Ok(()),
+    )
+    .unwrap();
+  assert!(
+    buffer.ends_with(
+      r#" WHERE EXISTS (SELECT 1 FROM (SELECT DISTINCT "c0"."id" AS "c0__id" FROM "c" AS "c0" LEFT JOIN "a" AS "a1" ON "c0"."id" = "a1"."id_a" LEFT JOIN "b" AS "b2" ON "c0"."id" = "b2"."id_b"  ORDER BY "c0"."id" DESC LIMIT 2 OFFSET 0) AS "page" WHERE "page"."c0__id" = "c0"."id") ORDER BY "c0"."id" DESC,"a1"."id" DESC,"b2"."id" DESC"#
+    ),
+    "{buffer}"
   );
 }
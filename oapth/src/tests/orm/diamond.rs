@@ -32,11 +32,13 @@
 //        |--> A
 
 use crate::{
+  database::SqlParam,
   orm::{
-    FromSuffixRslt, InitialInsertValue, NoTableAssociation, SelectLimit, SelectOrderBy, SqlWriter,
-    Table, TableAssociation, TableAssociationWrapper, TableField, TableParams,
+    FromSuffixRslt, InitialInsertValue, NoTableAssociation, SelectLimit, SelectOrderBy,
+    SqlBindings, SqlWriter, Table, TableAssociation, TableAssociationWrapper, TableField,
+    TableParams,
   },
-  TableSuffix,
+  DatabaseTy, TableSuffix,
 };
 use alloc::string::String;
 use core::mem;
@@ -66,9 +68,9 @@ impl<'entity> Table<'entity> for A {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
   }
 }
 
@@ -99,9 +101,9 @@ impl<'entity> Table<'entity> for B {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
 
     table.associations_mut().0.tables[0].update_all_table_fields(&entity.a);
   }
@@ -134,9 +136,9 @@ impl<'entity> Table<'entity> for C {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
 
     table.associations_mut().0.tables[0].update_all_table_fields(&entity.a);
   }
@@ -180,9 +182,9 @@ impl<'entity> Table<'entity> for D {
   }
 
   fn update_all_table_fields(entity: &'entity Self, table: &mut TableParams<'entity, Self>) {
-    *table.id_field_mut().value_mut() = Some((&entity.id).into());
+    *table.id_field_mut().value_mut() = Some(&entity.id);
 
-    *table.fields_mut().0.value_mut() = Some((entity.name).into());
+    *table.fields_mut().0.value_mut() = Some(entity.name);
 
     table.associations_mut().0.tables[0].update_all_table_fields(&entity.b);
     table.associations_mut().1.tables[0].update_all_table_fields(&entity.c);
@@ -204,38 +206,88 @@ fn multi_referred_table_has_correct_statements() {
   let mut d_table_defs = TableParams::<D>::default();
 
   d_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Ascending, &[]),
+      SelectLimit::All,
+      DatabaseTy::Postgres,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "d0".id AS d0__id,"d0".name AS d0__name,"b1".id AS b1__id,"b1".name AS b1__name,"a2".id AS a2__id,"a2".name AS a2__name,"c2".id AS c2__id,"c2".name AS c2__name,"a3".id AS a3__id,"a3".name AS a3__name FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0".id = "b1".id_d LEFT JOIN "c" AS "c2" ON "d0".id = "c2".id_d LEFT JOIN "a" AS "a2" ON "b1".id = "a2".id_b LEFT JOIN "a" AS "a3" ON "c2".id = "a3".id_c  ORDER BY "d0".id,"b1".id,"a2".id,"c2".id,"a3".id ASC LIMIT ALL"#
+    r#"SELECT "d0"."id" AS "d0__id","d0"."name" AS "d0__name","b1"."id" AS "b1__id","b1"."name" AS "b1__name","a2"."id" AS "a2__id","a2"."name" AS "a2__name","c2"."id" AS "c2__id","c2"."name" AS "c2__name","a3"."id" AS "a3__id","a3"."name" AS "a3__name" FROM "d" AS "d0" LEFT JOIN "b" AS "b1" ON "d0"."id" = "b1"."id_d" LEFT JOIN "c" AS "c2" ON "d0"."id" = "c2"."id_d" LEFT JOIN "a" AS "a2" ON "b1"."id" = "a2"."id_b" LEFT JOIN "a" AS "a3" ON "c2"."id" = "a3"."id_c"  ORDER BY "d0"."id" ASC,"b1"."id" ASC,"a2"."id" ASC,"c2"."id" ASC,"a3"."id" ASC LIMIT ALL"#
   );
 
   d_table_defs.update_all_table_fields(&D);
 
   buffer.clear();
-  d_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  d_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(
     &buffer,
-    r#"DELETE FROM a WHERE id='1';DELETE FROM b WHERE id='2';DELETE FROM c WHERE id='3';DELETE FROM d WHERE id='4';"#
+    r#"DELETE FROM "a" WHERE "id"=$1;DELETE FROM "b" WHERE "id"=$1;DELETE FROM "c" WHERE "id"=$1;DELETE FROM "d" WHERE "id"=$1;"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[SqlParam::I32(1), SqlParam::I32(2), SqlParam::I32(3), SqlParam::I32(4)]
   );
 
   buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
   d_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(
     &buffer,
     // FIXME
-    // INSERT INTO "d" (id,name) VALUES ('4','foo4');INSERT INTO "b" (id,name,id_d) VALUES ('2','foo2','4');INSERT INTO "c" (id,name,id_d) VALUES ('3','foo3','4');INSERT INTO "a" (id,name,id_b,id_c) VALUES ('1','foo1','2','3');
-    r#"INSERT INTO "d" (id,name) VALUES ('4','foo4');INSERT INTO "b" (id,name,id_d) VALUES ('2','foo2','4');INSERT INTO "a" (id,name,id_b) VALUES ('1','foo1','2');INSERT INTO "c" (id,name,id_d) VALUES ('3','foo3','4');"#
+    // INSERT INTO "d" ("id","name") VALUES ($1,$2);INSERT INTO "b" ("id","name","id_d") VALUES ($1,$2,$3);INSERT INTO "c" ("id","name","id_d") VALUES ($1,$2,$3);INSERT INTO "a" ("id","name","id_b","id_c") VALUES ($1,$2,$3,$4);
+    r#"INSERT INTO "d" ("id","name") VALUES ($1,$2);INSERT INTO "b" ("id","name","id_d") VALUES ($1,$2,$3);INSERT INTO "a" ("id","name","id_b") VALUES ($1,$2,$3);INSERT INTO "c" ("id","name","id_d") VALUES ($1,$2,$3);"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(4),
+      SqlParam::Str("foo4"),
+      SqlParam::I32(2),
+      SqlParam::Str("foo2"),
+      SqlParam::I32(4),
+      SqlParam::I32(1),
+      SqlParam::Str("foo1"),
+      SqlParam::I32(2),
+      SqlParam::I32(3),
+      SqlParam::Str("foo3"),
+      SqlParam::I32(4)
+    ]
   );
 
   buffer.clear();
-  d_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  d_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(
     &buffer,
-    r#"UPDATE d SET id='4',name='foo4' WHERE id='4';UPDATE b SET id='2',name='foo2' WHERE id='2';UPDATE a SET id='1',name='foo1' WHERE id='1';UPDATE c SET id='3',name='foo3' WHERE id='3';"#
+    r#"UPDATE "d" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "b" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "a" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "c" SET "id"=$1,"name"=$2 WHERE "id"=$3;"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(4),
+      SqlParam::Str("foo4"),
+      SqlParam::I32(4),
+      SqlParam::I32(2),
+      SqlParam::Str("foo2"),
+      SqlParam::I32(2),
+      SqlParam::I32(1),
+      SqlParam::Str("foo1"),
+      SqlParam::I32(1),
+      SqlParam::I32(3),
+      SqlParam::Str("foo3"),
+      SqlParam::I32(3)
+    ]
   );
 }
 
@@ -244,61 +296,238 @@ fn referred_table_has_correct_statements() {
   let mut buffer = String::new();
   let mut b_table_defs = TableParams::<B>::default();
   b_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Ascending, &[]),
+      SelectLimit::All,
+      DatabaseTy::Postgres,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "b0".id AS b0__id,"b0".name AS b0__name,"a1".id AS a1__id,"a1".name AS a1__name FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0".id = "a1".id_b  ORDER BY "b0".id,"a1".id ASC LIMIT ALL"#
+    r#"SELECT "b0"."id" AS "b0__id","b0"."name" AS "b0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name" FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0"."id" = "a1"."id_b"  ORDER BY "b0"."id" ASC,"a1"."id" ASC LIMIT ALL"#
   );
 
   b_table_defs.update_all_table_fields(&B);
 
   buffer.clear();
-  b_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"DELETE FROM a WHERE id='1';DELETE FROM b WHERE id='2';"#);
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  b_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(&buffer, r#"DELETE FROM "a" WHERE "id"=$1;DELETE FROM "b" WHERE "id"=$1;"#);
+  assert_eq!(bindings.params(), &[SqlParam::I32(1), SqlParam::I32(2)]);
 
   buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
   b_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"INSERT INTO "b" (id,name) VALUES ('2','foo2');INSERT INTO "a" (id,name,id_b) VALUES ('1','foo1','2');"#
+    r#"INSERT INTO "b" ("id","name") VALUES ($1,$2);INSERT INTO "a" ("id","name","id_b") VALUES ($1,$2,$3);"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(2),
+      SqlParam::Str("foo2"),
+      SqlParam::I32(1),
+      SqlParam::Str("foo1"),
+      SqlParam::I32(2)
+    ]
   );
 
   buffer.clear();
-  b_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  b_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
   assert_eq!(
     &buffer,
-    r#"UPDATE b SET id='2',name='foo2' WHERE id='2';UPDATE a SET id='1',name='foo1' WHERE id='1';"#
+    r#"UPDATE "b" SET "id"=$1,"name"=$2 WHERE "id"=$3;UPDATE "a" SET "id"=$1,"name"=$2 WHERE "id"=$3;"#
+  );
+  assert_eq!(
+    bindings.params(),
+    &[
+      SqlParam::I32(2),
+      SqlParam::Str("foo2"),
+      SqlParam::I32(2),
+      SqlParam::I32(1),
+      SqlParam::Str("foo1"),
+      SqlParam::I32(1)
+    ]
   );
 }
 
+#[test]
+fn referred_table_has_dialect_specific_statements() {
+  let mut buffer = String::new();
+  let mut b_table_defs = TableParams::<B>::default();
+  b_table_defs.update_all_table_fields(&B);
+  for (ty, [select_all, select_count, delete, insert, update]) in [
+    (
+      DatabaseTy::Mssql,
+      [
+        r#"SELECT [b0].[id] AS [b0__id],[b0].[name] AS [b0__name],[a1].[id] AS [a1__id],[a1].[name] AS [a1__name] FROM [b] AS [b0] LEFT JOIN [a] AS [a1] ON [b0].[id] = [a1].[id_b]  ORDER BY [b0].[id] ASC,[a1].[id] ASC"#,
+        r#"SELECT [b0].[id] AS [b0__id],[b0].[name] AS [b0__name],[a1].[id] AS [a1__id],[a1].[name] AS [a1__name] FROM [b] AS [b0] LEFT JOIN [a] AS [a1] ON [b0].[id] = [a1].[id_b]  ORDER BY [b0].[id] DESC,[a1].[id] DESC OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY"#,
+        r#"DELETE FROM [a] WHERE [id]=@P1;DELETE FROM [b] WHERE [id]=@P1;"#,
+        r#"INSERT INTO [b] ([id],[name]) VALUES (@P1,@P2);INSERT INTO [a] ([id],[name],[id_b]) VALUES (@P1,@P2,@P3);"#,
+        r#"UPDATE [b] SET [id]=@P1,[name]=@P2 WHERE [id]=@P3;UPDATE [a] SET [id]=@P1,[name]=@P2 WHERE [id]=@P3;"#,
+      ],
+    ),
+    (
+      DatabaseTy::MySql,
+      [
+        r#"SELECT `b0`.`id` AS `b0__id`,`b0`.`name` AS `b0__name`,`a1`.`id` AS `a1__id`,`a1`.`name` AS `a1__name` FROM `b` AS `b0` LEFT JOIN `a` AS `a1` ON `b0`.`id` = `a1`.`id_b`  ORDER BY `b0`.`id` ASC,`a1`.`id` ASC"#,
+        r#"SELECT `b0`.`id` AS `b0__id`,`b0`.`name` AS `b0__name`,`a1`.`id` AS `a1__id`,`a1`.`name` AS `a1__name` FROM `b` AS `b0` LEFT JOIN `a` AS `a1` ON `b0`.`id` = `a1`.`id_b`  ORDER BY `b0`.`id` DESC,`a1`.`id` DESC LIMIT 10"#,
+        r#"DELETE FROM `a` WHERE `id`=?;DELETE FROM `b` WHERE `id`=?;"#,
+        r#"INSERT INTO `b` (`id`,`name`) VALUES (?,?);INSERT INTO `a` (`id`,`name`,`id_b`) VALUES (?,?,?);"#,
+        r#"UPDATE `b` SET `id`=?,`name`=? WHERE `id`=?;UPDATE `a` SET `id`=?,`name`=? WHERE `id`=?;"#,
+      ],
+    ),
+    (
+      DatabaseTy::Sqlite,
+      [
+        r#"SELECT "b0"."id" AS "b0__id","b0"."name" AS "b0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name" FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0"."id" = "a1"."id_b"  ORDER BY "b0"."id" ASC,"a1"."id" ASC"#,
+        r#"SELECT "b0"."id" AS "b0__id","b0"."name" AS "b0__name","a1"."id" AS "a1__id","a1"."name" AS "a1__name" FROM "b" AS "b0" LEFT JOIN "a" AS "a1" ON "b0"."id" = "a1"."id_b"  ORDER BY "b0"."id" DESC,"a1"."id" DESC LIMIT 10"#,
+        r#"DELETE FROM "a" WHERE "id"=?;DELETE FROM "b" WHERE "id"=?;"#,
+        r#"INSERT INTO "b" ("id","name") VALUES (?,?);INSERT INTO "a" ("id","name","id_b") VALUES (?,?,?);"#,
+        r#"UPDATE "b" SET "id"=?,"name"=? WHERE "id"=?;UPDATE "a" SET "id"=?,"name"=? WHERE "id"=?;"#,
+      ],
+    ),
+  ] {
+    buffer.clear();
+    b_table_defs
+      .write_select(&mut buffer, (SelectOrderBy::Ascending, &[]), SelectLimit::All, ty, &mut |_| {
+        Ok(())
+      })
+      .unwrap();
+    assert_eq!(&buffer, select_all);
+
+    buffer.clear();
+    b_table_defs
+      .write_select(
+        &mut buffer,
+        (SelectOrderBy::Descending, &[]),
+        SelectLimit::Count(10),
+        ty,
+        &mut |_| Ok(()),
+      )
+      .unwrap();
+    assert_eq!(&buffer, select_count);
+
+    buffer.clear();
+    let mut bindings = SqlBindings::new(ty);
+    b_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+    assert_eq!(&buffer, delete);
+
+    buffer.clear();
+    let mut bindings = SqlBindings::new(ty);
+    b_table_defs
+      .write_insert::<InitialInsertValue>(
+        &mut <_>::default(),
+        (&mut buffer, &mut bindings),
+        &mut None,
+      )
+      .unwrap();
+    assert_eq!(&buffer, insert);
+
+    buffer.clear();
+    let mut bindings = SqlBindings::new(ty);
+    b_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+    assert_eq!(&buffer, update);
+  }
+}
+
 #[test]
 fn standalone_table_has_correct_statements() {
   let mut buffer = String::new();
   let mut a_table_defs = TableParams::<A>::default();
   a_table_defs
-    .write_select(&mut buffer, SelectOrderBy::Ascending, SelectLimit::All, &mut |_| Ok(()))
+    .write_select(
+      &mut buffer,
+      (SelectOrderBy::Ascending, &[]),
+      SelectLimit::All,
+      DatabaseTy::Postgres,
+      &mut |_| Ok(()),
+    )
     .unwrap();
   assert_eq!(
     &buffer,
-    r#"SELECT "a0".id AS a0__id,"a0".name AS a0__name FROM "a" AS "a0"  ORDER BY "a0".id ASC LIMIT ALL"#
+    r#"SELECT "a0"."id" AS "a0__id","a0"."name" AS "a0__name" FROM "a" AS "a0"  ORDER BY "a0"."id" ASC LIMIT ALL"#
   );
 
   a_table_defs.update_all_table_fields(&A);
 
   buffer.clear();
-  a_table_defs.write_delete(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"DELETE FROM a WHERE id='1';"#);
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  a_table_defs.write_delete(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(&buffer, r#"DELETE FROM "a" WHERE "id"=$1;"#);
+  assert_eq!(bindings.params(), &[SqlParam::I32(1)]);
 
   buffer.clear();
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
   a_table_defs
-    .write_insert::<InitialInsertValue>(&mut <_>::default(), &mut buffer, &mut None)
+    .write_insert::<InitialInsertValue>(
+      &mut <_>::default(),
+      (&mut buffer, &mut bindings),
+      &mut None,
+    )
     .unwrap();
-  assert_eq!(&buffer, r#"INSERT INTO "a" (id,name) VALUES ('1','foo1');"#);
+  assert_eq!(&buffer, r#"INSERT INTO "a" ("id","name") VALUES ($1,$2);"#);
+  assert_eq!(bindings.params(), &[SqlParam::I32(1), SqlParam::Str("foo1")]);
 
   buffer.clear();
-  a_table_defs.write_update(&mut <_>::default(), &mut buffer).unwrap();
-  assert_eq!(&buffer, r#"UPDATE a SET id='1',name='foo1' WHERE id='1';"#);
+  let mut bindings = SqlBindings::new(DatabaseTy::Postgres);
+  a_table_defs.write_update(&mut <_>::default(), (&mut buffer, &mut bindings)).unwrap();
+  assert_eq!(&buffer, r#"UPDATE "a" SET "id"=$1,"name"=$2 WHERE "id"=$3;"#);
+  assert_eq!(bindings.params(), &[SqlParam::I32(1), SqlParam::Str("foo1"), SqlParam::I32(1)]);
+}
+
+#[test]
+fn referred_table_has_dialect_specific_upserts() {
+  let mut buffer = String::new();
+  let mut b_table_defs = TableParams::<B>::default();
+  b_table_defs.update_all_table_fields(&B);
+  for (ty, expected) in [
+    (
+      DatabaseTy::Mssql,
+      r#"MERGE INTO [b] AS [target] USING (VALUES (@P1,@P2)) AS [source] ([id],[name]) ON [target].[id] = [source].[id] WHEN MATCHED THEN UPDATE SET [name]=[source].[name] WHEN NOT MATCHED THEN INSERT ([id],[name]) VALUES ([source].[id],[source].[name]);MERGE INTO [a] AS [target] USING (VALUES (@P1,@P2,@P3)) AS [source] ([id],[name],[id_b]) ON [target].[id] = [source].[id] WHEN MATCHED THEN UPDATE SET [name]=[source].[name],[id_b]=[source].[id_b] WHEN NOT MATCHED THEN INSERT ([id],[name],[id_b]) VALUES ([source].[id],[source].[name],[source].[id_b]);"#,
+    ),
+    (
+      DatabaseTy::MySql,
+      r#"INSERT INTO `b` (`id`,`name`) VALUES (?,?) ON DUPLICATE KEY UPDATE `name`=VALUES(`name`);INSERT INTO `a` (`id`,`name`,`id_b`) VALUES (?,?,?) ON DUPLICATE KEY UPDATE `name`=VALUES(`name`),`id_b`=VALUES(`id_b`);"#,
+    ),
+    (
+      DatabaseTy::Postgres,
+      r#"INSERT INTO "b" ("id","name") VALUES ($1,$2) ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name";INSERT INTO "a" ("id","name","id_b") VALUES ($1,$2,$3) ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name","id_b"=EXCLUDED."id_b";"#,
+    ),
+    (
+      DatabaseTy::Sqlite,
+      r#"INSERT INTO "b" ("id","name") VALUES (?,?) ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name";INSERT INTO "a" ("id","name","id_b") VALUES (?,?,?) ON CONFLICT ("id") DO UPDATE SET "name"=EXCLUDED."name","id_b"=EXCLUDED."id_b";"#,
+    ),
+  ] {
+    buffer.clear();
+    let mut bindings = SqlBindings::new(ty);
+    b_table_defs
+      .write_upsert::<InitialInsertValue>(
+        &mut <_>::default(),
+        (&mut buffer, &mut bindings),
+        &mut None,
+      )
+      .unwrap();
+    assert_eq!(&buffer, expected);
+    assert_eq!(
+      bindings.params(),
+      &[
+        SqlParam::I32(2),
+        SqlParam::Str("foo2"),
+        SqlParam::I32(1),
+        SqlParam::Str("foo1"),
+        SqlParam::I32(2)
+      ]
+    );
+  }
 }
@@ -0,0 +1,88 @@
+use crate::{
+  orm::{write_escaped_identifier, write_escaped_literal, SqlValue},
+  DatabaseTy,
+};
+use alloc::string::String;
+
+#[test]
+fn identifiers_are_quoted_according_to_the_database() {
+  let mut buffer = String::new();
+  for (ty, expected) in [
+    (DatabaseTy::Mssql, "[a]]b\"c`d]"),
+    (DatabaseTy::MySql, "`a]b\"c``d`"),
+    (DatabaseTy::Postgres, "\"a]b\"\"c`d\""),
+    (DatabaseTy::Sqlite, "\"a]b\"\"c`d\""),
+  ] {
+    write_escaped_identifier(&mut buffer, format_args!("a]b\"c`d"), ty).unwrap();
+    assert_eq!(buffer, expected);
+    buffer.clear();
+  }
+}
+
+#[test]
+fn literals_are_escaped_according_to_the_database() {
+  let mut buffer = String::new();
+  for (ty, expected) in [
+    (DatabaseTy::Mssql, r"N'O''Brien\'' OR 1=1; --'"),
+    (DatabaseTy::MySql, r"'O''Brien\\'' OR 1=1; --'"),
+    (DatabaseTy::Postgres, r"'O''Brien\'' OR 1=1; --'"),
+    (DatabaseTy::Sqlite, r"'O''Brien\'' OR 1=1; --'"),
+  ] {
+    write_escaped_literal(&mut buffer, format_args!(r"O'Brien\' OR 1=1; --"), ty).unwrap();
+    assert_eq!(buffer, expected);
+    buffer.clear();
+  }
+}
+
+#[test]
+fn sql_values_are_written_according_to_the_database() {
+  let mut buffer = String::new();
+  SqlValue::<crate::Error>::write(&true, &mut buffer, DatabaseTy::Mssql).unwrap();
+  SqlValue::<crate::Error>::write(&false, &mut buffer, DatabaseTy::Postgres).unwrap();
+  SqlValue::<crate::Error>::write(&None::<i32>, &mut buffer, DatabaseTy::Postgres).unwrap();
+  SqlValue::<crate::Error>::write(&-7i64, &mut buffer, DatabaseTy::Postgres).unwrap();
+  SqlValue::<crate::Error>::write(&"it's", &mut buffer, DatabaseTy::Mssql).unwrap();
+  SqlValue::<crate::Error>::write(&String::from("it's"), &mut buffer, DatabaseTy::Sqlite).unwrap();
+  assert_eq!(buffer, "1falsenull-7N'it''s''it''s'");
+}
+
+#[test]
+fn bytes_and_floats_are_written_according_to_the_database() {
+  let mut buffer = String::new();
+  for (ty, expected) in [
+    (DatabaseTy::Mssql, "0x0aff1.5"),
+    (DatabaseTy::MySql, "X'0aff'1.5"),
+    (DatabaseTy::Postgres, r"'\x0aff'1.5"),
+    (DatabaseTy::Sqlite, "X'0aff'1.5"),
+  ] {
+    SqlValue::<crate::Error>::write(&&[10u8, 255][..], &mut buffer, ty).unwrap();
+    SqlValue::<crate::Error>::write(&1.5f32, &mut buffer, ty).unwrap();
+    assert_eq!(buffer, expected);
+    buffer.clear();
+  }
+  SqlValue::<crate::Error>::write(&f64::NAN, &mut buffer, DatabaseTy::Postgres).unwrap();
+  SqlValue::<crate::Error>::write(&f64::NEG_INFINITY, &mut buffer, DatabaseTy::Postgres).unwrap();
+  assert_eq!(buffer, "'NaN''-Infinity'");
+  let rslt = SqlValue::<crate::Error>::write(&f32::INFINITY, &mut buffer, DatabaseTy::MySql);
+  assert!(matches!(rslt, Err(crate::Error::NonFiniteFloat)));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn dates_are_written_according_to_the_database() {
+  use chrono::{NaiveDate, TimeZone, Utc};
+  let mut buffer = String::new();
+  let date = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+  let date_time = Utc.with_ymd_and_hms(2023, 1, 2, 3, 4, 5).unwrap();
+  for (ty, expected) in [
+    (DatabaseTy::Mssql, "N'2023-01-02'N'2023-01-02 03:04:05+00:00'"),
+    (DatabaseTy::MySql, "'2023-01-02''2023-01-02 03:04:05'"),
+    (DatabaseTy::Postgres, "'2023-01-02''2023-01-02 03:04:05+00:00'"),
+    (DatabaseTy::Sqlite, "'2023-01-02''2023-01-02T03:04:05+00:00'"),
+  ] {
+    SqlValue::<crate::Error>::write(&date, &mut buffer, ty).unwrap();
+    SqlValue::<crate::Error>::write(&date_time, &mut buffer, ty).unwrap();
+    assert_eq!(buffer, expected);
+    buffer.clear();
+  }
+}